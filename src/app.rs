@@ -1,22 +1,36 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
+use futures_util::FutureExt;
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Instant;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, Mutex, Notify};
 use tracing::{debug, error, info, warn};
 
 use crate::audio::{
     capture::RecordingSession, AudioCapture, AudioFeedback, CapturedAudio, FastVad, FastVadOutcome,
 };
 use crate::benchmark::BenchmarkRecorder;
-use crate::config::{Config, ConfigManager, ShortcutsConfig, TranscriptionProvider};
-use crate::control::{ControlRequest, ControlServer, RecordCommand, RecorderState};
-use crate::input::{InputManagerHandle, ShortcutEvent, ShortcutKind, ShortcutPhase, TextInjector};
+use crate::config::{
+    AppProfileConfig, Config, ConfigManager, ShortcutAction, ShortcutBindingConfig, ShortcutSource,
+    ShortcutsConfig, TranscriptionProvider,
+};
+use crate::control::{ControlCommand, ControlRequest, ControlServer, RecordCommand, RecorderState};
+use crate::input::{
+    InputManagerHandle, ShortcutEvent, ShortcutKind, ShortcutPhase, ShortcutTriggerSender,
+    TextInjector,
+};
+use crate::profiles::{apply_profile, match_profile};
 use crate::status::{StatusWriter, WaybarState};
-use crate::text::NormalizeTextService;
-use crate::transcription::{TranscriptionBackend, TranscriptionResult};
+use crate::streaming::{self, StreamingDictation};
+use crate::text::{InjectionContext, LlmPostProcessor, NormalizeTextService};
+use crate::transcription::{
+    AudioLevel, HallucinationFilter, TranscribeOptions, Transcriber, TranscriberRegistry,
+    TranscriptionResult,
+};
 use crate::whisper::WhisperVadOptions;
 
-fn resample_audio(samples: &[f32], src_rate: u32, dst_rate: u32) -> Vec<f32> {
+pub(crate) fn resample_audio(samples: &[f32], src_rate: u32, dst_rate: u32) -> Vec<f32> {
     if samples.is_empty() || src_rate == 0 || dst_rate == 0 {
         return Vec::new();
     }
@@ -70,6 +84,53 @@ struct PreprocessedAudio {
     report: Option<FastVadSummary>,
 }
 
+/// Backend, normalizer and request options a recording runs with. They
+/// differ from the global ones when an application profile or a shortcut
+/// binding applied at start.
+#[derive(Clone)]
+pub(crate) struct RecordingProfile {
+    /// The config with the profile applied.
+    pub(crate) config: Arc<Config>,
+    pub(crate) transcriber: Arc<dyn Transcriber>,
+    pub(crate) text_normalizer: NormalizeTextService,
+    pub(crate) options: TranscribeOptions,
+}
+
+/// Puts the binding's provider override on top of `profile`. The result is
+/// named apart from the plain profile so their cached normalizers stay
+/// separate.
+fn with_binding_provider(
+    profile: Option<AppProfileConfig>,
+    binding: Option<&ShortcutBindingConfig>,
+) -> Option<(String, AppProfileConfig)> {
+    let Some((binding, provider)) =
+        binding.and_then(|binding| Some((binding, binding.provider.clone()?)))
+    else {
+        return profile.map(|profile| (profile.name.clone(), profile));
+    };
+
+    let mut profile = profile.unwrap_or_default();
+    let name = if profile.name.is_empty() {
+        format!("shortcut:{}", binding.name)
+    } else {
+        format!("{}+shortcut:{}", profile.name, binding.name)
+    };
+    info!(
+        "🎯 Shortcut '{}' records with {}",
+        binding.name,
+        provider.label()
+    );
+    profile.provider = Some(provider);
+    Some((name, profile))
+}
+
+/// Provider picked by a `switch_provider` shortcut. It survives config
+/// reloads until the config file itself selects a different provider.
+struct ProviderSwitch {
+    configured: TranscriptionProvider,
+    active: TranscriptionProvider,
+}
+
 fn build_vad_options(config_manager: &ConfigManager, config: &Config) -> WhisperVadOptions {
     let whisper_vad = &config.transcription.whisper_cpp.vad;
     WhisperVadOptions {
@@ -84,6 +145,8 @@ fn build_vad_options(config_manager: &ConfigManager, config: &Config) -> Whisper
     }
 }
 
+/// Whether fast VAD trims a recording made with `config`, the effective
+/// config of its profile. whisper-cli VAD does the trimming itself.
 fn fast_vad_allowed(config: &Config) -> bool {
     if !config.fast_vad.enabled {
         return false;
@@ -102,25 +165,51 @@ pub struct HyprwhsprApp {
     config_manager: ConfigManager,
     audio_capture: AudioCapture,
     audio_feedback: AudioFeedback,
-    transcriber: TranscriptionBackend,
+    transcriber_registry: TranscriberRegistry,
+    transcriber: Arc<dyn Transcriber>,
+    /// Backends for providers other than the active one, shared by the
+    /// profiles and shortcut bindings that select them.
+    provider_transcribers: HashMap<TranscriptionProvider, Arc<dyn Transcriber>>,
+    profile_normalizers: HashMap<String, NormalizeTextService>,
+    provider_switch: Option<ProviderSwitch>,
     fast_vad: Option<FastVad>,
     text_injector: Arc<Mutex<TextInjector>>,
     text_normalizer: NormalizeTextService,
+    post_processor: Option<LlmPostProcessor>,
+    hallucination_filter: HallucinationFilter,
     status_writer: StatusWriter,
     shortcut_tx: mpsc::Sender<ShortcutEvent>,
     shortcut_rx: Option<mpsc::Receiver<ShortcutEvent>>,
+    /// Signalled by cancel shortcuts ahead of the run loop, which is still
+    /// awaiting the transcription they abort. The permit is kept until the
+    /// processing awaits it and cleared when the next processing starts.
+    abort: Arc<Notify>,
     control_tx: mpsc::Sender<ControlRequest>,
     control_rx: Option<mpsc::Receiver<ControlRequest>>,
     input_manager: Option<InputManagerHandle>,
+    /// Where `shortcut press|release` control commands go; swapped along
+    /// with `input_manager`.
+    shortcut_triggers: Arc<std::sync::Mutex<Option<ShortcutTriggerSender>>>,
     current_config: Config,
     recording_session: Option<RecordingSession>,
     recording_trigger: Option<RecordingTrigger>,
+    streaming: Option<StreamingDictation>,
+    recording_profile: Option<RecordingProfile>,
     benchmark: Option<BenchmarkRecorder>,
     is_processing: bool,
 }
 
 impl HyprwhsprApp {
     pub fn new(config_manager: ConfigManager) -> Result<Self> {
+        Self::with_registry(config_manager, TranscriberRegistry::default())
+    }
+
+    /// Builds the app with a caller-supplied backend registry, letting embedders
+    /// add transcription providers beyond the built-in ones.
+    pub fn with_registry(
+        config_manager: ConfigManager,
+        transcriber_registry: TranscriberRegistry,
+    ) -> Result<Self> {
         let config = config_manager.get();
 
         let audio_capture =
@@ -132,23 +221,23 @@ impl HyprwhsprApp {
             assets_dir,
             config.start_sound_path.clone(),
             config.stop_sound_path.clone(),
+            config.cancel_sound_path.clone(),
             config.start_sound_volume,
             config.stop_sound_volume,
         );
 
         let vad_options = build_vad_options(&config_manager, &config);
 
-        let transcriber = TranscriptionBackend::build(&config_manager, &config, vad_options)
-            .context("Failed to configure transcription backend")?;
+        let transcriber: Arc<dyn Transcriber> = transcriber_registry
+            .build(&config_manager, &config, vad_options)
+            .context("Failed to configure transcription backend")?
+            .into();
 
         transcriber
             .initialize()
             .context("Failed to initialize transcription backend")?;
 
-        info!(
-            "🎯 Active transcription backend: {}",
-            transcriber.provider().label()
-        );
+        info!("🎯 Active transcription backend: {}", transcriber.label());
 
         let text_injector = TextInjector::new(
             config.shift_paste,
@@ -157,7 +246,11 @@ impl HyprwhsprApp {
             config.paste_hints.shift_insert.clone(),
             config.auto_copy_clipboard,
         )?;
-        let text_normalizer = NormalizeTextService::new(config.word_overrides.clone());
+        let text_normalizer = NormalizeTextService::from_config(&config)?;
+        let post_processor = LlmPostProcessor::from_config(&config.post_processing)
+            .context("Failed to configure post-processing")?;
+        let hallucination_filter =
+            HallucinationFilter::new(&config.transcription.hallucination_filter);
 
         let status_writer = StatusWriter::new()?;
         status_writer.set_state(WaybarState::Inactive, "Ready")?;
@@ -165,18 +258,11 @@ impl HyprwhsprApp {
         let (shortcut_tx, shortcut_rx) = mpsc::channel(10);
         let (control_tx, control_rx) = mpsc::channel(10);
 
-        let fast_vad = if fast_vad_allowed(&config) {
-            FastVad::maybe_new(&config.fast_vad, audio_capture.sample_rate_hint())
-                .context("Failed to initialize fast VAD pipeline")?
-        } else {
-            if config.fast_vad.enabled
-                && config.transcription.provider == TranscriptionProvider::WhisperCpp
-                && config.transcription.whisper_cpp.vad.enabled
-            {
-                info!("⚡ Earshot fast VAD disabled because whisper-cli VAD is active");
-            }
-            None
-        };
+        let fast_vad = FastVad::maybe_new(&config.fast_vad, audio_capture.sample_rate_hint())
+            .context("Failed to initialize fast VAD pipeline")?;
+        if config.fast_vad.enabled && !fast_vad_allowed(&config) {
+            info!("⚡ Earshot fast VAD skipped while whisper-cli VAD is active");
+        }
 
         if let Some(vad) = &fast_vad {
             info!(
@@ -190,19 +276,29 @@ impl HyprwhsprApp {
             config_manager,
             audio_capture,
             audio_feedback,
+            transcriber_registry,
             transcriber,
+            provider_transcribers: HashMap::new(),
+            profile_normalizers: HashMap::new(),
+            provider_switch: None,
             fast_vad,
             text_injector: Arc::new(Mutex::new(text_injector)),
             text_normalizer,
+            post_processor,
+            hallucination_filter,
             status_writer,
             shortcut_tx,
             shortcut_rx: Some(shortcut_rx),
+            abort: Arc::new(Notify::new()),
             control_tx,
             control_rx: Some(control_rx),
             input_manager: None,
+            shortcut_triggers: Arc::new(std::sync::Mutex::new(None)),
             current_config: config,
             recording_session: None,
             recording_trigger: None,
+            streaming: None,
+            recording_profile: None,
             benchmark: None,
             is_processing: false,
         })
@@ -211,14 +307,16 @@ impl HyprwhsprApp {
     pub async fn run(&mut self) -> Result<()> {
         info!("🚀 hyprwhspr running!");
 
-        let mut shortcut_rx = self
+        let input_rx = self
             .shortcut_rx
             .take()
             .expect("shortcut receiver already consumed");
-        let mut control_rx = self
+        let mut shortcut_rx = self.forward_shortcuts(input_rx);
+        let control_rx = self
             .control_rx
             .take()
             .expect("control receiver already consumed");
+        let mut control_rx = self.forward_control(control_rx);
         let _control_server = ControlServer::spawn(self.control_tx.clone())?;
         self.ensure_input_manager(self.current_config.shortcuts.clone())?;
         self.log_shortcut_configuration(&self.current_config.shortcuts);
@@ -269,15 +367,77 @@ impl HyprwhsprApp {
         Ok(())
     }
 
+    /// Relays shortcut events to the run loop, signalling cancel shortcuts on
+    /// the way so they can abort processing the loop is blocked on.
+    fn forward_shortcuts(
+        &self,
+        mut input_rx: mpsc::Receiver<ShortcutEvent>,
+    ) -> mpsc::Receiver<ShortcutEvent> {
+        let (tx, rx) = mpsc::channel(10);
+        let abort = Arc::clone(&self.abort);
+        tokio::spawn(async move {
+            while let Some(event) = input_rx.recv().await {
+                if event.kind == ShortcutKind::Cancel && event.phase == ShortcutPhase::Start {
+                    abort.notify_one();
+                }
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+        rx
+    }
+
+    /// Relays control requests to the run loop, handing shortcut triggers to
+    /// the input manager first so a cancel can abort processing in flight.
+    fn forward_control(
+        &self,
+        mut control_rx: mpsc::Receiver<ControlRequest>,
+    ) -> mpsc::Receiver<ControlRequest> {
+        let (tx, rx) = mpsc::channel(10);
+        let triggers = Arc::clone(&self.shortcut_triggers);
+        tokio::spawn(async move {
+            while let Some(request) = control_rx.recv().await {
+                if let ControlCommand::Shortcut(trigger) = &request.command {
+                    let sender = triggers
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .clone();
+                    let result = match sender {
+                        Some(sender) => sender.send(trigger.clone()).await,
+                        None => Err(anyhow!("input manager is not running")),
+                    };
+                    if let Err(err) = result {
+                        if request.reply_tx.send(Err(format!("{err:#}"))).is_err() {
+                            debug!("Control client disconnected before receiving response");
+                        }
+                        continue;
+                    }
+                }
+                if tx.send(request).await.is_err() {
+                    break;
+                }
+            }
+        });
+        rx
+    }
+
     fn ensure_input_manager(&mut self, shortcuts: ShortcutsConfig) -> Result<()> {
-        if let Some(manager) = &self.input_manager {
-            manager.update_shortcuts(shortcuts)?;
-        } else {
-            self.input_manager = Some(InputManagerHandle::spawn(
-                shortcuts,
-                self.shortcut_tx.clone(),
-            )?);
+        if let Some(manager) = &mut self.input_manager {
+            if manager.source() == shortcuts.source {
+                return manager.update_shortcuts(shortcuts);
+            }
+            info!("Shortcut source changed; restarting input manager");
+            manager.stop();
+            self.input_manager = None;
         }
+
+        let manager = InputManagerHandle::spawn(shortcuts, self.shortcut_tx.clone())?;
+        *self
+            .shortcut_triggers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(manager.trigger_sender());
+        self.input_manager = Some(manager);
         Ok(())
     }
 
@@ -287,8 +447,16 @@ impl HyprwhsprApp {
         }
     }
 
-    fn apply_config_update(&mut self, new_config: Config) -> Result<()> {
+    fn apply_config_update(&mut self, mut new_config: Config) -> Result<()> {
         tracing::debug!(?new_config, "Apply config update requested");
+        let keeps_switch = match &self.provider_switch {
+            Some(switch) if new_config.transcription.provider == switch.configured => {
+                new_config.transcription.provider = switch.active.clone();
+                true
+            }
+            _ => false,
+        };
+
         if new_config == self.current_config {
             tracing::debug!("Config unchanged; ignoring update");
             return Ok(());
@@ -299,12 +467,30 @@ impl HyprwhsprApp {
             return Ok(());
         }
 
+        if let Some(switch) = &self.provider_switch {
+            if keeps_switch {
+                info!(
+                    "🔀 Keeping {} from the provider switch shortcut over the configured {}",
+                    switch.active.label(),
+                    switch.configured.label()
+                );
+            } else {
+                info!(
+                    "Config now selects {}; dropping the provider switch to {}",
+                    new_config.transcription.provider.label(),
+                    switch.active.label()
+                );
+                self.provider_switch = None;
+            }
+        }
+
         let assets_dir = self.config_manager.get_assets_dir();
         let audio_feedback = AudioFeedback::new(
             new_config.audio_feedback,
             assets_dir,
             new_config.start_sound_path.clone(),
             new_config.stop_sound_path.clone(),
+            new_config.cancel_sound_path.clone(),
             new_config.start_sound_volume,
             new_config.stop_sound_volume,
         );
@@ -316,29 +502,43 @@ impl HyprwhsprApp {
             new_config.paste_hints.shift_insert.clone(),
             new_config.auto_copy_clipboard,
         )?;
-        let text_normalizer = NormalizeTextService::new(new_config.word_overrides.clone());
-
-        let transcriber_changed =
-            TranscriptionBackend::needs_refresh(&self.current_config, &new_config);
+        let text_normalizer = NormalizeTextService::from_config(&new_config)?;
+        let post_processor = LlmPostProcessor::from_config(&new_config.post_processing)
+            .context("Failed to configure post-processing")?;
+        let hallucination_filter =
+            HallucinationFilter::new(&new_config.transcription.hallucination_filter);
+
+        let transcriber_changed = TranscriberRegistry::needs_rebuild(
+            self.transcriber.as_ref(),
+            &self.current_config,
+            &new_config,
+        );
 
         if self.current_config.audio_device != new_config.audio_device {
             self.audio_capture
                 .update_preferred_device(new_config.audio_device);
         }
 
+        // Profile backends were built from the previous config. Stop them, and
+        // the active backend when it is replaced, before building the new one
+        // so e.g. a whisper-server frees its port first.
+        for backend in self.provider_transcribers.values() {
+            backend.shutdown();
+        }
+        self.provider_transcribers.clear();
+
         if transcriber_changed {
+            self.transcriber.shutdown();
             let vad_options = build_vad_options(&self.config_manager, &new_config);
-            let backend =
-                TranscriptionBackend::build(&self.config_manager, &new_config, vad_options)
-                    .context("Failed to reconfigure transcription backend")?;
+            let backend = self
+                .transcriber_registry
+                .build(&self.config_manager, &new_config, vad_options)
+                .context("Failed to reconfigure transcription backend")?;
             backend
                 .initialize()
                 .context("Failed to initialize updated transcription backend")?;
-            info!(
-                "🎯 Active transcription backend: {}",
-                backend.provider().label()
-            );
-            self.transcriber = backend;
+            info!("🎯 Active transcription backend: {}", backend.label());
+            self.transcriber = backend.into();
         }
 
         let shortcuts_changed =
@@ -349,27 +549,12 @@ impl HyprwhsprApp {
             self.log_shortcut_configuration(&new_config.shortcuts);
         }
 
-        let fast_vad_was_allowed = fast_vad_allowed(&self.current_config);
-        let fast_vad_is_allowed = fast_vad_allowed(&new_config);
-
-        if !fast_vad_is_allowed {
-            let conflict_with_whisper = new_config.fast_vad.enabled
-                && new_config.transcription.provider == TranscriptionProvider::WhisperCpp
-                && new_config.transcription.whisper_cpp.vad.enabled;
-
-            if conflict_with_whisper {
-                info!("⚡ Earshot fast VAD disabled because whisper-cli VAD is active");
-            } else if self.fast_vad.is_some()
-                || (fast_vad_was_allowed && self.current_config.fast_vad.enabled)
-            {
+        if !new_config.fast_vad.enabled {
+            if self.fast_vad.is_some() {
                 info!("⚡ Earshot fast VAD disabled");
             }
-
             self.fast_vad = None;
-        } else if !fast_vad_was_allowed
-            || self.current_config.fast_vad != new_config.fast_vad
-            || self.fast_vad.is_none()
-        {
+        } else if self.current_config.fast_vad != new_config.fast_vad || self.fast_vad.is_none() {
             self.fast_vad =
                 FastVad::maybe_new(&new_config.fast_vad, self.audio_capture.sample_rate_hint())
                     .context("Failed to refresh fast VAD pipeline")?;
@@ -382,8 +567,18 @@ impl HyprwhsprApp {
             }
         }
 
+        if new_config.fast_vad.enabled
+            && !fast_vad_allowed(&new_config)
+            && fast_vad_allowed(&self.current_config)
+        {
+            info!("⚡ Earshot fast VAD skipped while whisper-cli VAD is active");
+        }
+
+        self.profile_normalizers.clear();
         self.text_injector = Arc::new(Mutex::new(text_injector));
         self.text_normalizer = text_normalizer;
+        self.post_processor = post_processor;
+        self.hallucination_filter = hallucination_filter;
         self.audio_feedback = audio_feedback;
         self.current_config = new_config;
 
@@ -393,6 +588,10 @@ impl HyprwhsprApp {
     }
 
     fn log_shortcut_configuration(&self, shortcuts: &ShortcutsConfig) {
+        if shortcuts.source == ShortcutSource::Compositor {
+            info!("Shortcut keys come from compositor binds");
+        }
+
         match shortcuts.press.as_deref() {
             Some(value) => info!("Press shortcut active: {}", value),
             None => info!("Press shortcut disabled"),
@@ -402,19 +601,58 @@ impl HyprwhsprApp {
             Some(value) => info!("Hold shortcut active: {}", value),
             None => info!("Hold shortcut disabled"),
         }
+
+        for binding in &shortcuts.bindings {
+            info!(
+                "Shortcut '{}' active: {} ({:?})",
+                binding.name, binding.shortcut, binding.action
+            );
+        }
     }
 
     async fn handle_shortcut(&mut self, event: ShortcutEvent) -> Result<()> {
-        match (event.kind, event.phase) {
-            (ShortcutKind::Press, ShortcutPhase::Start) => {
-                self.toggle_recording(RecordingTrigger::PressShortcut, event.triggered_at)
-                    .await?;
+        let binding = match event.binding.as_deref() {
+            Some(name) => {
+                let Some(binding) = self
+                    .current_config
+                    .shortcuts
+                    .bindings
+                    .iter()
+                    .find(|binding| binding.name == name)
+                    .cloned()
+                else {
+                    debug!("Shortcut '{}' is no longer configured", name);
+                    return Ok(());
+                };
+                Some(binding)
             }
-            (ShortcutKind::Hold, ShortcutPhase::Start) => {
-                self.start_recording_if_idle(RecordingTrigger::HoldShortcut, event.triggered_at)
-                    .await?;
+            None => None,
+        };
+        let action = match (&binding, event.kind) {
+            (Some(binding), _) => binding.action,
+            (None, ShortcutKind::Hold) => ShortcutAction::Hold,
+            (None, ShortcutKind::Press) => ShortcutAction::Toggle,
+            (None, ShortcutKind::Cancel) => ShortcutAction::Cancel,
+        };
+
+        match (action, event.phase) {
+            (ShortcutAction::Toggle, ShortcutPhase::Start) => {
+                self.toggle_recording(
+                    RecordingTrigger::PressShortcut,
+                    event.triggered_at,
+                    binding.as_ref(),
+                )
+                .await?;
+            }
+            (ShortcutAction::Hold, ShortcutPhase::Start) => {
+                self.start_recording_if_idle(
+                    RecordingTrigger::HoldShortcut,
+                    event.triggered_at,
+                    binding.as_ref(),
+                )
+                .await?;
             }
-            (ShortcutKind::Hold, ShortcutPhase::End) => {
+            (ShortcutAction::Hold, ShortcutPhase::End) => {
                 if matches!(self.recording_trigger, Some(RecordingTrigger::HoldShortcut))
                     && self.recording_session.is_some()
                 {
@@ -423,7 +661,7 @@ impl HyprwhsprApp {
                     debug!("Hold release ignored (no active hold-triggered recording)");
                 }
             }
-            (ShortcutKind::Hold, ShortcutPhase::Cancel) => {
+            (ShortcutAction::Hold, ShortcutPhase::Cancel) => {
                 if self.recording_session.is_some() {
                     warn!("Input device changed while recording; stopping active recording");
                     self.stop_recording(event.triggered_at).await?;
@@ -431,17 +669,83 @@ impl HyprwhsprApp {
                     debug!("Input device cancel ignored (no active recording)");
                 }
             }
+            (ShortcutAction::Cancel, ShortcutPhase::Start) => {
+                self.cancel_recording()?;
+            }
+            (ShortcutAction::Reinject, ShortcutPhase::Start) => {
+                self.text_injector.lock().await.reinject_last().await?;
+            }
+            (ShortcutAction::SwitchProvider, ShortcutPhase::Start) => {
+                match binding.and_then(|binding| binding.provider) {
+                    Some(provider) => self.switch_provider(provider)?,
+                    None => warn!(
+                        "Shortcut '{}' has no provider to switch to",
+                        event.binding.as_deref().unwrap_or_default()
+                    ),
+                }
+            }
             _ => {}
         }
 
         Ok(())
     }
 
+    /// Makes `provider` the active backend until the config file selects a
+    /// different provider.
+    fn switch_provider(&mut self, provider: TranscriptionProvider) -> Result<()> {
+        if self.recording_session.is_some() || self.is_processing {
+            warn!("Still recording or processing, ignoring provider switch");
+            return Ok(());
+        }
+        if self.current_config.transcription.provider == provider {
+            debug!(
+                "Provider switch ignored; {} is already active",
+                provider.label()
+            );
+            return Ok(());
+        }
+
+        let configured = match &self.provider_switch {
+            Some(switch) => switch.configured.clone(),
+            None => self.current_config.transcription.provider.clone(),
+        };
+        let mut new_config = self.current_config.clone();
+        new_config.transcription.provider = provider.clone();
+        // A profile may already run this provider; take over its backend.
+        let backend = match self.provider_transcribers.remove(&provider) {
+            Some(backend) => backend,
+            None => {
+                let vad_options = build_vad_options(&self.config_manager, &new_config);
+                let backend = self
+                    .transcriber_registry
+                    .build(&self.config_manager, &new_config, vad_options)
+                    .context("Failed to build transcription backend")?;
+                backend
+                    .initialize()
+                    .context("Failed to initialize transcription backend")?;
+                backend.into()
+            }
+        };
+        info!("🔀 Switched transcription backend to {}", backend.label());
+        let previous = std::mem::replace(&mut self.transcriber, backend);
+        self.provider_transcribers
+            .insert(previous.provider(), previous);
+
+        self.provider_switch = (provider != configured).then_some(ProviderSwitch {
+            configured,
+            active: provider,
+        });
+        self.current_config = new_config;
+        Ok(())
+    }
+
     async fn handle_control_request(&mut self, request: ControlRequest) {
-        let result = self
-            .execute_record_command(request.command)
-            .await
-            .map_err(|err| format!("{err:#}"));
+        let result = match request.command {
+            ControlCommand::Record(command) => self.execute_record_command(command).await,
+            // Already delivered to the input manager by `forward_control`.
+            ControlCommand::Shortcut(_) => Ok(self.current_state()),
+        }
+        .map_err(|err| format!("{err:#}"));
 
         if request.reply_tx.send(result).is_err() {
             debug!("Control client disconnected before receiving response");
@@ -453,14 +757,14 @@ impl HyprwhsprApp {
 
         match command {
             RecordCommand::Start => {
-                self.start_recording_if_idle(RecordingTrigger::ExternalCommand, now)
+                self.start_recording_if_idle(RecordingTrigger::ExternalCommand, now, None)
                     .await?;
             }
             RecordCommand::Stop => {
                 self.stop_recording_if_active(now).await?;
             }
             RecordCommand::Toggle => {
-                self.toggle_recording(RecordingTrigger::ExternalCommand, now)
+                self.toggle_recording(RecordingTrigger::ExternalCommand, now, None)
                     .await?;
             }
             RecordCommand::Status => {}
@@ -483,6 +787,7 @@ impl HyprwhsprApp {
         &mut self,
         trigger: RecordingTrigger,
         triggered_at: Instant,
+        binding: Option<&ShortcutBindingConfig>,
     ) -> Result<()> {
         if self.is_processing {
             warn!("Still processing previous recording, ignoring start request");
@@ -494,7 +799,7 @@ impl HyprwhsprApp {
             return Ok(());
         }
 
-        self.start_recording(trigger, triggered_at).await
+        self.start_recording(trigger, triggered_at, binding).await
     }
 
     async fn stop_recording_if_active(&mut self, triggered_at: Instant) -> Result<()> {
@@ -511,6 +816,7 @@ impl HyprwhsprApp {
         &mut self,
         trigger: RecordingTrigger,
         triggered_at: Instant,
+        binding: Option<&ShortcutBindingConfig>,
     ) -> Result<()> {
         if self.is_processing {
             warn!("Still processing previous recording, ignoring toggle request");
@@ -520,7 +826,7 @@ impl HyprwhsprApp {
         if self.recording_session.is_some() {
             self.stop_recording(triggered_at).await?;
         } else {
-            self.start_recording(trigger, triggered_at).await?;
+            self.start_recording(trigger, triggered_at, binding).await?;
         }
 
         Ok(())
@@ -530,9 +836,11 @@ impl HyprwhsprApp {
         &mut self,
         trigger: RecordingTrigger,
         triggered_at: Instant,
+        binding: Option<&ShortcutBindingConfig>,
     ) -> Result<()> {
         info!("🎤 Starting recording...");
 
+        let profile = self.resolve_recording_profile(binding).await;
         self.audio_feedback.play_start_sound()?;
 
         let session = self
@@ -540,22 +848,209 @@ impl HyprwhsprApp {
             .start_recording()
             .context("Failed to start recording")?;
 
+        if self.current_config.streaming.enabled {
+            match streaming::phrase_segmenter(
+                &self.current_config,
+                self.audio_capture.sample_rate_hint(),
+            ) {
+                Ok(segmenter) => {
+                    self.streaming = Some(StreamingDictation::spawn(
+                        session.subscribe_chunks(),
+                        segmenter,
+                        profile.clone(),
+                        self.post_processor.clone(),
+                        self.hallucination_filter.clone(),
+                        Arc::clone(&self.text_injector),
+                    ));
+                    info!("🌊 Streaming transcription active; phrases inject as you pause");
+                }
+                Err(err) => {
+                    warn!("Streaming unavailable, transcribing after recording: {err:#}");
+                }
+            }
+        }
+
         self.recording_session = Some(session);
         self.recording_trigger = Some(trigger);
         self.set_input_app_busy(true);
 
-        let recording_started_at = Instant::now();
-        self.benchmark = Some(BenchmarkRecorder::new(
-            self.transcriber.provider().label().to_string(),
-            triggered_at,
-            recording_started_at,
-        ));
+        // Per-phrase streaming has no single pipeline to benchmark.
+        if self.streaming.is_none() {
+            let recording_started_at = Instant::now();
+            self.benchmark = Some(BenchmarkRecorder::new(
+                profile.transcriber.label().to_string(),
+                triggered_at,
+                recording_started_at,
+            ));
+        }
 
+        self.recording_profile = Some(profile);
         self.status_writer.set_recording(true)?;
 
         Ok(())
     }
 
+    /// Picks the profile the shortcut binding names, or else the one matching
+    /// the focused window, with the binding's provider on top. Falls back to
+    /// the global backend and normalizer when none applies.
+    async fn resolve_recording_profile(
+        &mut self,
+        binding: Option<&ShortcutBindingConfig>,
+    ) -> RecordingProfile {
+        let named = binding.and_then(|binding| {
+            let name = binding.profile.as_deref()?;
+            let profile = self
+                .current_config
+                .profiles
+                .iter()
+                .find(|profile| profile.name == name);
+            match profile {
+                Some(_) => info!(
+                    "🧩 Profile '{}' active for shortcut '{}'",
+                    name, binding.name
+                ),
+                None => warn!(
+                    "Shortcut '{}' names unknown profile '{}'; matching the focused window",
+                    binding.name, name
+                ),
+            }
+            profile.cloned()
+        });
+        let profile = match named {
+            Some(profile) => Some(profile),
+            None => self.window_profile().await,
+        };
+        let Some((name, profile)) = with_binding_provider(profile, binding) else {
+            return self.default_recording_profile();
+        };
+        let effective = apply_profile(&self.current_config, &profile);
+
+        let transcriber = match self.profile_transcriber(&effective) {
+            Ok(transcriber) => transcriber,
+            Err(err) => {
+                warn!(
+                    "Profile '{}' backend unavailable, using the default: {:#}",
+                    name, err
+                );
+                Arc::clone(&self.transcriber)
+            }
+        };
+
+        let text_normalizer = match self.profile_normalizers.get(&name) {
+            Some(text_normalizer) => text_normalizer.clone(),
+            None => match NormalizeTextService::from_config(&effective) {
+                Ok(text_normalizer) => {
+                    self.profile_normalizers
+                        .insert(name, text_normalizer.clone());
+                    text_normalizer
+                }
+                Err(err) => {
+                    warn!(
+                        "Profile '{}' normalization unavailable, using the default: {:#}",
+                        name, err
+                    );
+                    self.text_normalizer.clone()
+                }
+            },
+        };
+
+        let config = Arc::new(effective);
+        RecordingProfile {
+            options: TranscribeOptions::new(Arc::clone(&config)),
+            config,
+            transcriber,
+            text_normalizer,
+        }
+    }
+
+    /// The global backend and normalizer with the current config's prompt and
+    /// language.
+    fn default_recording_profile(&self) -> RecordingProfile {
+        let config = Arc::new(self.current_config.clone());
+        RecordingProfile {
+            options: TranscribeOptions::new(Arc::clone(&config)),
+            config,
+            transcriber: Arc::clone(&self.transcriber),
+            text_normalizer: self.text_normalizer.clone(),
+        }
+    }
+
+    async fn window_profile(&self) -> Option<AppProfileConfig> {
+        if self.current_config.profiles.is_empty() {
+            return None;
+        }
+
+        let class = self
+            .text_injector
+            .lock()
+            .await
+            .active_window_class()
+            .await?;
+        let Some(profile) = match_profile(&self.current_config.profiles, &class) else {
+            debug!("No profile matches window class {}", class);
+            return None;
+        };
+        info!(
+            "🧩 Profile '{}' active for window class {}",
+            profile.name, class
+        );
+        Some(profile.clone())
+    }
+
+    /// Returns the backend for a profile's effective config. Prompt and
+    /// language travel with each request, so only a different provider needs
+    /// a backend of its own, shared by every profile selecting it.
+    fn profile_transcriber(&mut self, effective: &Config) -> Result<Arc<dyn Transcriber>> {
+        if !TranscriberRegistry::needs_rebuild(
+            self.transcriber.as_ref(),
+            &self.current_config,
+            effective,
+        ) {
+            return Ok(Arc::clone(&self.transcriber));
+        }
+        let provider = &effective.transcription.provider;
+        if let Some(transcriber) = self.provider_transcribers.get(provider) {
+            return Ok(Arc::clone(transcriber));
+        }
+
+        let vad_options = build_vad_options(&self.config_manager, effective);
+        let backend: Arc<dyn Transcriber> = self
+            .transcriber_registry
+            .build(&self.config_manager, effective, vad_options)
+            .context("Failed to build profile transcription backend")?
+            .into();
+        backend
+            .initialize()
+            .context("Failed to initialize profile transcription backend")?;
+        info!("🎯 Profile transcription backend: {}", backend.label());
+        self.provider_transcribers
+            .insert(provider.clone(), Arc::clone(&backend));
+        Ok(backend)
+    }
+
+    /// Stops recording and discards the audio without transcribing it.
+    fn cancel_recording(&mut self) -> Result<()> {
+        let Some(session) = self.recording_session.take() else {
+            debug!("Cancel ignored because recording is not active");
+            return Ok(());
+        };
+
+        info!("🚫 Recording cancelled");
+        if let Some(streaming) = self.streaming.take() {
+            streaming.abort();
+        }
+        drop(session.stop().context("Failed to stop recording")?);
+        self.recording_trigger = None;
+        self.recording_profile = None;
+        self.benchmark = None;
+        self.audio_feedback.play_cancel_sound()?;
+        self.set_input_app_busy(false);
+        self.status_writer
+            .set_state(WaybarState::Inactive, "Ready")?;
+
+        Ok(())
+    }
+
     async fn stop_recording(&mut self, triggered_at: Instant) -> Result<()> {
         info!("🛑 Stopping recording...");
 
@@ -568,6 +1063,8 @@ impl HyprwhsprApp {
             benchmark.mark_keybind_stop(triggered_at);
         }
 
+        // Drop a cancel left over from before this processing started.
+        self.abort.notified().now_or_never();
         self.audio_feedback.play_stop_sound()?;
 
         self.status_writer.set_processing()?;
@@ -575,15 +1072,43 @@ impl HyprwhsprApp {
         let captured_audio = session.stop().context("Failed to stop recording")?;
         let stop_timestamp = Instant::now();
         self.recording_trigger = None;
+        let profile = self.recording_profile.take();
 
         if let Some(benchmark) = self.benchmark.as_mut() {
             benchmark.mark_recording_stop(stop_timestamp);
             benchmark.record_original_audio(captured_audio.len(), captured_audio.sample_rate);
         }
 
-        if !captured_audio.is_empty() {
+        let abort = Arc::clone(&self.abort);
+        if let Some(mut streaming) = self.streaming.take() {
             self.is_processing = true;
-            if let Err(e) = self.process_audio(captured_audio).await {
+            tokio::select! {
+                result = self.finish_streaming(&mut streaming) => {
+                    if let Err(e) = result {
+                        error!("❌ Error finishing streaming transcription: {:#}", e);
+                        self.status_writer
+                            .set_error(&format!("{:#}", e))
+                            .unwrap_or_else(|e| tracing::warn!("Failed to set error status: {}", e));
+                    }
+                }
+                _ = abort.notified() => {
+                    streaming.abort();
+                    self.announce_abort();
+                    // Let a phrase being injected finish typing.
+                    if let Err(e) = streaming.finish().await {
+                        debug!("Aborted streaming transcription ended with: {:#}", e);
+                    }
+                }
+            }
+            self.is_processing = false;
+            self.set_input_app_busy(false);
+            self.status_writer
+                .set_state(WaybarState::Inactive, "Ready")
+                .unwrap_or_else(|e| tracing::warn!("Failed to set inactive status: {}", e));
+        } else if !captured_audio.is_empty() {
+            self.is_processing = true;
+            let profile = profile.unwrap_or_else(|| self.default_recording_profile());
+            if let Err(e) = self.process_audio(captured_audio, &profile).await {
                 error!("❌ Error processing audio: {:#}", e);
                 self.status_writer
                     .set_error(&format!("{:#}", e))
@@ -609,13 +1134,58 @@ impl HyprwhsprApp {
         Ok(())
     }
 
-    fn preprocess_audio(&mut self, audio_data: CapturedAudio) -> Result<Option<PreprocessedAudio>> {
+    /// Runs `future` unless a cancel shortcut aborts it first. Only
+    /// transcription and post-processing go through here, so an edit or
+    /// injection that has started always runs to completion.
+    async fn unless_aborted<T>(&self, future: impl Future<Output = T>) -> Option<T> {
+        tokio::select! {
+            output = future => Some(output),
+            _ = self.abort.notified() => None,
+        }
+    }
+
+    fn announce_abort(&self) {
+        info!("🚫 Transcription aborted");
+        self.audio_feedback
+            .play_cancel_sound()
+            .unwrap_or_else(|e| warn!("Failed to play cancel sound: {}", e));
+    }
+
+    async fn finish_streaming(&mut self, streaming: &mut StreamingDictation) -> Result<()> {
+        let report = streaming.finish().await?;
+
+        if report.text.is_empty() {
+            warn!("Streaming recording produced no text");
+            return Ok(());
+        }
+
+        info!(
+            "📝 Streaming transcription ({} phrases): \"{}\"",
+            report.phrases, report.text
+        );
+
+        // Save to history for Walker/Elephant integration
+        if let Err(e) = self.status_writer.save_transcription(&report.text) {
+            tracing::warn!("Failed to save transcription to history: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Trims silence with fast VAD when `config`, the recording's effective
+    /// config, allows it.
+    fn preprocess_audio(
+        &mut self,
+        audio_data: CapturedAudio,
+        config: &Config,
+    ) -> Result<Option<PreprocessedAudio>> {
         let CapturedAudio {
             mut samples,
             mut sample_rate,
         } = audio_data;
 
-        if let Some(vad) = self.fast_vad.as_mut() {
+        let fast_vad = self.fast_vad.as_mut().filter(|_| fast_vad_allowed(config));
+        if let Some(vad) = fast_vad {
             if !FastVad::supports_sample_rate(sample_rate) {
                 warn!(
                     "🎚️ Input sample rate {} Hz unsupported by fast VAD; resampling to 16 kHz",
@@ -680,13 +1250,17 @@ impl HyprwhsprApp {
         }))
     }
 
-    async fn process_audio(&mut self, audio_data: CapturedAudio) -> Result<()> {
+    async fn process_audio(
+        &mut self,
+        audio_data: CapturedAudio,
+        profile: &RecordingProfile,
+    ) -> Result<()> {
         if let Some(benchmark) = self.benchmark.as_mut() {
             benchmark.mark_processing_start(Instant::now());
         }
 
         let preprocess_start = Instant::now();
-        let maybe_audio = self.preprocess_audio(audio_data)?;
+        let maybe_audio = self.preprocess_audio(audio_data, &profile.config)?;
         let preprocess_duration = preprocess_start.elapsed();
 
         if let Some(benchmark) = self.benchmark.as_mut() {
@@ -744,11 +1318,29 @@ impl HyprwhsprApp {
             benchmark.record_audio_sent(audio_for_transcription.len(), 16_000);
         }
 
-        let TranscriptionResult { text, metrics } =
-            self.transcriber.transcribe(audio_for_transcription).await?;
+        let audio_level = AudioLevel::measure(&audio_for_transcription, 16_000);
+        let transcription = profile
+            .transcriber
+            .transcribe_with(audio_for_transcription, profile.options.clone());
+        let Some(transcription) = self.unless_aborted(transcription).await else {
+            self.announce_abort();
+            return Ok(());
+        };
+        let TranscriptionResult {
+            text,
+            metrics,
+            language,
+            ..
+        } = transcription?;
+        let text = self.hallucination_filter.filter(&text, audio_level);
+
+        if let Some(language) = &language {
+            info!("🌐 Detected language: {}", language);
+        }
 
         if let Some(benchmark) = self.benchmark.as_mut() {
             benchmark.record_backend_metrics(metrics);
+            benchmark.record_detected_language(language);
         }
 
         if text.trim().is_empty() {
@@ -763,7 +1355,34 @@ impl HyprwhsprApp {
         }
 
         info!("📝 Transcription: \"{}\"", text);
-        let normalized_text = self.text_normalizer.normalize(&text);
+        if let Some(command) = profile.text_normalizer.edit_command(&text).await {
+            info!("✏️ Editing command: {:?}", command);
+            if self.text_injector.lock().await.apply_edit(&command).await? {
+                if let Some(mut benchmark) = self.benchmark.take() {
+                    benchmark.mark_injection_skipped(Instant::now());
+                    if let Some(summary) = benchmark.finalize() {
+                        info!(message = %format_args!("\n{}", summary));
+                    }
+                }
+                return Ok(());
+            }
+        }
+
+        let mut normalized_text = profile.text_normalizer.normalize_async(&text).await;
+        if let Some(post_processor) = &self.post_processor {
+            if !normalized_text.is_empty() {
+                match self
+                    .unless_aborted(post_processor.process(&normalized_text))
+                    .await
+                {
+                    Some(processed) => normalized_text = processed,
+                    None => {
+                        self.announce_abort();
+                        return Ok(());
+                    }
+                }
+            }
+        }
 
         if normalized_text.is_empty() {
             warn!("Transcription became empty after text normalization");
@@ -779,7 +1398,8 @@ impl HyprwhsprApp {
         debug!("📝 Normalized transcription: \"{}\"", normalized_text);
 
         // Save to history for Walker/Elephant integration
-        if let Err(e) = self.status_writer.save_transcription(&normalized_text) {
+        let history_text = profile.text_normalizer.expand_snippets(&normalized_text);
+        if let Err(e) = self.status_writer.save_transcription(&history_text) {
             tracing::warn!("Failed to save transcription to history: {}", e);
         }
 
@@ -791,8 +1411,20 @@ impl HyprwhsprApp {
             benchmark.mark_injection_start(injection_start);
         }
 
+        let context = if profile.text_normalizer.smart_spacing_enabled() {
+            injector.injection_context().await
+        } else {
+            InjectionContext::default()
+        };
+        let normalizer = &profile.text_normalizer;
+        let text_to_inject = normalizer.expand_snippets(&normalizer.fit_to_context(
+            &normalized_text,
+            &text,
+            &context,
+        ));
+
         debug!("⌨️  Injecting text into active application...");
-        injector.inject_text(&normalized_text).await?;
+        injector.inject_text(&text_to_inject).await?;
 
         let injection_end = Instant::now();
         if let Some(benchmark) = self.benchmark.as_mut() {
@@ -814,15 +1446,51 @@ impl HyprwhsprApp {
         if self.recording_session.is_some() {
             self.recording_session = None;
         }
+        if let Some(streaming) = self.streaming.take() {
+            streaming.abort();
+        }
+        self.recording_profile = None;
         self.status_writer.cleanup()?;
 
         if let Some(manager) = &mut self.input_manager {
             manager.stop();
         }
         self.input_manager = None;
+        *self
+            .shortcut_triggers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
         self.recording_trigger = None;
 
         info!("✅ Cleanup completed");
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_vad_gate_follows_the_profile_provider() {
+        let mut config = Config::default();
+        config.fast_vad.enabled = true;
+        config.transcription.provider = TranscriptionProvider::WhisperCpp;
+        config.transcription.whisper_cpp.vad.enabled = true;
+        assert!(!fast_vad_allowed(&config));
+
+        let cloud = AppProfileConfig {
+            provider: Some(TranscriptionProvider::Groq),
+            ..Default::default()
+        };
+        assert!(fast_vad_allowed(&apply_profile(&config, &cloud)));
+
+        config.transcription.provider = TranscriptionProvider::Groq;
+        let local = AppProfileConfig {
+            provider: Some(TranscriptionProvider::WhisperCpp),
+            ..Default::default()
+        };
+        assert!(fast_vad_allowed(&config));
+        assert!(!fast_vad_allowed(&apply_profile(&config, &local)));
+    }
+}
@@ -9,8 +9,8 @@ use crate::audio::{
 use crate::config::{Config, ConfigManager, TranscriptionProvider};
 use crate::input::TextInjector;
 use crate::status::StatusWriter;
-use crate::text::NormalizeTextService;
-use crate::transcription::{TranscriptionBackend, TranscriptionResult};
+use crate::text::{InjectionContext, LlmPostProcessor, NormalizeTextService};
+use crate::transcription::{Transcriber, TranscriberRegistry, TranscriptionResult};
 use crate::whisper::WhisperVadOptions;
 
 /// Test version of the app that doesn't use global shortcuts
@@ -18,10 +18,12 @@ pub struct HyprwhsprAppTest {
     config_manager: ConfigManager,
     audio_capture: AudioCapture,
     audio_feedback: AudioFeedback,
-    transcriber: TranscriptionBackend,
+    transcriber_registry: TranscriberRegistry,
+    transcriber: Box<dyn Transcriber>,
     fast_vad: Option<FastVad>,
     text_injector: Arc<Mutex<TextInjector>>,
     text_normalizer: NormalizeTextService,
+    post_processor: Option<LlmPostProcessor>,
     status_writer: StatusWriter,
     current_config: Config,
     recording_session: Option<RecordingSession>,
@@ -41,23 +43,23 @@ impl HyprwhsprAppTest {
             assets_dir,
             config.start_sound_path.clone(),
             config.stop_sound_path.clone(),
+            config.cancel_sound_path.clone(),
             config.start_sound_volume,
             config.stop_sound_volume,
         );
 
         let vad_options = build_vad_options(&config_manager, &config);
 
-        let transcriber = TranscriptionBackend::build(&config_manager, &config, vad_options)
+        let transcriber_registry = TranscriberRegistry::default();
+        let transcriber = transcriber_registry
+            .build(&config_manager, &config, vad_options)
             .context("Failed to configure transcription backend")?;
 
         transcriber
             .initialize()
             .context("Failed to initialize transcription backend")?;
 
-        info!(
-            "🎯 Active transcription backend: {}",
-            transcriber.provider().label()
-        );
+        info!("🎯 Active transcription backend: {}", transcriber.label());
 
         let text_injector = TextInjector::new(
             config.shift_paste,
@@ -66,7 +68,9 @@ impl HyprwhsprAppTest {
             config.paste_hints.shift_insert.clone(),
             config.auto_copy_clipboard,
         )?;
-        let text_normalizer = NormalizeTextService::new(config.word_overrides.clone());
+        let text_normalizer = NormalizeTextService::from_config(&config)?;
+        let post_processor = LlmPostProcessor::from_config(&config.post_processing)
+            .context("Failed to configure post-processing")?;
 
         let status_writer = StatusWriter::new()?;
         status_writer.set_recording(false)?;
@@ -88,10 +92,12 @@ impl HyprwhsprAppTest {
             config_manager,
             audio_capture,
             audio_feedback,
+            transcriber_registry,
             transcriber,
             fast_vad,
             text_injector: Arc::new(Mutex::new(text_injector)),
             text_normalizer,
+            post_processor,
             status_writer,
             current_config: config,
             recording_session: None,
@@ -117,6 +123,7 @@ impl HyprwhsprAppTest {
             assets_dir,
             new_config.start_sound_path.clone(),
             new_config.stop_sound_path.clone(),
+            new_config.cancel_sound_path.clone(),
             new_config.start_sound_volume,
             new_config.stop_sound_volume,
         );
@@ -128,23 +135,26 @@ impl HyprwhsprAppTest {
             new_config.paste_hints.shift_insert.clone(),
             new_config.auto_copy_clipboard,
         )?;
-        let text_normalizer = NormalizeTextService::new(new_config.word_overrides.clone());
-
-        let transcriber_changed =
-            TranscriptionBackend::needs_refresh(&self.current_config, &new_config);
+        let text_normalizer = NormalizeTextService::from_config(&new_config)?;
+        let post_processor = LlmPostProcessor::from_config(&new_config.post_processing)
+            .context("Failed to configure post-processing")?;
+
+        let transcriber_changed = TranscriberRegistry::needs_rebuild(
+            self.transcriber.as_ref(),
+            &self.current_config,
+            &new_config,
+        );
 
         if transcriber_changed {
             let vad_options = build_vad_options(&self.config_manager, &new_config);
-            let backend =
-                TranscriptionBackend::build(&self.config_manager, &new_config, vad_options)
-                    .context("Failed to reconfigure transcription backend")?;
+            let backend = self
+                .transcriber_registry
+                .build(&self.config_manager, &new_config, vad_options)
+                .context("Failed to reconfigure transcription backend")?;
             backend
                 .initialize()
                 .context("Failed to initialize updated transcription backend")?;
-            info!(
-                "🎯 Active transcription backend: {}",
-                backend.provider().label()
-            );
+            info!("🎯 Active transcription backend: {}", backend.label());
             self.transcriber = backend;
         }
 
@@ -188,6 +198,7 @@ impl HyprwhsprAppTest {
 
         self.text_injector = Arc::new(Mutex::new(text_injector));
         self.text_normalizer = text_normalizer;
+        self.post_processor = post_processor;
         self.audio_feedback = audio_feedback;
         self.current_config = new_config;
 
@@ -341,7 +352,19 @@ impl HyprwhsprAppTest {
         }
 
         info!("📝 Transcription: \"{}\"", transcription);
-        let normalized_text = self.text_normalizer.normalize(&transcription);
+        if let Some(command) = self.text_normalizer.edit_command(&transcription).await {
+            info!("✏️ Editing command: {:?}", command);
+            if self.text_injector.lock().await.apply_edit(&command).await? {
+                return Ok(());
+            }
+        }
+
+        let mut normalized_text = self.text_normalizer.normalize_async(&transcription).await;
+        if let Some(post_processor) = &self.post_processor {
+            if !normalized_text.is_empty() {
+                normalized_text = post_processor.process(&normalized_text).await;
+            }
+        }
         if normalized_text.is_empty() {
             warn!("Transcription became empty after text normalization");
             return Ok(());
@@ -351,8 +374,20 @@ impl HyprwhsprAppTest {
         let text_injector = Arc::clone(&self.text_injector);
         let mut injector = text_injector.lock().await;
 
+        let context = if self.text_normalizer.smart_spacing_enabled() {
+            injector.injection_context().await
+        } else {
+            InjectionContext::default()
+        };
+        let normalizer = &self.text_normalizer;
+        let text_to_inject = normalizer.expand_snippets(&normalizer.fit_to_context(
+            &normalized_text,
+            &transcription,
+            &context,
+        ));
+
         info!("⌨️  Injecting text into active application...");
-        injector.inject_text(&normalized_text).await?;
+        injector.inject_text(&text_to_inject).await?;
         info!("✅ Text injected successfully!");
 
         Ok(())
@@ -3,6 +3,7 @@ use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{BufferSize, InputCallbackInfo, SampleRate, StreamConfig};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 pub struct AudioCapture {
@@ -13,6 +14,7 @@ pub struct AudioCapture {
 pub struct RecordingSession {
     stream: cpal::Stream,
     audio_data: Arc<Mutex<Vec<f32>>>,
+    chunk_tx: Arc<Mutex<Option<mpsc::UnboundedSender<CapturedAudio>>>>,
     sample_rate_tracker: Arc<Mutex<SampleRateTracker>>,
     requested_sample_rate: u32,
 }
@@ -202,6 +204,10 @@ impl RecordingSession {
     pub fn stop(self) -> Result<CapturedAudio> {
         // Drop the stream (stops recording)
         drop(self.stream);
+        // Close any chunk subscriber so it knows the recording ended
+        if let Ok(mut chunk_tx) = self.chunk_tx.lock() {
+            chunk_tx.take();
+        }
 
         let measured_sample_rate = self
             .sample_rate_tracker
@@ -237,6 +243,33 @@ impl RecordingSession {
         })
     }
 
+    /// Emits every captured chunk to the returned channel, starting with the
+    /// audio buffered so far. Each chunk carries the sample rate measured when
+    /// it arrived. The channel closes when the session stops.
+    pub fn subscribe_chunks(&self) -> mpsc::UnboundedReceiver<CapturedAudio> {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        // Hold the buffer lock so no chunk lands between the snapshot and the subscription
+        if let Ok(buffer) = self.audio_data.lock() {
+            if !buffer.is_empty() {
+                let sample_rate = self
+                    .sample_rate_tracker
+                    .lock()
+                    .map(|tracker| tracker.sample_rate())
+                    .unwrap_or(self.requested_sample_rate);
+                let _ = tx.send(CapturedAudio {
+                    samples: buffer.clone(),
+                    sample_rate,
+                });
+            }
+            if let Ok(mut chunk_tx) = self.chunk_tx.lock() {
+                *chunk_tx = Some(tx);
+            }
+        }
+
+        rx
+    }
+
     pub fn get_current_level(&self) -> f32 {
         if let Ok(data) = self.audio_data.lock() {
             if data.is_empty() {
@@ -357,20 +390,36 @@ impl AudioCapture {
         // Shared buffer for audio data
         let audio_data = Arc::new(Mutex::new(Vec::new()));
         let audio_data_clone = Arc::clone(&audio_data);
+        let chunk_tx: Arc<Mutex<Option<mpsc::UnboundedSender<CapturedAudio>>>> =
+            Arc::new(Mutex::new(None));
+        let chunk_tx_clone = Arc::clone(&chunk_tx);
         let sample_rate_tracker = Arc::new(Mutex::new(SampleRateTracker::new(
             config.sample_rate.0,
             config.channels,
         )));
         let tracker_clone = Arc::clone(&sample_rate_tracker);
+        let requested_sample_rate = config.sample_rate.0;
 
         let stream = match device.build_input_stream(
             config,
             move |data: &[f32], info: &InputCallbackInfo| {
-                if let Ok(mut tracker) = tracker_clone.lock() {
-                    tracker.update(data.len(), info);
-                }
+                let sample_rate = match tracker_clone.lock() {
+                    Ok(mut tracker) => {
+                        tracker.update(data.len(), info);
+                        tracker.sample_rate()
+                    }
+                    Err(_) => requested_sample_rate,
+                };
                 if let Ok(mut buffer) = audio_data_clone.lock() {
                     buffer.extend_from_slice(data);
+                    if let Ok(chunk_tx) = chunk_tx_clone.lock() {
+                        if let Some(tx) = chunk_tx.as_ref() {
+                            let _ = tx.send(CapturedAudio {
+                                samples: data.to_vec(),
+                                sample_rate,
+                            });
+                        }
+                    }
                 }
             },
             move |err| {
@@ -405,6 +454,7 @@ impl AudioCapture {
         Ok(RecordingSession {
             stream,
             audio_data,
+            chunk_tx,
             sample_rate_tracker,
             requested_sample_rate: config.sample_rate.0,
         })
@@ -5,10 +5,15 @@ use std::io::BufReader;
 use std::path::PathBuf;
 use tracing::{debug, error, warn};
 
+/// Playback speed of the stop sound when it stands in for the cancel sound;
+/// the lower pitch tells an aborted recording apart from a finished one.
+const CANCEL_SOUND_SPEED: f32 = 0.7;
+
 pub struct AudioFeedback {
     enabled: bool,
     start_sound: PathBuf,
     stop_sound: PathBuf,
+    cancel_sound: Option<PathBuf>,
     start_volume: f32,
     stop_volume: f32,
 }
@@ -19,6 +24,7 @@ impl AudioFeedback {
         assets_dir: PathBuf,
         start_sound_path: Option<String>,
         stop_sound_path: Option<String>,
+        cancel_sound_path: Option<String>,
         start_volume: f32,
         stop_volume: f32,
     ) -> Self {
@@ -56,6 +62,16 @@ impl AudioFeedback {
             assets_dir.join("ping-down.ogg")
         };
 
+        // Resolve cancel sound path; without one the stop sound plays slowed down
+        let cancel_sound = cancel_sound_path.map(|path| {
+            let custom_path = PathBuf::from(&path);
+            if custom_path.exists() {
+                custom_path
+            } else {
+                assets_dir.join(path)
+            }
+        });
+
         // Validate volumes
         let start_volume = start_volume.clamp(0.1, 1.0);
         let stop_volume = stop_volume.clamp(0.1, 1.0);
@@ -67,6 +83,9 @@ impl AudioFeedback {
         if !stop_sound.exists() {
             warn!("Stop sound not found: {:?}", stop_sound);
         }
+        if let Some(cancel_sound) = cancel_sound.as_ref().filter(|path| !path.exists()) {
+            warn!("Cancel sound not found: {:?}", cancel_sound);
+        }
 
         debug!(
             "Audio feedback initialized - enabled: {}, start: {:?}, stop: {:?}",
@@ -77,6 +96,7 @@ impl AudioFeedback {
             enabled,
             start_sound,
             stop_sound,
+            cancel_sound,
             start_volume,
             stop_volume,
         }
@@ -88,7 +108,7 @@ impl AudioFeedback {
         }
 
         debug!("Playing start sound: {:?}", self.start_sound);
-        self.play_sound(&self.start_sound, self.start_volume)
+        self.play_sound(&self.start_sound, self.start_volume, 1.0)
     }
 
     pub fn play_stop_sound(&self) -> Result<()> {
@@ -97,10 +117,30 @@ impl AudioFeedback {
         }
 
         debug!("Playing stop sound: {:?}", self.stop_sound);
-        self.play_sound(&self.stop_sound, self.stop_volume)
+        self.play_sound(&self.stop_sound, self.stop_volume, 1.0)
+    }
+
+    pub fn play_cancel_sound(&self) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        match &self.cancel_sound {
+            Some(cancel_sound) => {
+                debug!("Playing cancel sound: {:?}", cancel_sound);
+                self.play_sound(cancel_sound, self.stop_volume, 1.0)
+            }
+            None => {
+                debug!(
+                    "Playing slowed stop sound for cancel: {:?}",
+                    self.stop_sound
+                );
+                self.play_sound(&self.stop_sound, self.stop_volume, CANCEL_SOUND_SPEED)
+            }
+        }
     }
 
-    fn play_sound(&self, path: &PathBuf, volume: f32) -> Result<()> {
+    fn play_sound(&self, path: &PathBuf, volume: f32, speed: f32) -> Result<()> {
         if !path.exists() {
             warn!("Sound file not found: {:?}", path);
             return Ok(());
@@ -109,7 +149,7 @@ impl AudioFeedback {
         // Spawn in a separate thread to avoid blocking
         let path = path.clone();
         std::thread::spawn(move || {
-            if let Err(e) = Self::play_sound_blocking(&path, volume) {
+            if let Err(e) = Self::play_sound_blocking(&path, volume, speed) {
                 error!("Failed to play sound {:?}: {}", path, e);
             }
         });
@@ -117,7 +157,7 @@ impl AudioFeedback {
         Ok(())
     }
 
-    fn play_sound_blocking(path: &PathBuf, volume: f32) -> Result<()> {
+    fn play_sound_blocking(path: &PathBuf, volume: f32, speed: f32) -> Result<()> {
         // Create output stream
         let (_stream, stream_handle) =
             OutputStream::try_default().context("Failed to open audio output")?;
@@ -130,8 +170,9 @@ impl AudioFeedback {
             File::open(path).with_context(|| format!("Failed to open audio file: {:?}", path))?;
         let source = Decoder::new(BufReader::new(file)).context("Failed to decode audio file")?;
 
-        // Set volume and play
+        // Set volume and speed, then play
         sink.set_volume(volume);
+        sink.set_speed(speed);
         sink.append(source);
 
         // Wait for playback to complete
@@ -223,100 +223,24 @@ impl FastVad {
             });
         }
 
-        self.current_profile = self.settings.base_profile;
-        self.detector = VoiceActivityDetector::new(self.current_profile.into());
-        self.detector.reset();
-        self.decision_history.clear();
-        self.profile_switches = 0;
+        self.reset_detector();
 
         let mut trimmed = Vec::with_capacity(audio.len());
-        let mut active_segment = Vec::new();
-        let mut pre_roll: VecDeque<Vec<f32>> =
-            VecDeque::with_capacity(self.settings.pre_roll_frames.max(1));
-        let mut pending_silence: VecDeque<(Vec<f32>, bool)> = VecDeque::new();
-        let mut in_speech = false;
-        let mut silence_frames = 0usize;
+        let mut state = SegmentState::new(self.settings.pre_roll_frames);
         let mut evaluated_frames = 0usize;
         let mut segments = 0usize;
 
         for chunk in audio.chunks(self.frame_samples) {
-            let frame: Vec<f32> = chunk.to_vec();
-            let pcm_frame = Self::convert_frame(&frame, self.frame_samples);
-            let is_speech = self.predict_frame(&pcm_frame)?;
             evaluated_frames += 1;
-            let volatility = self.push_decision(is_speech);
-            self.adjust_profile(volatility);
-
-            if !in_speech {
-                if is_speech {
-                    in_speech = true;
-                    self.flush_pre_roll(&mut pre_roll, &mut active_segment);
-                    if !pending_silence.is_empty() {
-                        for (silence_frame, appended) in pending_silence.drain(..) {
-                            if !appended {
-                                active_segment.extend_from_slice(&silence_frame);
-                            }
-                        }
-                    }
-                    active_segment.extend_from_slice(&frame);
-                    silence_frames = 0;
-                } else {
-                    self.push_pre_roll(&mut pre_roll, &frame);
-                }
-                continue;
-            }
-
-            if is_speech {
-                if !pending_silence.is_empty() {
-                    for (silence_frame, appended) in pending_silence.drain(..) {
-                        if !appended {
-                            active_segment.extend_from_slice(&silence_frame);
-                        }
-                    }
-                }
-                active_segment.extend_from_slice(&frame);
-                silence_frames = 0;
-                continue;
-            }
-
-            silence_frames += 1;
-            let appended = if silence_frames <= self.settings.post_roll_frames {
-                active_segment.extend_from_slice(&frame);
-                true
-            } else {
-                false
-            };
-            pending_silence.push_back((frame.clone(), appended));
-
-            if silence_frames >= self.settings.silence_timeout_frames {
-                if !active_segment.is_empty() && active_segment.len() >= self.min_speech_samples() {
-                    trimmed.extend_from_slice(&active_segment);
-                    segments += 1;
-                }
-                active_segment.clear();
-
-                if !pending_silence.is_empty() {
-                    self.reseed_pre_roll(&mut pre_roll, &pending_silence);
-                    pending_silence.clear();
-                }
-
-                in_speech = false;
-                silence_frames = 0;
+            if let Some(segment) = self.push_frame(&mut state, chunk)? {
+                trimmed.extend_from_slice(&segment);
+                segments += 1;
             }
         }
 
-        if in_speech {
-            if !pending_silence.is_empty() {
-                for (silence_frame, appended) in pending_silence.drain(..) {
-                    if !appended {
-                        active_segment.extend_from_slice(&silence_frame);
-                    }
-                }
-            }
-            if !active_segment.is_empty() && active_segment.len() >= self.min_speech_samples() {
-                trimmed.extend_from_slice(&active_segment);
-                segments += 1;
-            }
+        if let Some(segment) = self.finish_segment(&mut state) {
+            trimmed.extend_from_slice(&segment);
+            segments += 1;
         }
 
         let dropped_samples = audio.len().saturating_sub(trimmed.len());
@@ -331,6 +255,82 @@ impl FastVad {
         })
     }
 
+    fn reset_detector(&mut self) {
+        self.current_profile = self.settings.base_profile;
+        self.detector = VoiceActivityDetector::new(self.current_profile.into());
+        self.detector.reset();
+        self.decision_history.clear();
+        self.profile_switches = 0;
+    }
+
+    /// Feeds one frame through the detector. Returns the finished segment once
+    /// enough trailing silence has closed it.
+    fn push_frame(&mut self, state: &mut SegmentState, frame: &[f32]) -> Result<Option<Vec<f32>>> {
+        let pcm_frame = Self::convert_frame(frame, self.frame_samples);
+        let is_speech = self.predict_frame(&pcm_frame)?;
+        let volatility = self.push_decision(is_speech);
+        self.adjust_profile(volatility);
+
+        if !state.in_speech {
+            if is_speech {
+                state.in_speech = true;
+                self.flush_pre_roll(&mut state.pre_roll, &mut state.active_segment);
+                state.drain_pending_silence();
+                state.active_segment.extend_from_slice(frame);
+                state.silence_frames = 0;
+            } else {
+                self.push_pre_roll(&mut state.pre_roll, frame);
+            }
+            return Ok(None);
+        }
+
+        if is_speech {
+            state.drain_pending_silence();
+            state.active_segment.extend_from_slice(frame);
+            state.silence_frames = 0;
+            return Ok(None);
+        }
+
+        state.silence_frames += 1;
+        let appended = if state.silence_frames <= self.settings.post_roll_frames {
+            state.active_segment.extend_from_slice(frame);
+            true
+        } else {
+            false
+        };
+        state.pending_silence.push_back((frame.to_vec(), appended));
+
+        if state.silence_frames < self.settings.silence_timeout_frames {
+            return Ok(None);
+        }
+
+        let segment = std::mem::take(&mut state.active_segment);
+        if !state.pending_silence.is_empty() {
+            self.reseed_pre_roll(&mut state.pre_roll, &state.pending_silence);
+            state.pending_silence.clear();
+        }
+        state.in_speech = false;
+        state.silence_frames = 0;
+
+        Ok(self.accept_segment(segment))
+    }
+
+    /// Closes a segment that was still open when the audio ended.
+    fn finish_segment(&self, state: &mut SegmentState) -> Option<Vec<f32>> {
+        if !state.in_speech {
+            return None;
+        }
+
+        state.drain_pending_silence();
+        state.in_speech = false;
+        state.silence_frames = 0;
+        self.accept_segment(std::mem::take(&mut state.active_segment))
+    }
+
+    fn accept_segment(&self, segment: Vec<f32>) -> Option<Vec<f32>> {
+        (!segment.is_empty() && segment.len() >= self.min_speech_samples()).then_some(segment)
+    }
+
     pub fn settings(&self) -> &FastVadSettings {
         &self.settings
     }
@@ -452,6 +452,94 @@ impl FastVad {
     }
 }
 
+/// Segmentation progress carried between frames.
+struct SegmentState {
+    active_segment: Vec<f32>,
+    pre_roll: VecDeque<Vec<f32>>,
+    pending_silence: VecDeque<(Vec<f32>, bool)>,
+    in_speech: bool,
+    silence_frames: usize,
+}
+
+impl SegmentState {
+    fn new(pre_roll_frames: usize) -> Self {
+        Self {
+            active_segment: Vec::new(),
+            pre_roll: VecDeque::with_capacity(pre_roll_frames.max(1)),
+            pending_silence: VecDeque::new(),
+            in_speech: false,
+            silence_frames: 0,
+        }
+    }
+
+    fn drain_pending_silence(&mut self) {
+        for (silence_frame, appended) in self.pending_silence.drain(..) {
+            if !appended {
+                self.active_segment.extend_from_slice(&silence_frame);
+            }
+        }
+    }
+}
+
+/// Splits live audio into phrases at the pauses detected by [`FastVad`], so
+/// each phrase can be transcribed while recording continues.
+pub struct PhraseSegmenter {
+    vad: FastVad,
+    state: SegmentState,
+    partial_frame: Vec<f32>,
+}
+
+impl PhraseSegmenter {
+    pub fn new(settings: FastVadSettings, sample_rate_hz: u32) -> Result<Self> {
+        FastVad::validate_sample_rate(sample_rate_hz)?;
+        let state = SegmentState::new(settings.pre_roll_frames);
+        Ok(Self {
+            vad: FastVad::with_settings(settings, sample_rate_hz),
+            state,
+            partial_frame: Vec::new(),
+        })
+    }
+
+    pub fn sample_rate_hz(&self) -> u32 {
+        self.vad.sample_rate_hz
+    }
+
+    /// Consumes a chunk of samples and returns every phrase it completed.
+    pub fn push(&mut self, samples: &[f32]) -> Result<Vec<Vec<f32>>> {
+        let frame_samples = self.vad.frame_samples;
+        let mut phrases = Vec::new();
+        self.partial_frame.extend_from_slice(samples);
+
+        let complete = self.partial_frame.len() - self.partial_frame.len() % frame_samples;
+        for frame in self.partial_frame[..complete].chunks(frame_samples) {
+            if let Some(phrase) = self.vad.push_frame(&mut self.state, frame)? {
+                phrases.push(phrase);
+            }
+        }
+        self.partial_frame.drain(..complete);
+
+        Ok(phrases)
+    }
+
+    /// Flushes buffered audio and returns the phrase still in progress, if any.
+    pub fn finish(&mut self) -> Result<Option<Vec<f32>>> {
+        let mut last = None;
+        if !self.partial_frame.is_empty() {
+            let frame = std::mem::take(&mut self.partial_frame);
+            last = self.vad.push_frame(&mut self.state, &frame)?;
+        }
+
+        let tail = self.vad.finish_segment(&mut self.state);
+        Ok(match (last, tail) {
+            (Some(mut phrase), Some(tail)) => {
+                phrase.extend_from_slice(&tail);
+                Some(phrase)
+            }
+            (phrase, tail) => phrase.or(tail),
+        })
+    }
+}
+
 impl fmt::Debug for FastVad {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("FastVad")
@@ -606,6 +694,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn segmenter_emits_phrases_before_the_stream_ends() -> Result<()> {
+        let config = FastVadConfig {
+            enabled: true,
+            min_speech_ms: 90,
+            silence_timeout_ms: 300,
+            ..Default::default()
+        };
+        let mut segmenter =
+            PhraseSegmenter::new(FastVadSettings::from_config(&config), TEST_SAMPLE_RATE_HZ)?;
+
+        let mut audio = Vec::new();
+        audio.extend(silence_ms(200));
+        audio.extend(tone_ms(600));
+        audio.extend(silence_ms(800));
+        audio.extend(tone_ms(400));
+
+        let mut phrases = Vec::new();
+        for chunk in audio.chunks(700) {
+            phrases.extend(segmenter.push(chunk)?);
+        }
+        assert_eq!(phrases.len(), 1, "first phrase closes at the pause");
+
+        let tail = segmenter
+            .finish()?
+            .expect("second phrase flushed on finish");
+        assert!(tail.len() >= TEST_SAMPLE_RATE_HZ as usize * 400 / 1000);
+        assert!(segmenter.finish()?.is_none());
+        Ok(())
+    }
+
     #[test]
     fn benchmark_hook_runs() -> Result<()> {
         let config = FastVadConfig {
@@ -7,12 +7,15 @@ use comfy_table::modifiers::UTF8_ROUND_CORNERS;
 use comfy_table::presets::UTF8_FULL;
 use comfy_table::{Cell, CellAlignment, ContentArrangement, Row, Table};
 
-use crate::transcription::BackendMetrics;
+use crate::transcription::{BackendMetrics, FallbackAttempt};
 
 const DASH: &str = "—";
 
 pub struct BenchmarkRecorder {
     provider_label: String,
+    served_by: Option<String>,
+    detected_language: Option<String>,
+    fallback_attempts: Vec<FallbackAttempt>,
     keybind_start: Instant,
     keybind_stop: Option<Instant>,
     recording_start: Instant,
@@ -41,6 +44,9 @@ impl BenchmarkRecorder {
     pub fn new(provider_label: String, keybind_start: Instant, recording_start: Instant) -> Self {
         Self {
             provider_label,
+            served_by: None,
+            detected_language: None,
+            fallback_attempts: Vec::new(),
             keybind_start,
             keybind_stop: None,
             recording_start,
@@ -118,6 +124,12 @@ impl BenchmarkRecorder {
         self.upload_duration = metrics.upload_duration;
         self.response_duration = metrics.response_duration;
         self.transcription_duration = Some(metrics.transcription_duration);
+        self.fallback_attempts = metrics.fallback_attempts;
+        self.served_by = metrics.served_by;
+    }
+
+    pub fn record_detected_language(&mut self, language: Option<String>) {
+        self.detected_language = language;
     }
 
     pub fn mark_injection_start(&mut self, at: Instant) {
@@ -200,8 +212,25 @@ impl BenchmarkRecorder {
             .fast_vad_duration
             .map(|duration| duration.as_secs_f64() * 1000.0));
 
+        let provider_label = match self.served_by {
+            Some(served_by) => format!("{} → {}", self.provider_label, served_by),
+            None => self.provider_label,
+        };
+        let failed_attempts = self
+            .fallback_attempts
+            .into_iter()
+            .map(|attempt| {
+                (
+                    attempt.provider_label,
+                    attempt.duration.as_secs_f64() * 1000.0,
+                )
+            })
+            .collect();
+
         Some(BenchmarkSummary {
-            provider_label: self.provider_label,
+            provider_label,
+            detected_language: self.detected_language,
+            failed_attempts,
             keybind_to_record_start_ms,
             recording_duration_ms,
             stop_to_processing_ms,
@@ -268,6 +297,8 @@ fn savings_cell(value: Option<f64>, pct: Option<f64>) -> Cell {
 
 pub(crate) struct BenchmarkSummary {
     provider_label: String,
+    detected_language: Option<String>,
+    failed_attempts: Vec<(String, f64)>,
     keybind_to_record_start_ms: f64,
     recording_duration_ms: Option<f64>,
     stop_to_processing_ms: Option<f64>,
@@ -300,8 +331,13 @@ impl fmt::Display for BenchmarkSummary {
             .set_content_arrangement(ContentArrangement::DynamicFullWidth)
             .force_no_tty();
 
+        let title = match &self.detected_language {
+            Some(language) => format!("Benchmark · {} · {}", self.provider_label, language),
+            None => format!("Benchmark · {}", self.provider_label),
+        };
+
         table.set_header(vec![
-            Cell::new(format!("Benchmark · {}", self.provider_label)),
+            Cell::new(title),
             Cell::new("DUR (ms)"),
             Cell::new("Audio (ms)"),
             Cell::new("Audio (KB)"),
@@ -341,6 +377,15 @@ impl fmt::Display for BenchmarkSummary {
             kb_cell(self.trimmed_audio_kb),
         ]));
 
+        for (label, duration_ms) in &self.failed_attempts {
+            table.add_row(Row::from(vec![
+                Cell::new(format!("Failed · {label}")),
+                ms_cell(Some(*duration_ms)),
+                empty_cell(),
+                empty_cell(),
+            ]));
+        }
+
         table.add_row(Row::from(vec![
             Cell::new("Encode"),
             ms_cell(self.encode_ms),
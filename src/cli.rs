@@ -17,10 +17,12 @@ pub struct Cli {
 
 #[derive(Subcommand)]
 pub enum Command {
-    /// Install integration components (waybar, systemd, elephant)
+    /// Install integration components (waybar, systemd, elephant, hyprland)
     Install(InstallArgs),
     /// Control the running recorder daemon
     Record(RecordArgs),
+    /// Press or release a shortcut by name, e.g. from a compositor bind
+    Shortcut(ShortcutArgs),
 }
 
 #[derive(clap::Args)]
@@ -37,6 +39,10 @@ pub struct InstallArgs {
     #[arg(long)]
     pub elephant: bool,
 
+    /// Generate Hyprland binds for the configured shortcuts
+    #[arg(long)]
+    pub hyprland: bool,
+
     /// Install all components (non-interactive)
     #[arg(long)]
     pub all: bool,
@@ -49,7 +55,7 @@ pub struct InstallArgs {
 impl InstallArgs {
     /// Returns true if any specific component flag was set
     pub fn has_specific_flags(&self) -> bool {
-        self.waybar || self.service || self.elephant || self.all
+        self.waybar || self.service || self.elephant || self.hyprland || self.all
     }
 }
 
@@ -70,3 +76,17 @@ pub enum RecordAction {
     /// Print current recorder state
     Status,
 }
+
+#[derive(clap::Args)]
+pub struct ShortcutArgs {
+    #[command(subcommand)]
+    pub action: ShortcutAction,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum ShortcutAction {
+    /// Press the shortcut (`press`, `hold`, `cancel` or a binding name)
+    Press { name: String },
+    /// Release a shortcut pressed earlier
+    Release { name: String },
+}
@@ -1,8 +1,10 @@
 use crate::paths::expand_tilde;
+use crate::text::normalize_step_names;
 use crate::transcription::DEFAULT_PROMPT;
 use anyhow::{anyhow, Context, Result};
 use jsonc_parser::{parse_to_serde_value, ParseOptions};
 use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::de::Error as _;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::borrow::Cow;
 use std::collections::HashMap;
@@ -27,6 +29,32 @@ pub struct ShortcutsConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub press: Option<String>,
+
+    /// Aborts the recording, or the transcription in flight, without
+    /// injecting anything. Only listened for while busy; `null` disables it.
+    #[serde(default = "default_cancel_shortcut")]
+    pub cancel: Option<String>,
+
+    /// Named shortcuts, each with its own action and optional overrides.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bindings: Vec<ShortcutBindingConfig>,
+
+    /// How long a binding's keys must stay down to count as a long press;
+    /// anything shorter is a tap.
+    #[serde(default = "default_long_press_ms")]
+    pub long_press_ms: u64,
+
+    /// Longest gap between releasing the first tap and pressing the second.
+    #[serde(default = "default_double_tap_ms")]
+    pub double_tap_ms: u64,
+
+    /// Kinds of input devices listened to for shortcuts.
+    #[serde(default = "default_device_classes")]
+    pub device_classes: Vec<InputDeviceClass>,
+
+    /// Where shortcut presses come from.
+    #[serde(default)]
+    pub source: ShortcutSource,
 }
 
 impl Default for ShortcutsConfig {
@@ -34,10 +62,97 @@ impl Default for ShortcutsConfig {
         Self {
             hold: None,
             press: Some(default_primary_shortcut()),
+            cancel: default_cancel_shortcut(),
+            bindings: Vec::new(),
+            long_press_ms: default_long_press_ms(),
+            double_tap_ms: default_double_tap_ms(),
+            device_classes: default_device_classes(),
+            source: ShortcutSource::default(),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ShortcutBindingConfig {
+    /// Identifies the binding in logs and shortcut events; must be unique.
+    pub name: String,
+    pub shortcut: String,
+    #[serde(default)]
+    pub action: ShortcutAction,
+    #[serde(default)]
+    pub gesture: ShortcutGesture,
+    /// Only listens to the device whose name contains this, case-insensitively,
+    /// or to the device node at this path (`/dev/input/by-id/...` links work).
+    /// Keys on that device then no longer reach bindings without a `device`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// Records with this entry of `profiles` instead of the one matching the
+    /// focused window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Records through this provider, or switches to it for `switch_provider`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<TranscriptionProvider>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutSource {
+    /// Reads the devices under `/dev/input`, which needs the `input` group.
+    #[default]
+    Evdev,
+    /// Leaves the keys to compositor binds that run `hyprwhspr-rs record` or
+    /// `hyprwhspr-rs shortcut press|release <name>`; see `install --hyprland`.
+    Compositor,
+}
+
+/// A kind of evdev input device, told apart by the keys it reports.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum InputDeviceClass {
+    /// Devices with letter keys.
+    Keyboard,
+    /// Devices with a left button; side buttons are `BTN_SIDE` and `BTN_EXTRA`.
+    Mouse,
+    /// Gamepads and joysticks.
+    Gamepad,
+    /// Anything else with keys or buttons, such as foot pedals.
+    Other,
+}
+
+/// How a binding's keys have to be pressed to trigger it.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutGesture {
+    /// The keys going down.
+    #[default]
+    Press,
+    /// A press shorter than `long_press_ms`, triggering on release. Pair it
+    /// with a `long_press` binding on the same keys for tap versus hold.
+    Tap,
+    /// Two taps with at most `double_tap_ms` between them.
+    DoubleTap,
+    /// Keys held for `long_press_ms`. A `hold` action lasts until release.
+    LongPress,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    /// Starts recording, or stops and transcribes when already recording.
+    #[default]
+    Toggle,
+    /// Records while the shortcut is held down.
+    Hold,
+    /// Aborts the recording, or the transcription in flight, without
+    /// injecting anything. Only listened for while busy.
+    Cancel,
+    /// Injects the last dictation again.
+    Reinject,
+    /// Makes `provider` the transcription provider until the config reloads.
+    SwitchProvider,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(default)]
 pub struct PasteHintsConfig {
@@ -68,6 +183,26 @@ pub struct Config {
     #[serde(default)]
     pub word_overrides: HashMap<String, String>,
 
+    /// Terms listed in the transcription prompt as a glossary and turned into
+    /// word overrides, so recognition and spelling share one list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vocabulary: Vec<VocabularyTerm>,
+
+    /// Blocks injected when their trigger phrase is spoken, e.g.
+    /// "insert signature". Supports `{date}`, `{time}` and `{clipboard}`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub snippets: HashMap<String, SnippetConfig>,
+
+    /// Ordered regex replacements applied during text normalization.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub text_rules: Vec<TextRuleConfig>,
+
+    #[serde(default)]
+    pub normalization: NormalizationConfig,
+
+    #[serde(default)]
+    pub smart_spacing: SmartSpacingConfig,
+
     #[serde(default)]
     pub audio_feedback: bool,
 
@@ -83,6 +218,11 @@ pub struct Config {
     #[serde(default)]
     pub stop_sound_path: Option<String>,
 
+    /// Played when a recording is cancelled; defaults to the stop sound at a
+    /// lower pitch.
+    #[serde(default)]
+    pub cancel_sound_path: Option<String>,
+
     #[serde(default = "default_auto_copy_clipboard")]
     pub auto_copy_clipboard: bool,
 
@@ -101,9 +241,20 @@ pub struct Config {
     #[serde(default)]
     pub fast_vad: FastVadConfig,
 
+    #[serde(default)]
+    pub streaming: StreamingConfig,
+
     #[serde(default)]
     pub transcription: TranscriptionConfig,
 
+    #[serde(default)]
+    pub post_processing: PostProcessingConfig,
+
+    /// Per-application overrides, matched on the window class focused when
+    /// recording starts. The first matching profile wins.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<AppProfileConfig>,
+
     #[serde(default, rename = "model", skip_serializing)]
     legacy_model: Option<String>,
 
@@ -137,6 +288,22 @@ fn default_primary_shortcut() -> String {
     "SUPER+ALT+R".to_string() // R for Rust version (Python uses D)
 }
 
+fn default_cancel_shortcut() -> Option<String> {
+    Some("ESC".to_string())
+}
+
+fn default_long_press_ms() -> u64 {
+    400
+}
+
+fn default_double_tap_ms() -> u64 {
+    300
+}
+
+fn default_device_classes() -> Vec<InputDeviceClass> {
+    vec![InputDeviceClass::Keyboard]
+}
+
 fn default_model() -> String {
     "base".to_string()
 }
@@ -169,6 +336,18 @@ fn default_no_speech_threshold() -> f32 {
     0.60
 }
 
+fn default_whisper_server_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_whisper_server_port() -> u16 {
+    18181
+}
+
+fn default_whisper_server_startup_timeout_secs() -> u64 {
+    60
+}
+
 fn default_vad_model() -> String {
     "ggml-silero-v5.1.2.bin".to_string()
 }
@@ -197,6 +376,41 @@ where
     Ok(value.unwrap_or_else(default_vad_max_speech_s))
 }
 
+fn deserialize_normalize_steps<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(steps) = Option::<Vec<String>>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    let known = normalize_step_names();
+    for (index, step) in steps.iter().enumerate() {
+        if !known.contains(&step.as_str()) {
+            return Err(D::Error::custom(format!(
+                "unknown normalization step '{step}', expected one of: {}",
+                known.join(", ")
+            )));
+        }
+        if steps[..index].contains(step) {
+            return Err(D::Error::custom(format!(
+                "normalization step '{step}' is listed more than once"
+            )));
+        }
+    }
+    Ok(Some(steps))
+}
+
+fn normalize_steps_schema(_generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": ["array", "null"],
+        "items": {
+            "type": "string",
+            "enum": normalize_step_names()
+        },
+        "uniqueItems": true
+    })
+}
+
 fn is_f32_non_finite(value: &f32) -> bool {
     !value.is_finite()
 }
@@ -209,6 +423,10 @@ fn default_vad_samples_overlap() -> f32 {
     0.10
 }
 
+fn default_transcription_language() -> String {
+    "en".to_string()
+}
+
 fn default_transcription_request_timeout_secs() -> u64 {
     45
 }
@@ -269,6 +487,14 @@ fn default_fast_vad_volatility_decrease_threshold() -> f32 {
     0.12
 }
 
+fn default_streaming_phrase_silence_ms() -> u32 {
+    700
+}
+
+const DEFAULT_POST_PROCESSING_PROMPT: &str = "Clean up this dictated text. Fix grammar and \
+punctuation and remove filler words such as \"um\" and \"like\". Keep the wording and meaning \
+otherwise unchanged. Reply with the cleaned text only.";
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(default)]
 pub struct VadConfig {
@@ -348,7 +574,276 @@ impl Default for FastVadConfig {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Transcribes and injects each phrase while the recording is still running.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(default)]
+pub struct StreamingConfig {
+    pub enabled: bool,
+    /// Pause (ms) that ends a phrase. Uses the `fast_vad` detector settings
+    /// otherwise, whether or not `fast_vad` trimming is enabled.
+    pub phrase_silence_ms: u32,
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            phrase_silence_ms: default_streaming_phrase_silence_ms(),
+        }
+    }
+}
+
+/// Sends the normalized text through an OpenAI-compatible chat completions
+/// endpoint before injection. Any failure injects the unprocessed text.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(default)]
+pub struct PostProcessingConfig {
+    pub enabled: bool,
+    pub base_url: ValueSource,
+    pub endpoint: String,
+    pub model: String,
+    pub api_key: SecretSource,
+    pub system_prompt: String,
+    pub temperature: f32,
+    /// Gives up and injects the unprocessed text after this long.
+    pub timeout_ms: u64,
+}
+
+impl Default for PostProcessingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: ValueSource::default(),
+            endpoint: "/v1/chat/completions".to_string(),
+            model: String::new(),
+            api_key: SecretSource::default(),
+            system_prompt: DEFAULT_POST_PROCESSING_PROMPT.to_string(),
+            temperature: 0.0,
+            timeout_ms: 5000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(default)]
+pub struct AppProfileConfig {
+    pub name: String,
+    /// Window classes this profile applies to. Matched case-insensitively
+    /// against the whole class or one of its `.`/`-`/`_` separated parts, like
+    /// `paste_hints`.
+    pub classes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<TranscriptionProvider>,
+    /// Replaces the prompt of the profile's provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Merged over the global `word_overrides`.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub word_overrides: HashMap<String, String>,
+    /// Replaces the global `normalization` settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub normalization: Option<NormalizationConfig>,
+}
+
+/// Joins each dictation onto the text last injected into the same window:
+/// a leading space, a capital after a finished sentence, and no
+/// sentence-start capital in the middle of one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(default)]
+pub struct SmartSpacingConfig {
+    pub enabled: bool,
+    /// Window classes, such as chat apps, where a trailing period is dropped.
+    pub drop_trailing_period: Vec<String>,
+}
+
+/// Snippet text, inline or read from a file each time it is used.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SnippetConfig {
+    Text(String),
+    /// Relative paths resolve against the config directory.
+    File {
+        file: String,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(default)]
+pub struct VocabularyTerm {
+    /// Spelling to produce, e.g. `Hyprland`. Matched case-insensitively, so
+    /// the term also fixes its own capitalization.
+    pub term: String,
+    /// Spoken or misheard forms rewritten to `term`, e.g. `hyper land`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+/// Built-in text normalization steps.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(default)]
+pub struct NormalizationConfig {
+    /// Built-in steps to run, in order. Steps left out are disabled; omit the
+    /// list to run the default pipeline. Must include `remove_disfluencies`
+    /// and `spoken_commands` while those are enabled, ahead of
+    /// `control_commands`.
+    #[serde(
+        deserialize_with = "deserialize_normalize_steps",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(schema_with = "normalize_steps_schema")]
+    pub steps: Option<Vec<String>>,
+    pub disfluencies: DisfluencyConfig,
+    pub spoken_commands: SpokenCommandsConfig,
+    pub edit_commands: EditCommandsConfig,
+    pub itn: ItnConfig,
+}
+
+/// The `remove_disfluencies` step. It also collapses repeated words ("the
+/// the", but not "that that") and drops false starts corrected with "I mean".
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(default)]
+pub struct DisfluencyConfig {
+    /// Off by default; the step is skipped until enabled.
+    pub enabled: bool,
+    /// Words and phrases to remove, matched case-insensitively. Single words
+    /// go wherever they occur; phrases only when set off by commas or the
+    /// sentence edges.
+    pub fillers: Vec<String>,
+}
+
+impl Default for DisfluencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fillers: [
+                "um", "umm", "uh", "uhh", "er", "erm", "ah", "hmm", "you know",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        }
+    }
+}
+
+/// The `spoken_commands` step: spoken punctuation and quotes, formatting
+/// commands such as "camel case", and "literal" to escape them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(default)]
+pub struct SpokenCommandsConfig {
+    /// Off by default; the step is skipped until enabled.
+    pub enabled: bool,
+}
+
+/// Voice editing of the last dictation: "scratch that", "undo last" and
+/// "replace X with Y".
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(default)]
+pub struct EditCommandsConfig {
+    /// Off by default; such utterances are typed as dictation until enabled.
+    pub enabled: bool,
+}
+
+/// Inverse text normalization engine used by the `inverse_text_normalization` step.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(default)]
+pub struct ItnConfig {
+    pub engine: ItnEngineKind,
+    /// Program and arguments for the `command` engine. It reads the text on
+    /// stdin and writes the normalized text to stdout.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub command: Vec<String>,
+    /// How long the `command` engine may run before the text is passed
+    /// through unchanged.
+    pub timeout_ms: u64,
+}
+
+impl Default for ItnConfig {
+    fn default() -> Self {
+        Self {
+            engine: ItnEngineKind::default(),
+            command: Vec::new(),
+            timeout_ms: 2_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ItnEngineKind {
+    /// Built-in English numbers, dates, currency and symbols.
+    #[default]
+    English,
+    None,
+    /// An external program, for other languages or custom rules.
+    Command,
+}
+
+/// A user-defined regex replacement in the normalization pipeline.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(default)]
+pub struct TextRuleConfig {
+    /// Shown in the text pipeline debug log; defaults to the rule's position
+    /// in the list.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub pattern: String,
+    /// Replacement text; `$1` or `${name}` insert capture groups.
+    pub replacement: String,
+    pub case_sensitive: bool,
+    /// Only match where the pattern starts and ends on a word boundary.
+    pub whole_word: bool,
+    pub position: TextRulePosition,
+}
+
+/// Where a text rule runs relative to the built-in normalization steps.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextRulePosition {
+    /// On the words as spoken, before spoken commands and inverse text
+    /// normalization.
+    BeforeItn,
+    #[default]
+    AfterControlCommands,
+    /// After every built-in step, on the final text.
+    Last,
+}
+
+/// Drops phantom text that Whisper-style models produce on silence or noise.
+/// By default only phantom phrases are removed; the repetition and audio
+/// level checks are opt-in.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(default)]
+pub struct HallucinationFilterConfig {
+    pub enabled: bool,
+    /// Phrases removed when they make up the transcript or its trailing
+    /// sentence, in addition to the built-in list ("Thank you for watching", ...).
+    pub phrases: Vec<String>,
+    /// A run of words repeated more than this many times in a row is collapsed
+    /// to a single occurrence. 0 leaves repetitions alone.
+    pub max_repeats: usize,
+    /// Any text from audio shorter than this (ms) is discarded. 0 disables the
+    /// check.
+    pub min_audio_ms: u32,
+    /// Any text from audio with an RMS level below this (0.0-1.0) is discarded.
+    /// 0.0 disables the check.
+    pub min_rms: f32,
+}
+
+impl Default for HallucinationFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            phrases: Vec::new(),
+            max_repeats: 0,
+            min_audio_ms: 0,
+            min_rms: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TranscriptionProvider {
     WhisperCpp,
     Groq,
@@ -444,6 +939,46 @@ pub struct WhisperCppConfig {
     pub no_speech_threshold: f32,
     pub models_dirs: Vec<String>,
     pub vad: VadConfig,
+    /// Overrides `transcription.language` for this provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// `cli` spawns whisper-cli per utterance; `server` keeps a supervised
+    /// whisper-server running with the model loaded.
+    pub mode: WhisperCppMode,
+    pub server: WhisperServerConfig,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WhisperCppMode {
+    #[default]
+    Cli,
+    Server,
+    /// In-process whisper-rs inference; requires the `whisper-native` cargo feature.
+    Native,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(default)]
+pub struct WhisperServerConfig {
+    /// Path to whisper-server; discovered next to whisper-cli when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<String>,
+    pub host: String,
+    pub port: u16,
+    /// How long to wait for the server to load the model and report healthy.
+    pub startup_timeout_secs: u64,
+}
+
+impl Default for WhisperServerConfig {
+    fn default() -> Self {
+        Self {
+            binary: None,
+            host: default_whisper_server_host(),
+            port: default_whisper_server_port(),
+            startup_timeout_secs: default_whisper_server_startup_timeout_secs(),
+        }
+    }
 }
 
 impl Default for WhisperCppConfig {
@@ -457,6 +992,9 @@ impl Default for WhisperCppConfig {
             no_speech_threshold: default_no_speech_threshold(),
             models_dirs: default_models_dirs(),
             vad: VadConfig::default(),
+            language: None,
+            mode: WhisperCppMode::default(),
+            server: WhisperServerConfig::default(),
         }
     }
 }
@@ -467,6 +1005,9 @@ pub struct GroqConfig {
     pub model: String,
     pub endpoint: String,
     pub prompt: String,
+    /// Overrides `transcription.language` for this provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 impl Default for GroqConfig {
@@ -475,6 +1016,7 @@ impl Default for GroqConfig {
             model: default_groq_model(),
             endpoint: default_groq_endpoint(),
             prompt: default_whisper_prompt(),
+            language: None,
         }
     }
 }
@@ -487,6 +1029,9 @@ pub struct GeminiConfig {
     pub temperature: f32,
     pub max_output_tokens: u32,
     pub prompt: String,
+    /// Overrides `transcription.language` for this provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 impl Default for GeminiConfig {
@@ -497,6 +1042,7 @@ impl Default for GeminiConfig {
             temperature: default_gemini_temperature(),
             max_output_tokens: default_gemini_max_output_tokens(),
             prompt: default_whisper_prompt(),
+            language: None,
         }
     }
 }
@@ -511,6 +1057,9 @@ fn default_parakeet_model_dir() -> String {
 pub struct ParakeetConfig {
     pub model_dir: String,
     pub prompt: String,
+    /// Overrides `transcription.language` for this provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 impl Default for ParakeetConfig {
@@ -518,6 +1067,7 @@ impl Default for ParakeetConfig {
         Self {
             model_dir: default_parakeet_model_dir(),
             prompt: default_whisper_prompt(),
+            language: None,
         }
     }
 }
@@ -527,6 +1077,11 @@ impl Default for ParakeetConfig {
 pub enum CustomProviderKind {
     #[serde(rename = "openai_audio_transcriptions")]
     OpenAiAudioTranscriptions,
+    /// POSTs the encoded audio as the raw request body (Deepgram-style);
+    /// `model` and `language` travel as query parameters.
+    RawAudio,
+    /// POSTs a JSON object with the audio base64-encoded in `audio_field`.
+    JsonBase64,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
@@ -753,9 +1308,27 @@ pub struct CustomProviderConfig {
     pub audio_format: String,
     pub api_key: SecretSource,
     pub subscription: SubscriptionAuthSource,
+    /// Authorization scheme placed before the resolved token, e.g. `Token` for
+    /// Deepgram. Empty sends the bare token.
+    pub auth_scheme: String,
     pub headers: HashMap<String, String>,
     pub body: HashMap<String, String>,
+    /// Query parameters appended to the endpoint URL.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub query: HashMap<String, String>,
+    /// `raw_audio` request Content-Type; defaults to the MIME type of `audio_format`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// `json_base64` body field that carries the encoded audio.
+    pub audio_field: String,
+    /// JSON Pointer for the transcript inside the response body.
+    pub transcript_pointer: String,
+    /// JSON Pointer for the detected language, when the provider reports one.
+    pub language_pointer: String,
     pub prompt: String,
+    /// Overrides `transcription.language` for this provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 impl Default for CustomProviderConfig {
@@ -769,9 +1342,16 @@ impl Default for CustomProviderConfig {
             audio_format: "wav".to_string(),
             api_key: SecretSource::default(),
             subscription: SubscriptionAuthSource::default(),
+            auth_scheme: "Bearer".to_string(),
             headers: HashMap::new(),
             body: HashMap::new(),
+            query: HashMap::new(),
+            content_type: None,
+            audio_field: "audio".to_string(),
+            transcript_pointer: "/text".to_string(),
+            language_pointer: "/language".to_string(),
             prompt: default_whisper_prompt(),
+            language: None,
         }
     }
 }
@@ -780,8 +1360,14 @@ impl Default for CustomProviderConfig {
 #[serde(default)]
 pub struct TranscriptionConfig {
     pub provider: TranscriptionProvider,
+    /// ISO 639-1 code such as "en" or "de", or "auto" to let the backend detect it.
+    pub language: String,
+    /// Providers tried in order with the same audio when the primary provider fails.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fallback: Vec<TranscriptionProvider>,
     pub request_timeout_secs: u64,
     pub max_retries: u32,
+    pub hallucination_filter: HallucinationFilterConfig,
     pub whisper_cpp: WhisperCppConfig,
     pub groq: GroqConfig,
     pub gemini: GeminiConfig,
@@ -793,8 +1379,11 @@ impl Default for TranscriptionConfig {
     fn default() -> Self {
         Self {
             provider: TranscriptionProvider::default(),
+            language: default_transcription_language(),
+            fallback: Vec::new(),
             request_timeout_secs: default_transcription_request_timeout_secs(),
             max_retries: default_transcription_max_retries(),
+            hallucination_filter: HallucinationFilterConfig::default(),
             whisper_cpp: WhisperCppConfig::default(),
             groq: GroqConfig::default(),
             gemini: GeminiConfig::default(),
@@ -810,18 +1399,27 @@ impl Default for Config {
             primary_shortcut: default_primary_shortcut(),
             shortcuts: ShortcutsConfig::default(),
             word_overrides: HashMap::new(),
+            vocabulary: Vec::new(),
+            snippets: HashMap::new(),
             audio_feedback: false,
             start_sound_volume: default_volume(),
             stop_sound_volume: default_volume(),
             start_sound_path: None,
             stop_sound_path: None,
+            cancel_sound_path: None,
             auto_copy_clipboard: default_auto_copy_clipboard(),
             shift_paste: default_shift_paste(),
             global_paste_shortcut: false,
             paste_hints: PasteHintsConfig::default(),
             audio_device: None,
             fast_vad: FastVadConfig::default(),
+            streaming: StreamingConfig::default(),
             transcription: TranscriptionConfig::default(),
+            post_processing: PostProcessingConfig::default(),
+            profiles: Vec::new(),
+            text_rules: Vec::new(),
+            normalization: NormalizationConfig::default(),
+            smart_spacing: SmartSpacingConfig::default(),
             legacy_model: None,
             legacy_threads: None,
             legacy_gpu_layers: None,
@@ -851,21 +1449,26 @@ impl Config {
             .hold
             .as_ref()
             .and_then(|value| Self::sanitize_shortcut(value));
+        self.shortcuts.cancel = self
+            .shortcuts
+            .cancel
+            .as_ref()
+            .and_then(|value| Self::sanitize_shortcut(value));
 
         if let (Some(current), Some(legacy)) = (&self.shortcuts.press, &legacy_primary) {
             if current != legacy {
                 // self.shortcuts.press = Some(legacy.clone());
                 let uses_default_primary = legacy == &default_primary;
-                let has_hold_only = self.shortcuts.hold.is_some();
-                if !(uses_default_primary && has_hold_only) {
+                let has_other_shortcuts = self.has_non_press_shortcuts();
+                if !(uses_default_primary && has_other_shortcuts) {
                     self.shortcuts.press = Some(legacy.clone());
                 }
             }
         } else if self.shortcuts.press.is_none() {
             if let Some(legacy) = &legacy_primary {
                 let uses_default_primary = legacy == &default_primary;
-                let has_hold_only = self.shortcuts.hold.is_some();
-                if !(uses_default_primary && has_hold_only) {
+                let has_other_shortcuts = self.has_non_press_shortcuts();
+                if !(uses_default_primary && has_other_shortcuts) {
                     self.shortcuts.press = Some(legacy.clone());
                 }
             }
@@ -927,6 +1530,37 @@ impl Config {
         self.shortcuts.hold.as_deref()
     }
 
+    fn has_non_press_shortcuts(&self) -> bool {
+        self.shortcuts.hold.is_some() || !self.shortcuts.bindings.is_empty()
+    }
+
+    /// `word_overrides` plus a rule for every vocabulary term and alias.
+    /// Explicit `word_overrides` entries win over generated ones.
+    pub fn effective_word_overrides(&self) -> HashMap<String, String> {
+        let mut overrides = HashMap::new();
+        for entry in &self.vocabulary {
+            let term = entry.term.trim();
+            if term.is_empty() {
+                continue;
+            }
+            for spoken in
+                std::iter::once(term).chain(entry.aliases.iter().map(|alias| alias.trim()))
+            {
+                if !spoken.is_empty() {
+                    overrides.insert(spoken.to_string(), term.to_string());
+                }
+            }
+        }
+        overrides.retain(|spoken, _| {
+            !self
+                .word_overrides
+                .keys()
+                .any(|explicit| explicit.trim().eq_ignore_ascii_case(spoken))
+        });
+        overrides.extend(self.word_overrides.clone());
+        overrides
+    }
+
     fn sanitize_shortcut(value: &str) -> Option<String> {
         let trimmed = value.trim();
         if trimmed.is_empty() {
@@ -994,6 +1628,20 @@ impl ConfigManager {
         })
     }
 
+    /// A manager over `config` that never touches the user's config file.
+    #[cfg(test)]
+    pub(crate) fn in_memory(config: Config) -> Self {
+        let (change_tx, _) = watch::channel(config.clone());
+        Self {
+            inner: Arc::new(ConfigManagerInner {
+                config: RwLock::new(config),
+                config_path: env::temp_dir().join("hyprwhspr-rs-test-config.jsonc"),
+                change_tx,
+                watcher_active: AtomicBool::new(false),
+            }),
+        }
+    }
+
     pub fn start_watching(&self) {
         if self.inner.watcher_active.swap(true, Ordering::SeqCst) {
             return;
@@ -1081,6 +1729,15 @@ impl ConfigManager {
         Self::discover_whisper_binary_candidates(include_fallbacks)
     }
 
+    pub fn get_whisper_server_binary_candidates(&self, config: &Config) -> Vec<PathBuf> {
+        match &config.transcription.whisper_cpp.server.binary {
+            Some(binary) if !binary.trim().is_empty() => {
+                vec![expand_tilde(binary)]
+            }
+            _ => Self::discover_whisper_server_candidates(),
+        }
+    }
+
     pub fn get_temp_dir(&self) -> PathBuf {
         let data_dir = directories::ProjectDirs::from("", "", "hyprwhspr-rs")
             .expect("Failed to get data directory")
@@ -1147,6 +1804,33 @@ impl ConfigManager {
         candidates
     }
 
+    fn discover_whisper_server_candidates() -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+        let mut push_candidate = |path: PathBuf| {
+            if path.exists() && !candidates.contains(&path) {
+                candidates.push(path);
+            }
+        };
+
+        if let Some(project_dirs) = directories::ProjectDirs::from("", "", "hyprwhspr-rs") {
+            let local_dir = project_dirs.data_dir().join("whisper.cpp");
+            push_candidate(local_dir.join("build/bin/whisper-server"));
+            push_candidate(local_dir.join("whisper-server"));
+        }
+
+        if let Ok(home) = env::var("HOME") {
+            let local_dir = PathBuf::from(home).join(".local/share/hyprwhspr-rs/whisper.cpp");
+            push_candidate(local_dir.join("build/bin/whisper-server"));
+            push_candidate(local_dir.join("whisper-server"));
+        }
+
+        for path in Self::find_binaries_on_path(&["whisper-server"]) {
+            push_candidate(path);
+        }
+
+        candidates
+    }
+
     fn find_binaries_on_path(names: &[&str]) -> Vec<PathBuf> {
         let Some(path_os) = env::var_os("PATH") else {
             return Vec::new();
@@ -1363,7 +2047,7 @@ impl ConfigManager {
 
 #[cfg(test)]
 mod tests {
-    use super::{Config, ConfigManager};
+    use super::{Config, ConfigManager, ShortcutAction, TranscriptionProvider};
     use std::fs;
     use std::sync::Mutex;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -1463,6 +2147,27 @@ mod tests {
         fs::remove_dir_all(&root).expect("cleanup");
     }
 
+    #[test]
+    fn named_bindings_replace_the_default_press_shortcut() {
+        let config = ConfigManager::parse_config(
+            r#"{
+                "shortcuts": {
+                    "bindings": [
+                        { "name": "groq", "shortcut": "SUPER+G", "provider": "groq" },
+                        { "name": "again", "shortcut": "SUPER+V", "action": "reinject" },
+                    ],
+                },
+            }"#,
+        )
+        .expect("config");
+
+        assert_eq!(config.shortcuts.press, None);
+        let bindings = &config.shortcuts.bindings;
+        assert_eq!(bindings[0].action, ShortcutAction::Toggle);
+        assert_eq!(bindings[0].provider, Some(TranscriptionProvider::Groq));
+        assert_eq!(bindings[1].action, ShortcutAction::Reinject);
+    }
+
     #[test]
     fn assets_dir_can_be_overridden_by_env() {
         let _guard = ENV_LOCK.lock().expect("env lock");
@@ -73,11 +73,63 @@ impl FromStr for RecordCommand {
     }
 }
 
+/// A shortcut press or release sent from outside the app, e.g. by a
+/// compositor bind. `name` is `press`, `hold`, `cancel` or a binding name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortcutTrigger {
+    pub name: String,
+    pub pressed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlCommand {
+    Record(RecordCommand),
+    Shortcut(ShortcutTrigger),
+}
+
+impl fmt::Display for ControlCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Record(command) => f.write_str(command.as_str()),
+            Self::Shortcut(trigger) => {
+                let phase = if trigger.pressed { "press" } else { "release" };
+                write!(f, "shortcut {phase} {}", trigger.name)
+            }
+        }
+    }
+}
+
+impl FromStr for ControlCommand {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        let Some(rest) = value.strip_prefix("shortcut ") else {
+            return RecordCommand::from_str(value).map(Self::Record);
+        };
+
+        let (phase, name) = rest.trim_start().split_once(' ').unwrap_or((rest, ""));
+        let pressed = match phase {
+            "press" => true,
+            "release" => false,
+            other => return Err(anyhow!("Unknown shortcut phase: {other}")),
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("Shortcut command needs a shortcut name");
+        }
+        Ok(Self::Shortcut(ShortcutTrigger {
+            name: name.to_string(),
+            pressed,
+        }))
+    }
+}
+
 pub type ControlReply = std::result::Result<RecorderState, String>;
 
 #[derive(Debug)]
 pub struct ControlRequest {
-    pub command: RecordCommand,
+    pub command: ControlCommand,
     pub reply_tx: oneshot::Sender<ControlReply>,
 }
 
@@ -141,7 +193,7 @@ mod platform {
         }
     }
 
-    pub async fn send_record_command(command: RecordCommand) -> Result<RecorderState> {
+    pub async fn send_control_command(command: &ControlCommand) -> Result<RecorderState> {
         let path = control_socket_path()?;
         let mut stream = UnixStream::connect(&path).await.with_context(|| {
             format!(
@@ -151,7 +203,7 @@ mod platform {
         })?;
 
         stream
-            .write_all(command.as_str().as_bytes())
+            .write_all(command.to_string().as_bytes())
             .await
             .context("Failed to send control command")?;
         stream
@@ -175,7 +227,7 @@ mod platform {
             .await
             .context("Failed to read control request")?;
 
-        let command = RecordCommand::from_str(String::from_utf8_lossy(&request).trim())?;
+        let command = ControlCommand::from_str(&String::from_utf8_lossy(&request))?;
         let (reply_tx, reply_rx) = oneshot::channel();
         tx.send(ControlRequest { command, reply_tx })
             .await
@@ -242,12 +294,16 @@ mod platform {
         }
     }
 
-    pub async fn send_record_command(_command: RecordCommand) -> Result<RecorderState> {
-        bail!("`hyprwhspr-rs record` is currently only supported on Linux")
+    pub async fn send_control_command(_command: &ControlCommand) -> Result<RecorderState> {
+        bail!("`hyprwhspr-rs record` and `shortcut` are currently only supported on Linux")
     }
 }
 
-pub use platform::{send_record_command, ControlServer};
+pub use platform::{send_control_command, ControlServer};
+
+pub async fn send_record_command(command: RecordCommand) -> Result<RecorderState> {
+    send_control_command(&ControlCommand::Record(command)).await
+}
 
 fn parse_response(raw: &str) -> Result<RecorderState> {
     let body = raw.trim();
@@ -296,6 +352,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn shortcut_commands_round_trip() {
+        let command = ControlCommand::Shortcut(ShortcutTrigger {
+            name: "pedal dictation".to_string(),
+            pressed: false,
+        });
+        assert_eq!(command.to_string(), "shortcut release pedal dictation");
+        assert_eq!(
+            ControlCommand::from_str(&command.to_string()).unwrap(),
+            command
+        );
+        assert_eq!(
+            ControlCommand::from_str("toggle\n").unwrap(),
+            ControlCommand::Record(RecordCommand::Toggle)
+        );
+        assert!(ControlCommand::from_str("shortcut press").is_err());
+        assert!(ControlCommand::from_str("shortcut tap hold").is_err());
+    }
+
     #[test]
     fn rejects_unknown_response() {
         assert!(parse_response("wat").is_err());
@@ -110,6 +110,19 @@ impl HyprlandDispatcher {
         Ok(None)
     }
 
+    pub(super) async fn active_window_address(&self) -> Result<Option<String>> {
+        let response = self.send_command("j/activewindow").await?;
+        if Self::is_unknown_request(&response) {
+            return Ok(None);
+        }
+        if let Some(address) = Self::extract_window_address_from_response(&response) {
+            return Ok(Some(address));
+        }
+
+        let plain_response = self.send_command("activewindow").await?;
+        Ok(Self::extract_window_address_from_response(&plain_response))
+    }
+
     async fn send_command(&self, command: &str) -> Result<String> {
         let mut stream = UnixStream::connect(&self.socket_path)
             .await
@@ -220,6 +233,24 @@ impl HyprlandDispatcher {
         Err(anyhow!("No class entry found in Hyprland response"))
     }
 
+    fn extract_window_address_from_response(response: &str) -> Option<String> {
+        let trimmed = response.trim();
+        let address = match serde_json::from_str::<Value>(trimmed) {
+            Ok(value) => value
+                .get("address")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            // Plain text starts with "Window 55d2f1a0 -> title:".
+            Err(_) => trimmed
+                .strip_prefix("Window ")
+                .and_then(|rest| rest.split_whitespace().next())
+                .map(str::to_string),
+        };
+        address
+            .map(|address| Self::normalize_address(&address))
+            .filter(|address| !address.is_empty())
+    }
+
     fn extract_class_from_clients_json(text: &str, address: &str) -> Option<String> {
         let Ok(value) = serde_json::from_str::<Value>(text) else {
             return None;
@@ -335,4 +366,18 @@ Title: sample
         let class = super::HyprlandDispatcher::extract_window_class_from_response(sample).unwrap();
         assert_eq!(class, Some("foot".to_string()));
     }
+
+    #[test]
+    fn extracts_window_address_from_json_and_plain_output() {
+        let json = r#"{"address":"0x55D2F1A0","class":"foot"}"#;
+        let plain = "Window 55d2f1a0 -> shell:\n\tclass: foot";
+        for sample in [json, plain] {
+            let address = super::HyprlandDispatcher::extract_window_address_from_response(sample);
+            assert_eq!(address, Some("55d2f1a0".to_string()));
+        }
+        assert_eq!(
+            super::HyprlandDispatcher::extract_window_address_from_response("Invalid"),
+            None
+        );
+    }
 }
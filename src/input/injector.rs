@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use arboard::Clipboard;
 use enigo::{Keyboard, Settings};
+use std::collections::VecDeque;
 use std::env;
 use tracing::{debug, info, warn};
 use wl_clipboard_rs::copy::{ClipboardType, Error as WlCopyError, MimeType, Options, Source};
@@ -11,6 +12,11 @@ use crate::input::paste_route::{
     PasteRoute, PasteRouteContext, PasteShortcut, class_paste_hint_for_class,
     normalize_hint_classes, plan_paste_routes,
 };
+use crate::text::{EditCommand, EditPlan, InjectionContext};
+
+/// Windows whose last injection is kept for smart spacing; the least
+/// recently dictated-into window is forgotten first.
+const MAX_WINDOW_INJECTIONS: usize = 32;
 
 pub struct TextInjector {
     clipboard: Clipboard,
@@ -23,6 +29,17 @@ pub struct TextInjector {
     wrtype_attempted: bool,
     wayland_env: bool,
     wayland_clipboard_enabled: bool,
+    /// The last successful injection, for voice editing commands.
+    last_injection: Option<LastInjection>,
+    /// Last injection per Hyprland window address, for smart spacing, most
+    /// recent window last.
+    window_injections: VecDeque<(String, String)>,
+}
+
+/// Text of an injection and the Hyprland address of the window it went to.
+struct LastInjection {
+    text: String,
+    window: Option<String>,
 }
 
 impl TextInjector {
@@ -57,6 +74,8 @@ impl TextInjector {
             wrtype_attempted: false,
             wayland_env,
             wayland_clipboard_enabled: wayland_env,
+            last_injection: None,
+            window_injections: VecDeque::new(),
         })
     }
 
@@ -83,14 +102,132 @@ impl TextInjector {
             wayland_env: self.wayland_env,
         });
 
+        let mut last_error = None;
         for route in routes {
             match self.try_paste_route(route).await {
-                Ok(()) => return Ok(()),
-                Err(err) => warn!(route = ?route, "Paste route failed: {err:?}"),
+                Ok(()) => {
+                    let window = self.active_window_address().await;
+                    self.remember_window_injection(window.clone(), text);
+                    self.last_injection = Some(LastInjection {
+                        text: text.to_string(),
+                        window,
+                    });
+                    return Ok(());
+                }
+                Err(err) => {
+                    warn!(route = ?route, "Paste route failed: {err:?}");
+                    last_error = Some(err);
+                }
             }
         }
 
-        Ok(())
+        Err(last_error
+            .unwrap_or_else(|| anyhow::anyhow!("No paste route available"))
+            .context("Failed to inject text"))
+    }
+
+    /// Injects the last dictation again, e.g. into a window focused since.
+    pub async fn reinject_last(&mut self) -> Result<()> {
+        let Some(last) = self.last_injection.as_ref().map(|last| last.text.clone()) else {
+            warn!("Nothing injected yet; ignoring re-inject");
+            return Ok(());
+        };
+        self.inject_text(&last).await
+    }
+
+    /// Applies a voice editing command to the last injected text and
+    /// returns whether it did. The edit is refused when focus moved to
+    /// another window since that injection, as the backspaces would land in
+    /// the wrong place; callers then type the utterance as dictation.
+    pub async fn apply_edit(&mut self, command: &EditCommand) -> Result<bool> {
+        let window = match self.last_injection {
+            Some(_) => self.active_window_address().await,
+            None => None,
+        };
+        let Some(plan) = plan_edit(self.last_injection.as_ref(), window.as_deref(), command) else {
+            return Ok(false);
+        };
+        let Some(last) = self.last_injection.take() else {
+            return Ok(false);
+        };
+
+        debug!(erase = plan.erase, "Applying editing command {command:?}");
+        if let Err(err) = self.send_backspaces(plan.erase) {
+            self.last_injection = Some(last);
+            return Err(err);
+        }
+        if !plan.insert.is_empty() {
+            if let Err(err) = self.inject_text(&plan.insert).await {
+                // The erase went through, so only the text before it is left.
+                let kept_chars = last.text.chars().count().saturating_sub(plan.erase);
+                let kept: String = last.text.chars().take(kept_chars).collect();
+                self.remember_window_injection(window.clone(), &kept);
+                self.last_injection =
+                    (!kept.is_empty()).then_some(LastInjection { text: kept, window });
+                return Err(err);
+            }
+        }
+        self.remember_window_injection(window.clone(), &plan.remaining);
+        self.last_injection = (!plan.remaining.is_empty()).then_some(LastInjection {
+            text: plan.remaining,
+            window,
+        });
+        info!("✅ Applied editing command {command:?}");
+        Ok(true)
+    }
+
+    /// The focused window and what was last injected into it.
+    pub async fn injection_context(&self) -> InjectionContext {
+        let preceding = match self.active_window_address().await {
+            Some(address) => self
+                .window_injections
+                .iter()
+                .find(|(window, _)| *window == address)
+                .map(|(_, text)| text.clone()),
+            None => None,
+        };
+        InjectionContext {
+            window_class: self.active_window_class().await,
+            preceding,
+        }
+    }
+
+    fn remember_window_injection(&mut self, address: Option<String>, text: &str) {
+        let Some(address) = address else {
+            return;
+        };
+        self.window_injections
+            .retain(|(window, _)| *window != address);
+        if !text.is_empty() {
+            if self.window_injections.len() == MAX_WINDOW_INJECTIONS {
+                self.window_injections.pop_front();
+            }
+            self.window_injections
+                .push_back((address, text.to_string()));
+        }
+    }
+
+    async fn active_window_address(&self) -> Option<String> {
+        let dispatcher = self.hyprland_dispatcher.as_ref()?;
+        match dispatcher.active_window_address().await {
+            Ok(address) => address,
+            Err(err) => {
+                warn!("Failed to query Hyprland active window address: {err:?}");
+                None
+            }
+        }
+    }
+
+    /// Class of the focused window, when Hyprland IPC is available.
+    pub async fn active_window_class(&self) -> Option<String> {
+        let dispatcher = self.hyprland_dispatcher.as_ref()?;
+        match dispatcher.active_window_class().await {
+            Ok(class) => class,
+            Err(err) => {
+                warn!("Failed to query Hyprland active window class: {err:?}");
+                None
+            }
+        }
     }
 
     async fn active_class_paste_hint(&self) -> Option<PasteShortcut> {
@@ -215,6 +352,46 @@ impl TextInjector {
         }
     }
 
+    fn send_backspaces(&mut self, count: usize) -> Result<()> {
+        if count == 0 {
+            return Ok(());
+        }
+
+        // Only the backspaces the virtual keyboard did not send fall back to
+        // Enigo, so text typed before the dictation is never erased.
+        let mut sent = 0;
+        if let Some(client) = self.ensure_wrtype_client() {
+            let mut failure = None;
+            while sent < count {
+                if let Err(err) = client.type_key("BackSpace") {
+                    failure = Some(err);
+                    break;
+                }
+                sent += 1;
+            }
+            match failure {
+                None => return Ok(()),
+                Some(err) => {
+                    warn!(
+                        sent,
+                        "Virtual keyboard backspace failed (falling back to Enigo): {err:?}"
+                    );
+                    self.invalidate_wrtype_client();
+                }
+            }
+        }
+
+        use enigo::{Direction, Key};
+        let mut enigo = enigo::Enigo::new(&Settings::default())
+            .context("Failed to initialize Enigo for backspace")?;
+        for _ in sent..count {
+            enigo
+                .key(Key::Backspace, Direction::Click)
+                .context("Failed to press Backspace")?;
+        }
+        Ok(())
+    }
+
     fn copy_wayland_clipboard(&self, text: &str) -> Result<(), WlCopyError> {
         let bytes = text.as_bytes().to_vec();
 
@@ -310,6 +487,29 @@ impl TextInjector {
     }
 }
 
+/// The edit `command` makes to the last injection, or `None` when it does
+/// not apply: nothing was injected yet, the text does not fit the command,
+/// or focus is on another window now.
+fn plan_edit(
+    last: Option<&LastInjection>,
+    window: Option<&str>,
+    command: &EditCommand,
+) -> Option<EditPlan> {
+    let Some(last) = last else {
+        warn!("Nothing injected yet; typing {command:?} as dictation");
+        return None;
+    };
+    let Some(plan) = command.plan(&last.text) else {
+        warn!("{command:?} does not apply to the last injection; typing it as dictation");
+        return None;
+    };
+    if window != last.window.as_deref() {
+        warn!("Focus moved since the last injection; typing {command:?} as dictation");
+        return None;
+    }
+    Some(plan)
+}
+
 fn send_virtual_keyboard_paste(client: &mut WrtypeClient, use_shift: bool) -> Result<()> {
     if use_shift {
         client.send_shortcut(&[Modifier::Ctrl, Modifier::Shift], "v")
@@ -322,3 +522,38 @@ fn send_virtual_keyboard_global_paste(client: &mut WrtypeClient) -> Result<()> {
     // Universal paste: Shift+Insert works in most applications including terminals
     client.send_shortcut(&[Modifier::Shift], "Insert")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn injected(text: &str, window: &str) -> LastInjection {
+        LastInjection {
+            text: text.to_string(),
+            window: Some(window.to_string()),
+        }
+    }
+
+    #[test]
+    fn edits_that_do_not_apply_fall_through_to_dictation() {
+        let replace = EditCommand::Replace {
+            from: "battery".to_string(),
+            to: "charger".to_string(),
+        };
+        let last = injected("Swap the cable", "0x1");
+
+        assert_eq!(
+            plan_edit(None, Some("0x1"), &EditCommand::ScratchThat),
+            None
+        );
+        assert_eq!(plan_edit(Some(&last), Some("0x1"), &replace), None);
+        assert_eq!(
+            plan_edit(Some(&last), Some("0x2"), &EditCommand::ScratchThat),
+            None
+        );
+
+        let plan = plan_edit(Some(&last), Some("0x1"), &EditCommand::ScratchThat)
+            .expect("scratch applies");
+        assert_eq!(plan.erase, "Swap the cable".len());
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
@@ -6,11 +7,14 @@ use tokio::sync::{mpsc, oneshot};
 use tokio::time;
 use tracing::{debug, error, info, warn};
 
-use crate::config::ShortcutsConfig;
+use crate::config::{ShortcutSource, ShortcutsConfig};
+use crate::control::ShortcutTrigger;
 use crate::input::shortcuts::{
-    ShortcutController, ShortcutEvent, ShortcutInput, ShortcutPhase, ShortcutSummary,
+    ShortcutController, ShortcutEvent, ShortcutInput, ShortcutKind, ShortcutPhase, ShortcutSummary,
+};
+use crate::input::source::{
+    CompositorEventSource, EvdevUdevEventSource, InputEventSource, InputSourceEvent,
 };
-use crate::input::source::{EvdevUdevEventSource, InputEventSource, InputSourceEvent};
 
 const EVDEV_TICK: Duration = Duration::from_millis(10);
 const MAX_KEY_EVENTS_PER_TICK: usize = 256;
@@ -28,7 +32,7 @@ pub struct InputStats {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::input::shortcuts::ShortcutKind;
+    use crate::config::InputDeviceClass;
     use evdev::Key;
     use std::collections::HashSet;
 
@@ -59,6 +63,19 @@ mod tests {
             &self.pressed_keys
         }
 
+        fn pressed_keys_on(&self, _device: &str) -> HashSet<Key> {
+            self.pressed_keys.clone()
+        }
+
+        fn set_device_filters(&mut self, _filters: &[String]) {}
+
+        fn set_device_classes(
+            &mut self,
+            _classes: &[InputDeviceClass],
+        ) -> Result<Vec<InputSourceEvent>> {
+            Ok(Vec::new())
+        }
+
         fn device_count(&self) -> usize {
             self.devices.len()
         }
@@ -66,12 +83,18 @@ mod tests {
         fn device_paths(&self) -> Vec<PathBuf> {
             self.devices.clone()
         }
+
+        fn accept_trigger(&mut self, _trigger: &ShortcutTrigger) -> Result<()> {
+            Ok(())
+        }
     }
 
     fn shortcuts(press: Option<&str>, hold: Option<&str>) -> ShortcutsConfig {
         ShortcutsConfig {
             press: press.map(str::to_string),
             hold: hold.map(str::to_string),
+            cancel: Some("ESC".to_string()),
+            ..ShortcutsConfig::default()
         }
     }
 
@@ -140,6 +163,69 @@ mod tests {
         assert_eq!(end.phase, ShortcutPhase::Cancel);
     }
 
+    #[tokio::test]
+    async fn cancel_shortcut_is_only_delivered_while_busy() {
+        let (event_tx, mut event_rx) = mpsc::channel(8);
+        let mut source = TestInputSource::new();
+        source.press("ESC");
+        let mut manager = test_manager(source, event_tx);
+
+        manager
+            .dispatch_source_events(vec![InputSourceEvent::KeyStateChanged { key_events: 1 }])
+            .await;
+        assert!(event_rx.try_recv().is_err());
+
+        manager.source.clear();
+        manager
+            .dispatch_source_events(vec![InputSourceEvent::KeyStateChanged { key_events: 1 }])
+            .await;
+        manager
+            .handle_command(Some(InputManagerCommand::SetAppBusy(true)))
+            .await
+            .unwrap();
+        manager.source.press("ESC");
+        manager
+            .dispatch_source_events(vec![InputSourceEvent::KeyStateChanged { key_events: 1 }])
+            .await;
+
+        let cancel = event_rx.recv().await.unwrap();
+        assert_eq!(cancel.kind, ShortcutKind::Cancel);
+        assert_eq!(cancel.phase, ShortcutPhase::Start);
+    }
+
+    #[tokio::test]
+    async fn compositor_triggers_drive_shortcuts_without_devices() {
+        let (event_tx, mut event_rx) = mpsc::channel(8);
+        let (_command_tx, command_rx) = mpsc::unbounded_channel();
+        let mut manager = InputManagerRuntime::with_source(
+            shortcuts(None, Some("SUPER+ALT")),
+            event_tx,
+            command_rx,
+            CompositorEventSource::default(),
+        )
+        .unwrap();
+        let trigger = |pressed| ShortcutTrigger {
+            name: "hold".to_string(),
+            pressed,
+        };
+
+        manager.apply_trigger(&trigger(true)).await.unwrap();
+        let start = event_rx.recv().await.unwrap();
+        assert_eq!(start.kind, ShortcutKind::Hold);
+        assert_eq!(start.phase, ShortcutPhase::Start);
+
+        manager.apply_trigger(&trigger(false)).await.unwrap();
+        let end = event_rx.recv().await.unwrap();
+        assert_eq!(end.phase, ShortcutPhase::End);
+
+        let unknown = ShortcutTrigger {
+            name: "press".to_string(),
+            pressed: true,
+        };
+        assert!(manager.apply_trigger(&unknown).await.is_err());
+        assert_eq!(manager.snapshot().device_count, 0);
+    }
+
     #[tokio::test]
     async fn source_backpressure_is_busy_but_does_not_reconcile_shortcuts() {
         let (event_tx, mut event_rx) = mpsc::channel(8);
@@ -169,11 +255,20 @@ pub struct InputSnapshot {
 pub struct InputManagerHandle {
     command_tx: mpsc::UnboundedSender<InputManagerCommand>,
     handle: Option<JoinHandle<()>>,
+    source: ShortcutSource,
+}
+
+/// Delivers named shortcut triggers to the input manager without going
+/// through the app's run loop, which may be blocked on processing.
+#[derive(Clone)]
+pub struct ShortcutTriggerSender {
+    command_tx: mpsc::UnboundedSender<InputManagerCommand>,
 }
 
 enum InputManagerCommand {
     UpdateShortcuts(ShortcutsConfig),
     SetAppBusy(bool),
+    Trigger(ShortcutTrigger, oneshot::Sender<Result<()>>),
     Snapshot(oneshot::Sender<InputSnapshot>),
     Shutdown,
 }
@@ -191,6 +286,7 @@ struct InputManagerRuntime<S = EvdevUdevEventSource> {
 enum ShortcutTransitionCause {
     KeyStateChanged,
     DeviceSetChanged,
+    TimerElapsed,
 }
 
 impl InputManagerHandle {
@@ -199,6 +295,7 @@ impl InputManagerHandle {
         event_tx: mpsc::Sender<ShortcutEvent>,
     ) -> Result<Self> {
         ShortcutController::new(shortcuts.clone())?;
+        let source = shortcuts.source;
         let (command_tx, command_rx) = mpsc::unbounded_channel();
         let (init_tx, init_rx) = std::sync::mpsc::sync_channel(1);
 
@@ -218,9 +315,22 @@ impl InputManagerHandle {
         Ok(Self {
             command_tx,
             handle: Some(handle),
+            source,
         })
     }
 
+    /// Where the running manager takes shortcut presses from; changing it
+    /// needs a new manager.
+    pub fn source(&self) -> ShortcutSource {
+        self.source
+    }
+
+    pub fn trigger_sender(&self) -> ShortcutTriggerSender {
+        ShortcutTriggerSender {
+            command_tx: self.command_tx.clone(),
+        }
+    }
+
     pub fn update_shortcuts(&self, shortcuts: ShortcutsConfig) -> Result<()> {
         ShortcutController::new(shortcuts.clone())?;
         self.command_tx
@@ -255,6 +365,16 @@ impl InputManagerHandle {
     }
 }
 
+impl ShortcutTriggerSender {
+    pub async fn send(&self, trigger: ShortcutTrigger) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(InputManagerCommand::Trigger(trigger, tx))
+            .context("input manager is not running")?;
+        rx.await.context("input manager dropped trigger reply")?
+    }
+}
+
 impl Drop for InputManagerHandle {
     fn drop(&mut self) {
         self.stop();
@@ -273,8 +393,21 @@ fn run_input_thread(
         .context("failed to build input manager runtime")?;
 
     runtime.block_on(async move {
-        let manager = InputManagerRuntime::new(shortcuts, event_tx, command_rx)?;
-        manager.run().await
+        match shortcuts.source {
+            ShortcutSource::Evdev => {
+                let manager = InputManagerRuntime::new(shortcuts, event_tx, command_rx)?;
+                manager.run().await
+            }
+            ShortcutSource::Compositor => {
+                let manager = InputManagerRuntime::with_source(
+                    shortcuts,
+                    event_tx,
+                    command_rx,
+                    CompositorEventSource::default(),
+                )?;
+                manager.run().await
+            }
+        }
     })
 }
 
@@ -284,7 +417,7 @@ impl InputManagerRuntime<EvdevUdevEventSource> {
         event_tx: mpsc::Sender<ShortcutEvent>,
         command_rx: mpsc::UnboundedReceiver<InputManagerCommand>,
     ) -> Result<Self> {
-        let source = EvdevUdevEventSource::open()?;
+        let source = EvdevUdevEventSource::open(&shortcuts.device_classes)?;
         Self::with_source(shortcuts, event_tx, command_rx, source)
     }
 
@@ -323,6 +456,10 @@ impl InputManagerRuntime<EvdevUdevEventSource> {
                 _ = evdev_tick.tick() => {
                     let events = self.source.poll_key_events(MAX_KEY_EVENTS_PER_TICK)?;
                     busy = self.dispatch_source_events(events).await;
+                    if self.shortcuts.timer_pending() {
+                        self.transition_shortcuts(ShortcutTransitionCause::TimerElapsed)
+                            .await;
+                    }
                 }
             }
 
@@ -336,14 +473,42 @@ impl InputManagerRuntime<EvdevUdevEventSource> {
     }
 }
 
+impl InputManagerRuntime<CompositorEventSource> {
+    async fn run(mut self) -> Result<()> {
+        let mut timer_tick = time::interval(EVDEV_TICK);
+        timer_tick.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+
+        info!("Input manager started; waiting for compositor shortcut triggers");
+
+        loop {
+            tokio::select! {
+                command = self.command_rx.recv() => {
+                    let should_shutdown = self.shutting_down(command.as_ref());
+                    self.handle_command(command).await?;
+                    if should_shutdown {
+                        break;
+                    }
+                }
+                _ = timer_tick.tick(), if self.shortcuts.timer_pending() => {
+                    self.transition_shortcuts(ShortcutTransitionCause::TimerElapsed)
+                        .await;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<S: InputEventSource> InputManagerRuntime<S> {
     fn with_source(
         shortcuts: ShortcutsConfig,
         event_tx: mpsc::Sender<ShortcutEvent>,
         command_rx: mpsc::UnboundedReceiver<InputManagerCommand>,
-        source: S,
+        mut source: S,
     ) -> Result<Self> {
         let shortcuts = ShortcutController::new(shortcuts)?;
+        source.set_device_filters(&shortcuts.device_filters());
 
         Ok(Self {
             command_rx,
@@ -359,14 +524,24 @@ impl<S: InputEventSource> InputManagerRuntime<S> {
     async fn handle_command(&mut self, command: Option<InputManagerCommand>) -> Result<bool> {
         match command {
             Some(InputManagerCommand::UpdateShortcuts(shortcuts)) => {
+                let device_events = self.source.set_device_classes(&shortcuts.device_classes)?;
                 let pressed_keys = self.source.pressed_keys().clone();
-                for event in self.shortcuts.transition(ShortcutInput::ConfigChanged {
+                let events = self.shortcuts.transition(ShortcutInput::ConfigChanged {
                     shortcuts,
                     pressed_keys: &pressed_keys,
                     now: Instant::now(),
-                })? {
+                })?;
+                self.source
+                    .set_device_filters(&self.shortcuts.device_filters());
+                for event in events {
                     self.emit_shortcut(event).await;
                 }
+                self.dispatch_source_events(device_events).await;
+                Ok(true)
+            }
+            Some(InputManagerCommand::Trigger(trigger, reply_tx)) => {
+                let result = self.apply_trigger(&trigger).await;
+                let _ = reply_tx.send(result);
                 Ok(true)
             }
             Some(InputManagerCommand::SetAppBusy(app_busy)) => {
@@ -388,6 +563,18 @@ impl<S: InputEventSource> InputManagerRuntime<S> {
         matches!(command, Some(InputManagerCommand::Shutdown) | None)
     }
 
+    async fn apply_trigger(&mut self, trigger: &ShortcutTrigger) -> Result<()> {
+        self.source.accept_trigger(trigger)?;
+        let events = self.shortcuts.transition(ShortcutInput::Triggered {
+            trigger,
+            now: Instant::now(),
+        })?;
+        for event in events {
+            self.emit_shortcut(event).await;
+        }
+        Ok(())
+    }
+
     async fn dispatch_source_events(&mut self, events: Vec<InputSourceEvent>) -> bool {
         let mut busy = false;
 
@@ -421,15 +608,26 @@ impl<S: InputEventSource> InputManagerRuntime<S> {
     async fn transition_shortcuts(&mut self, cause: ShortcutTransitionCause) {
         let now = Instant::now();
         let pressed_keys = self.source.pressed_keys().clone();
+        let device_keys = self
+            .shortcuts
+            .device_filters()
+            .into_iter()
+            .map(|device| {
+                let keys = self.source.pressed_keys_on(&device);
+                (device, keys)
+            })
+            .collect::<HashMap<_, _>>();
         let input = match cause {
             ShortcutTransitionCause::KeyStateChanged => ShortcutInput::KeyStateChanged {
                 pressed_keys: &pressed_keys,
+                device_keys: &device_keys,
                 now,
             },
             ShortcutTransitionCause::DeviceSetChanged => ShortcutInput::DeviceSetChanged {
                 pressed_keys: &pressed_keys,
                 now,
             },
+            ShortcutTransitionCause::TimerElapsed => ShortcutInput::TimerElapsed { now },
         };
 
         match self.shortcuts.transition(input) {
@@ -443,6 +641,11 @@ impl<S: InputEventSource> InputManagerRuntime<S> {
     }
 
     async fn emit_shortcut(&mut self, event: ShortcutEvent) {
+        if event.kind == ShortcutKind::Cancel && !self.app_busy {
+            debug!("Cancel shortcut ignored while idle");
+            return;
+        }
+
         let phase = event.phase;
         if matches!(phase, ShortcutPhase::End | ShortcutPhase::Cancel) {
             if let Err(err) = self.event_tx.send(event).await {
@@ -7,6 +7,8 @@ pub mod shortcuts;
 mod source;
 
 pub use injector::TextInjector;
-pub use manager::{InputManagerHandle, InputSnapshot, InputStats};
+pub use manager::{InputManagerHandle, InputSnapshot, InputStats, ShortcutTriggerSender};
+pub(crate) use paste_route::{class_hint_index, normalize_hint_classes};
 pub use registry::list_available_keyboards;
+pub(crate) use shortcuts::parse_shortcut;
 pub use shortcuts::{ShortcutEvent, ShortcutKind, ShortcutPhase};
@@ -155,7 +155,7 @@ fn built_in_shift_hint_for_class(class: &str) -> bool {
     false
 }
 
-fn class_hint_index(class: &str, hints: &[String]) -> Option<usize> {
+pub(crate) fn class_hint_index(class: &str, hints: &[String]) -> Option<usize> {
     if hints.is_empty() {
         return None;
     }
@@ -170,7 +170,7 @@ fn class_hint_index(class: &str, hints: &[String]) -> Option<usize> {
     })
 }
 
-pub(super) fn normalize_hint_classes(entries: Vec<String>) -> Vec<String> {
+pub(crate) fn normalize_hint_classes(entries: Vec<String>) -> Vec<String> {
     let mut normalized = Vec::new();
     let mut seen = HashSet::new();
     for entry in entries {
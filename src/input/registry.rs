@@ -1,5 +1,5 @@
 use anyhow::Result;
-use evdev::{Device, InputEventKind, Key};
+use evdev::{AttributeSetRef, Device, InputEventKind, Key};
 use std::collections::HashSet;
 use std::fs;
 use std::io;
@@ -8,11 +8,30 @@ use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
 use tracing::{debug, error, info, warn};
 
+use crate::config::InputDeviceClass;
+
 pub(super) struct KeyboardRegistry {
     devices: Vec<KeyboardDevice>,
+    classes: Vec<InputDeviceClass>,
+    filters: Vec<DeviceFilter>,
+    /// Keys held on devices no `device` filter claims.
     pressed_keys: HashSet<Key>,
 }
 
+/// A binding's `device` filter: a path when it starts with `/`, resolving
+/// links, and otherwise part of the device name.
+#[derive(Debug, Clone)]
+pub(super) struct DeviceFilter {
+    filter: String,
+    target: DeviceTarget,
+}
+
+#[derive(Debug, Clone)]
+enum DeviceTarget {
+    Path(PathBuf),
+    Name(String),
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub(super) struct PollOutcome {
     pub key_events: usize,
@@ -24,19 +43,23 @@ struct KeyboardDevice {
     path: PathBuf,
     name: String,
     device: Device,
+    pressed_keys: HashSet<Key>,
 }
 
 impl KeyboardRegistry {
-    pub(super) fn open_initial() -> Result<Self> {
-        let devices = Self::find_keyboard_devices(true)?;
+    pub(super) fn open_initial(classes: &[InputDeviceClass]) -> Result<Self> {
+        let devices = Self::find_keyboard_devices(classes, true)?;
         if devices.is_empty() {
-            warn!("No keyboard devices found!");
+            warn!("No input devices found for {:?}!", classes);
             warn!("Make sure you have read permissions for /dev/input/event*");
             warn!("You may need to add your user to the 'input' group");
+            warn!("Or set shortcuts.source to \"compositor\" and bind keys in your compositor");
         }
 
         Ok(Self {
             devices,
+            classes: classes.to_vec(),
+            filters: Vec::new(),
             pressed_keys: HashSet::new(),
         })
     }
@@ -45,6 +68,52 @@ impl KeyboardRegistry {
         &self.pressed_keys
     }
 
+    /// Keys held on the devices `filter` selects; see [`DeviceFilter`].
+    pub(super) fn pressed_keys_on(&self, filter: &str) -> HashSet<Key> {
+        let resolved;
+        let filter = match self.filters.iter().find(|known| known.filter == filter) {
+            Some(known) => known,
+            None => {
+                resolved = DeviceFilter::new(filter);
+                &resolved
+            }
+        };
+        self.devices
+            .iter()
+            .filter(|device| filter.matches(device))
+            .flat_map(|device| device.pressed_keys.iter().copied())
+            .collect()
+    }
+
+    /// Resolves the bindings' `device` filters. Devices they claim stop
+    /// feeding the shortcuts without a filter.
+    pub(super) fn set_device_filters(&mut self, filters: &[String]) {
+        self.filters = filters
+            .iter()
+            .map(|filter| DeviceFilter::new(filter))
+            .collect();
+        for filter in &self.filters {
+            if !self.devices.iter().any(|device| filter.matches(device)) {
+                warn!(
+                    "Shortcut device '{}' matches none of the {:?} devices listened to; \
+                     check shortcuts.device_classes",
+                    filter.filter, self.classes
+                );
+            }
+        }
+        self.sync_pressed_keys();
+    }
+
+    /// Switches the device classes listened to, returning whether the set of
+    /// devices changed.
+    pub(super) fn set_classes(&mut self, classes: &[InputDeviceClass]) -> Result<bool> {
+        if self.classes == classes {
+            return Ok(false);
+        }
+        self.classes = classes.to_vec();
+        self.refresh()
+    }
+
     pub(super) fn device_count(&self) -> usize {
         self.devices.len()
     }
@@ -60,7 +129,7 @@ impl KeyboardRegistry {
         let mut outcome = PollOutcome::default();
         let mut removed_devices = HashSet::new();
 
-        for entry in &mut self.devices {
+        'devices: for entry in &mut self.devices {
             if is_device_node_stale(entry.device.as_raw_fd(), &entry.path) {
                 warn!(
                     "Keyboard device node changed or disappeared at {:?}; refreshing input devices",
@@ -76,11 +145,11 @@ impl KeyboardRegistry {
                         if let InputEventKind::Key(key) = event.kind() {
                             match event.value() {
                                 1 => {
-                                    self.pressed_keys.insert(key);
+                                    entry.pressed_keys.insert(key);
                                     outcome.key_events += 1;
                                 }
                                 0 => {
-                                    self.pressed_keys.remove(&key);
+                                    entry.pressed_keys.remove(&key);
                                     outcome.key_events += 1;
                                 }
                                 _ => {}
@@ -89,7 +158,7 @@ impl KeyboardRegistry {
 
                         if outcome.key_events >= max_events {
                             outcome.backpressure = true;
-                            return Ok(outcome);
+                            break 'devices;
                         }
                     }
                 }
@@ -104,31 +173,53 @@ impl KeyboardRegistry {
             }
         }
 
+        if outcome.backpressure {
+            self.sync_pressed_keys();
+            return Ok(outcome);
+        }
+
         if !removed_devices.is_empty() {
             self.devices
                 .retain(|device| !removed_devices.contains(&device.path));
-            self.pressed_keys.clear();
+            for device in &mut self.devices {
+                device.pressed_keys.clear();
+            }
             outcome.devices_changed = true;
             self.refresh()?;
         }
+        self.sync_pressed_keys();
 
         Ok(outcome)
     }
 
+    /// Rebuilds the combined key state from the devices no filter claims.
+    fn sync_pressed_keys(&mut self) {
+        self.pressed_keys = self
+            .devices
+            .iter()
+            .filter(|device| !self.filters.iter().any(|filter| filter.matches(device)))
+            .flat_map(|device| device.pressed_keys.iter().copied())
+            .collect();
+    }
+
     pub(super) fn refresh(&mut self) -> Result<bool> {
         let previous_paths: HashSet<PathBuf> = self
             .devices
             .iter()
             .map(|device| device.path.clone())
             .collect();
-        let devices = Self::find_keyboard_devices(false)?;
+        let mut devices = Self::find_keyboard_devices(&self.classes, false)?;
         let updated_paths: HashSet<PathBuf> =
             devices.iter().map(|device| device.path.clone()).collect();
         let changed = previous_paths != updated_paths;
 
         if changed {
+            // A replugged device can come back behind a different node.
+            for filter in &mut self.filters {
+                *filter = DeviceFilter::new(&filter.filter);
+            }
             info!(
-                "Keyboard devices refreshed - monitoring {} device(s)",
+                "Input devices refreshed - monitoring {} device(s)",
                 devices.len()
             );
             debug!(
@@ -136,33 +227,49 @@ impl KeyboardRegistry {
                     .iter()
                     .map(|device| (&device.path, &device.name))
                     .collect::<Vec<_>>(),
-                "Keyboard device set changed"
+                "Input device set changed"
             );
-            self.pressed_keys.clear();
         } else {
             debug!(
-                "Keyboard devices refreshed - monitoring {} device(s)",
+                "Input devices refreshed - monitoring {} device(s)",
                 devices.len()
             );
+            for device in &mut devices {
+                if let Some(previous) = self.devices.iter().find(|old| old.path == device.path) {
+                    device.pressed_keys = previous.pressed_keys.clone();
+                }
+            }
         }
 
         self.devices = devices;
+        self.sync_pressed_keys();
         Ok(changed)
     }
 
-    fn find_keyboard_devices(log_devices: bool) -> Result<Vec<KeyboardDevice>> {
+    fn find_keyboard_devices(
+        classes: &[InputDeviceClass],
+        log_devices: bool,
+    ) -> Result<Vec<KeyboardDevice>> {
         let mut keyboards = Vec::new();
 
         for (path, device) in evdev::enumerate() {
-            if is_keyboard_device(&device) {
+            let Some(class) = device.supported_keys().and_then(classify_keys) else {
+                continue;
+            };
+            if classes.contains(&class) {
                 if let Err(err) = set_device_nonblocking(&device) {
                     warn!("Failed to set non-blocking mode for {:?}: {}", path, err);
                 }
                 let name = device.name().unwrap_or("Unknown").to_string();
                 if log_devices {
-                    info!("Found keyboard device: {} at {:?}", name, path);
+                    info!("Found {:?} device: {} at {:?}", class, name, path);
                 }
-                keyboards.push(KeyboardDevice { path, name, device });
+                keyboards.push(KeyboardDevice {
+                    path,
+                    name,
+                    device,
+                    pressed_keys: HashSet::new(),
+                });
             }
         }
 
@@ -174,7 +281,7 @@ pub fn list_available_keyboards() -> Result<Vec<(PathBuf, String)>> {
     let mut keyboards = Vec::new();
 
     for (path, device) in evdev::enumerate() {
-        if is_keyboard_device(&device) {
+        if device.supported_keys().and_then(classify_keys) == Some(InputDeviceClass::Keyboard) {
             let name = device.name().unwrap_or("Unknown").to_string();
             keyboards.push((path, name));
         }
@@ -183,10 +290,43 @@ pub fn list_available_keyboards() -> Result<Vec<(PathBuf, String)>> {
     Ok(keyboards)
 }
 
-fn is_keyboard_device(device: &Device) -> bool {
-    device.supported_keys().is_some_and(|keys| {
-        keys.contains(Key::KEY_A) && keys.contains(Key::KEY_S) && keys.contains(Key::KEY_D)
-    })
+fn classify_keys(keys: &AttributeSetRef<Key>) -> Option<InputDeviceClass> {
+    if keys.contains(Key::KEY_A) && keys.contains(Key::KEY_S) && keys.contains(Key::KEY_D) {
+        Some(InputDeviceClass::Keyboard)
+    } else if keys.contains(Key::BTN_LEFT) {
+        Some(InputDeviceClass::Mouse)
+    } else if keys.contains(Key::BTN_SOUTH) || keys.contains(Key::BTN_TRIGGER) {
+        Some(InputDeviceClass::Gamepad)
+    } else if keys.iter().next().is_some() {
+        Some(InputDeviceClass::Other)
+    } else {
+        None
+    }
+}
+
+impl DeviceFilter {
+    pub(super) fn new(filter: &str) -> Self {
+        let target = if filter.starts_with('/') {
+            DeviceTarget::Path(fs::canonicalize(filter).unwrap_or_else(|_| PathBuf::from(filter)))
+        } else {
+            DeviceTarget::Name(filter.to_lowercase())
+        };
+        Self {
+            filter: filter.to_string(),
+            target,
+        }
+    }
+
+    fn matches(&self, device: &KeyboardDevice) -> bool {
+        self.matches_device(&device.path, &device.name)
+    }
+
+    fn matches_device(&self, path: &Path, name: &str) -> bool {
+        match &self.target {
+            DeviceTarget::Path(target) => target == path,
+            DeviceTarget::Name(part) => name.to_lowercase().contains(part),
+        }
+    }
 }
 
 fn is_device_disconnect_error(err: &io::Error) -> bool {
@@ -253,6 +393,7 @@ pub(super) fn is_input_event_node(path: &Path) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use evdev::AttributeSet;
     use std::fs::File;
 
     fn temp_path(name: &str) -> PathBuf {
@@ -263,6 +404,42 @@ mod tests {
         ))
     }
 
+    #[test]
+    fn devices_are_classified_by_their_keys() {
+        let keyboard = AttributeSet::from_iter([Key::KEY_A, Key::KEY_S, Key::KEY_D, Key::KEY_ESC]);
+        let mouse = AttributeSet::from_iter([Key::BTN_LEFT, Key::BTN_RIGHT, Key::BTN_SIDE]);
+        let gamepad = AttributeSet::from_iter([Key::BTN_SOUTH, Key::BTN_EAST]);
+        let pedal = AttributeSet::from_iter([Key::KEY_B]);
+
+        assert_eq!(classify_keys(&keyboard), Some(InputDeviceClass::Keyboard));
+        assert_eq!(classify_keys(&mouse), Some(InputDeviceClass::Mouse));
+        assert_eq!(classify_keys(&gamepad), Some(InputDeviceClass::Gamepad));
+        assert_eq!(classify_keys(&pedal), Some(InputDeviceClass::Other));
+        assert_eq!(classify_keys(&AttributeSet::new()), None);
+    }
+
+    #[test]
+    fn device_filter_matches_name_or_linked_path() {
+        let node = temp_path("device-node");
+        fs::write(&node, b"device").unwrap();
+        let link = temp_path("device-link");
+        let _ = fs::remove_file(&link);
+        std::os::unix::fs::symlink(&node, &link).unwrap();
+        let node = fs::canonicalize(&node).unwrap();
+
+        let matches =
+            |filter: &str, name: &str| DeviceFilter::new(filter).matches_device(&node, name);
+
+        assert!(matches("footswitch", "PCsensor FootSwitch"));
+        assert!(!matches("mouse", "PCsensor FootSwitch"));
+        assert!(matches(node.to_str().unwrap(), "Pedal"));
+        assert!(matches(link.to_str().unwrap(), "Pedal"));
+        assert!(!matches("/dev/input/event-missing", "Pedal"));
+
+        let _ = fs::remove_file(link);
+        let _ = fs::remove_file(node);
+    }
+
     #[test]
     fn open_fd_matches_existing_path() {
         let path = temp_path("matching-fd");
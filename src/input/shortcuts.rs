@@ -1,14 +1,19 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use evdev::Key;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 
-use crate::config::ShortcutsConfig;
+use crate::config::{ShortcutAction, ShortcutGesture, ShortcutsConfig};
+use crate::control::ShortcutTrigger;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ShortcutKind {
     Hold,
     Press,
+    /// Fires on press like `Press`, without the debounce. Only delivered
+    /// while the app is recording or processing.
+    Cancel,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -23,18 +28,25 @@ pub struct ShortcutEvent {
     pub triggered_at: Instant,
     pub kind: ShortcutKind,
     pub phase: ShortcutPhase,
+    /// Name of the `shortcuts.bindings` entry that fired, or `None` for the
+    /// plain `press` and `hold` shortcuts.
+    pub binding: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ShortcutSummary {
     pub kind: ShortcutKind,
     pub name: String,
+    pub binding: Option<String>,
     pub active: bool,
 }
 
 pub(super) enum ShortcutInput<'a> {
     KeyStateChanged {
         pressed_keys: &'a HashSet<Key>,
+        /// Keys held per binding `device` filter, from
+        /// [`ShortcutController::device_filters`].
+        device_keys: &'a HashMap<String, HashSet<Key>>,
         now: Instant,
     },
     DeviceSetChanged {
@@ -46,26 +58,54 @@ pub(super) enum ShortcutInput<'a> {
         pressed_keys: &'a HashSet<Key>,
         now: Instant,
     },
+    /// Time passed without key events; see [`ShortcutController::timer_pending`].
+    TimerElapsed { now: Instant },
+    /// A shortcut pressed or released by name rather than by its keys.
+    Triggered {
+        trigger: &'a ShortcutTrigger,
+        now: Instant,
+    },
 }
 
 #[derive(Debug)]
 pub(super) struct ShortcutController {
     bindings: Vec<ShortcutBinding>,
+    timing: GestureTiming,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GestureTiming {
+    long_press: Duration,
+    double_tap: Duration,
 }
 
 #[derive(Debug)]
 struct ShortcutBinding {
     kind: ShortcutKind,
     name: String,
+    id: Option<String>,
+    gesture: ShortcutGesture,
+    device: Option<String>,
     keys: HashSet<Key>,
     active: bool,
     last_trigger: Instant,
+    /// When the keys went down, while they are still held.
+    pressed_since: Option<Instant>,
+    /// When the last short press was released, while a double tap may follow.
+    last_tap: Option<Instant>,
+    /// Set when other keys joined a gesture binding's keys, making the press
+    /// part of a chord rather than a gesture, until the keys are released.
+    chorded: bool,
 }
 
 impl ShortcutController {
     pub(super) fn new(shortcuts: ShortcutsConfig) -> Result<Self> {
         let mut controller = Self {
             bindings: Vec::new(),
+            timing: GestureTiming {
+                long_press: Duration::ZERO,
+                double_tap: Duration::ZERO,
+            },
         };
         controller.transition(ShortcutInput::ConfigChanged {
             shortcuts,
@@ -77,9 +117,11 @@ impl ShortcutController {
 
     pub(super) fn transition(&mut self, input: ShortcutInput<'_>) -> Result<Vec<ShortcutEvent>> {
         match input {
-            ShortcutInput::KeyStateChanged { pressed_keys, now } => {
-                Ok(self.apply_key_state(pressed_keys, now))
-            }
+            ShortcutInput::KeyStateChanged {
+                pressed_keys,
+                device_keys,
+                now,
+            } => Ok(self.apply_key_state(pressed_keys, device_keys, now)),
             ShortcutInput::DeviceSetChanged { pressed_keys, now } => {
                 let _ = pressed_keys;
                 Ok(self.cancel_holds(now))
@@ -89,7 +131,34 @@ impl ShortcutController {
                 pressed_keys,
                 now,
             } => self.replace_shortcuts(shortcuts, pressed_keys, now),
+            ShortcutInput::TimerElapsed { now } => Ok(self.apply_timers(now)),
+            ShortcutInput::Triggered { trigger, now } => self.apply_trigger(trigger, now),
+        }
+    }
+
+    /// The distinct `device` filters of the current bindings.
+    pub(super) fn device_filters(&self) -> Vec<String> {
+        let mut filters = Vec::new();
+        for device in self
+            .bindings
+            .iter()
+            .filter_map(|binding| binding.device.as_ref())
+        {
+            if !filters.contains(device) {
+                filters.push(device.clone());
+            }
         }
+        filters
+    }
+
+    /// Whether a long press is being held toward its threshold, so the caller
+    /// should send [`ShortcutInput::TimerElapsed`] even without key events.
+    pub(super) fn timer_pending(&self) -> bool {
+        self.bindings.iter().any(|binding| {
+            binding.gesture == ShortcutGesture::LongPress
+                && binding.pressed_since.is_some()
+                && !binding.active
+        })
     }
 
     fn replace_shortcuts(
@@ -100,76 +169,142 @@ impl ShortcutController {
     ) -> Result<Vec<ShortcutEvent>> {
         let mut releases = Vec::new();
         let mut next = Vec::new();
+        let timing = GestureTiming {
+            long_press: Duration::from_millis(shortcuts.long_press_ms),
+            double_tap: Duration::from_millis(shortcuts.double_tap_ms),
+        };
 
         if let Some(shortcut) = shortcuts.press {
-            next.push(ShortcutBinding::new(ShortcutKind::Press, shortcut)?);
+            next.push(ShortcutBinding::new(ShortcutKind::Press, None, shortcut)?);
         }
         if let Some(shortcut) = shortcuts.hold {
-            next.push(ShortcutBinding::new(ShortcutKind::Hold, shortcut)?);
+            next.push(ShortcutBinding::new(ShortcutKind::Hold, None, shortcut)?);
+        }
+        if let Some(shortcut) = shortcuts.cancel {
+            next.push(ShortcutBinding::new(ShortcutKind::Cancel, None, shortcut)?);
+        }
+        for binding in shortcuts.bindings {
+            if next
+                .iter()
+                .any(|existing| existing.id.as_ref() == Some(&binding.name))
+            {
+                bail!("Duplicate shortcut binding name: {}", binding.name);
+            }
+            let kind = match binding.action {
+                ShortcutAction::Hold => ShortcutKind::Hold,
+                ShortcutAction::Cancel => ShortcutKind::Cancel,
+                _ => ShortcutKind::Press,
+            };
+            if kind == ShortcutKind::Hold && binding.gesture == ShortcutGesture::Tap {
+                bail!(
+                    "Shortcut binding '{}' cannot use the tap gesture for a hold action",
+                    binding.name
+                );
+            }
+            let name = binding.name;
+            let mut parsed = ShortcutBinding::new(kind, Some(name.clone()), binding.shortcut)
+                .with_context(|| format!("Invalid shortcut for binding '{name}'"))?;
+            parsed.gesture = binding.gesture;
+            parsed.device = binding
+                .device
+                .map(|device| device.trim().to_string())
+                .filter(|device| !device.is_empty());
+            next.push(parsed);
         }
 
         for old in &self.bindings {
             if old.kind == ShortcutKind::Hold
                 && old.active
-                && !next
-                    .iter()
-                    .any(|new| new.kind == old.kind && new.name == old.name)
+                && !next.iter().any(|new| new.same_as(old))
             {
                 releases.push(ShortcutEvent {
                     triggered_at: now,
                     kind: ShortcutKind::Hold,
                     phase: ShortcutPhase::End,
+                    binding: old.id.clone(),
                 });
             }
         }
 
         for binding in &mut next {
-            if let Some(old) = self
-                .bindings
-                .iter()
-                .find(|old| old.kind == binding.kind && old.name == binding.name)
-            {
-                binding.active = old.active && binding.keys.is_subset(pressed_keys);
+            if let Some(old) = self.bindings.iter().find(|old| old.same_as(binding)) {
+                let still_pressed = binding.keys.is_subset(pressed_keys);
+                binding.active = old.active && still_pressed;
                 binding.last_trigger = old.last_trigger;
+                binding.pressed_since = old.pressed_since.filter(|_| still_pressed);
+                binding.last_tap = old.last_tap;
+                binding.chorded = old.chorded && still_pressed;
             }
         }
 
         self.bindings = next;
+        self.timing = timing;
         Ok(releases)
     }
 
-    fn apply_key_state(&mut self, pressed_keys: &HashSet<Key>, now: Instant) -> Vec<ShortcutEvent> {
+    fn apply_key_state(
+        &mut self,
+        pressed_keys: &HashSet<Key>,
+        device_keys: &HashMap<String, HashSet<Key>>,
+        now: Instant,
+    ) -> Vec<ShortcutEvent> {
         let mut events = Vec::new();
+        let no_keys = HashSet::new();
 
         for binding in &mut self.bindings {
+            let pressed_keys = match &binding.device {
+                Some(device) => device_keys.get(device).unwrap_or(&no_keys),
+                None => pressed_keys,
+            };
             let combination_pressed = binding.keys.is_subset(pressed_keys);
+            if combination_pressed && pressed_keys.len() > binding.keys.len() {
+                binding.interrupt_gesture();
+            }
+            binding.apply_pressed(combination_pressed, now, self.timing, &mut events);
+        }
 
-            if combination_pressed && !binding.active {
-                let should_trigger = match binding.kind {
-                    ShortcutKind::Hold => true,
-                    ShortcutKind::Press => {
-                        now.duration_since(binding.last_trigger) > Duration::from_millis(500)
-                    }
-                };
-
-                if should_trigger {
-                    binding.active = true;
-                    binding.last_trigger = now;
-                    events.push(ShortcutEvent {
-                        triggered_at: now,
-                        kind: binding.kind,
-                        phase: ShortcutPhase::Start,
-                    });
-                }
-            } else if !combination_pressed && binding.active {
-                binding.active = false;
-                if binding.kind == ShortcutKind::Hold {
-                    events.push(ShortcutEvent {
-                        triggered_at: now,
-                        kind: ShortcutKind::Hold,
-                        phase: ShortcutPhase::End,
-                    });
-                }
+        events
+    }
+
+    /// Presses or releases one binding as if exactly its keys changed. Named
+    /// bindings win over the built-in `press`, `hold` and `cancel` shortcuts.
+    fn apply_trigger(
+        &mut self,
+        trigger: &ShortcutTrigger,
+        now: Instant,
+    ) -> Result<Vec<ShortcutEvent>> {
+        let builtin = match trigger.name.as_str() {
+            "press" => Some(ShortcutKind::Press),
+            "hold" => Some(ShortcutKind::Hold),
+            "cancel" => Some(ShortcutKind::Cancel),
+            _ => None,
+        };
+        let index = self
+            .bindings
+            .iter()
+            .position(|binding| binding.id.as_ref() == Some(&trigger.name))
+            .or_else(|| {
+                self.bindings
+                    .iter()
+                    .position(|binding| binding.id.is_none() && Some(binding.kind) == builtin)
+            })
+            .with_context(|| format!("No shortcut named '{}' is configured", trigger.name))?;
+
+        let mut events = Vec::new();
+        self.bindings[index].apply_pressed(trigger.pressed, now, self.timing, &mut events);
+        Ok(events)
+    }
+
+    fn apply_timers(&mut self, now: Instant) -> Vec<ShortcutEvent> {
+        let mut events = Vec::new();
+
+        for binding in &mut self.bindings {
+            let held_long = binding
+                .pressed_since
+                .is_some_and(|since| now.duration_since(since) >= self.timing.long_press);
+            if binding.gesture == ShortcutGesture::LongPress && held_long && !binding.active {
+                binding.active = true;
+                binding.trigger(now, &mut events);
             }
         }
 
@@ -180,12 +315,17 @@ impl ShortcutController {
         let mut events = Vec::new();
 
         for binding in &mut self.bindings {
+            // A gesture in progress cannot be finished on a changed device set.
+            binding.pressed_since = None;
+            binding.last_tap = None;
+            binding.chorded = false;
             if binding.kind == ShortcutKind::Hold {
                 binding.active = false;
                 events.push(ShortcutEvent {
                     triggered_at: now,
                     kind: ShortcutKind::Hold,
                     phase: ShortcutPhase::Cancel,
+                    binding: binding.id.clone(),
                 });
             }
         }
@@ -199,6 +339,7 @@ impl ShortcutController {
             .map(|binding| ShortcutSummary {
                 kind: binding.kind,
                 name: binding.name.clone(),
+                binding: binding.id.clone(),
                 active: binding.active,
             })
             .collect()
@@ -206,18 +347,132 @@ impl ShortcutController {
 }
 
 impl ShortcutBinding {
-    fn new(kind: ShortcutKind, name: String) -> Result<Self> {
+    fn new(kind: ShortcutKind, id: Option<String>, name: String) -> Result<Self> {
         Ok(Self {
             kind,
             keys: parse_shortcut(&name)?,
             name,
+            id,
+            gesture: ShortcutGesture::Press,
+            device: None,
             active: false,
             last_trigger: Instant::now() - Duration::from_secs(10),
+            pressed_since: None,
+            last_tap: None,
+            chorded: false,
         })
     }
+
+    fn same_as(&self, other: &ShortcutBinding) -> bool {
+        self.kind == other.kind
+            && self.name == other.name
+            && self.id == other.id
+            && self.gesture == other.gesture
+            && self.device == other.device
+    }
+
+    fn apply_pressed(
+        &mut self,
+        pressed: bool,
+        now: Instant,
+        timing: GestureTiming,
+        events: &mut Vec<ShortcutEvent>,
+    ) {
+        if self.gesture != ShortcutGesture::Press {
+            self.apply_gesture(pressed, now, timing, events);
+            return;
+        }
+
+        if pressed && !self.active {
+            let should_trigger = match self.kind {
+                ShortcutKind::Hold | ShortcutKind::Cancel => true,
+                ShortcutKind::Press => {
+                    now.duration_since(self.last_trigger) > Duration::from_millis(500)
+                }
+            };
+
+            if should_trigger {
+                self.active = true;
+                self.trigger(now, events);
+            }
+        } else if !pressed && self.active {
+            self.active = false;
+            if self.kind == ShortcutKind::Hold {
+                events.push(self.event(ShortcutPhase::End, now));
+            }
+        }
+    }
+
+    /// Stops other keys held with a gesture binding, e.g. a chord like
+    /// RCTRL+C, from counting as a tap, double tap or long press.
+    fn interrupt_gesture(&mut self) {
+        if self.gesture != ShortcutGesture::Press && !self.active {
+            self.chorded = true;
+            self.pressed_since = None;
+            self.last_tap = None;
+        }
+    }
+
+    /// Tracks taps, double taps and long presses. `active` is set while a
+    /// triggered gesture still has its keys down.
+    fn apply_gesture(
+        &mut self,
+        pressed: bool,
+        now: Instant,
+        timing: GestureTiming,
+        events: &mut Vec<ShortcutEvent>,
+    ) {
+        if self.chorded {
+            self.chorded = pressed;
+            return;
+        }
+        match (pressed, self.pressed_since) {
+            (true, None) => {
+                self.pressed_since = Some(now);
+                let second_tap = self
+                    .last_tap
+                    .take()
+                    .is_some_and(|tap| now.duration_since(tap) <= timing.double_tap);
+                if self.gesture == ShortcutGesture::DoubleTap && second_tap {
+                    self.active = true;
+                    self.trigger(now, events);
+                }
+            }
+            (false, Some(since)) => {
+                self.pressed_since = None;
+                if self.active {
+                    self.active = false;
+                    if self.kind == ShortcutKind::Hold {
+                        events.push(self.event(ShortcutPhase::End, now));
+                    }
+                } else if now.duration_since(since) < timing.long_press {
+                    match self.gesture {
+                        ShortcutGesture::Tap => self.trigger(now, events),
+                        ShortcutGesture::DoubleTap => self.last_tap = Some(now),
+                        ShortcutGesture::Press | ShortcutGesture::LongPress => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn trigger(&mut self, now: Instant, events: &mut Vec<ShortcutEvent>) {
+        self.last_trigger = now;
+        events.push(self.event(ShortcutPhase::Start, now));
+    }
+
+    fn event(&self, phase: ShortcutPhase, now: Instant) -> ShortcutEvent {
+        ShortcutEvent {
+            triggered_at: now,
+            kind: self.kind,
+            phase,
+            binding: self.id.clone(),
+        }
+    }
 }
 
-pub(super) fn parse_shortcut(shortcut: &str) -> Result<HashSet<Key>> {
+pub(crate) fn parse_shortcut(shortcut: &str) -> Result<HashSet<Key>> {
     let mut keys = HashSet::new();
 
     for part in shortcut.split('+') {
@@ -239,6 +494,10 @@ fn parse_key(key_str: &str) -> Result<Key> {
         "ALT" => Ok(Key::KEY_LEFTALT),
         "CTRL" | "CONTROL" => Ok(Key::KEY_LEFTCTRL),
         "SHIFT" => Ok(Key::KEY_LEFTSHIFT),
+        "RSUPER" | "RIGHTSUPER" | "RMETA" | "RIGHTMETA" => Ok(Key::KEY_RIGHTMETA),
+        "RALT" | "RIGHTALT" | "ALTGR" => Ok(Key::KEY_RIGHTALT),
+        "RCTRL" | "RIGHTCTRL" | "RCONTROL" | "RIGHTCONTROL" => Ok(Key::KEY_RIGHTCTRL),
+        "RSHIFT" | "RIGHTSHIFT" => Ok(Key::KEY_RIGHTSHIFT),
         "F1" => Ok(Key::KEY_F1),
         "F2" => Ok(Key::KEY_F2),
         "F3" => Ok(Key::KEY_F3),
@@ -302,6 +561,10 @@ fn parse_key(key_str: &str) -> Result<Key> {
         "DOWN" => Ok(Key::KEY_DOWN),
         "LEFT" => Ok(Key::KEY_LEFT),
         "RIGHT" => Ok(Key::KEY_RIGHT),
+        // Raw evdev codes, e.g. BTN_SIDE for a mouse side button.
+        code if code.starts_with("KEY_") || code.starts_with("BTN_") => {
+            Key::from_str(code).map_err(|_| anyhow::anyhow!("Unknown key code: {}", code))
+        }
         _ => Err(anyhow::anyhow!("Unknown key: {}", key_str)),
     }
 }
@@ -309,21 +572,66 @@ fn parse_key(key_str: &str) -> Result<Key> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ShortcutBindingConfig;
 
     fn config(press: Option<&str>, hold: Option<&str>) -> ShortcutsConfig {
         ShortcutsConfig {
             press: press.map(str::to_string),
             hold: hold.map(str::to_string),
+            cancel: None,
+            ..ShortcutsConfig::default()
+        }
+    }
+
+    fn binding(name: &str, shortcut: &str, action: ShortcutAction) -> ShortcutBindingConfig {
+        ShortcutBindingConfig {
+            name: name.to_string(),
+            shortcut: shortcut.to_string(),
+            action,
+            gesture: ShortcutGesture::Press,
+            device: None,
+            profile: None,
+            provider: None,
+        }
+    }
+
+    fn gesture_binding(
+        name: &str,
+        shortcut: &str,
+        action: ShortcutAction,
+        gesture: ShortcutGesture,
+    ) -> ShortcutBindingConfig {
+        ShortcutBindingConfig {
+            gesture,
+            ..binding(name, shortcut, action)
         }
     }
 
+    fn gestures(bindings: Vec<ShortcutBindingConfig>) -> ShortcutController {
+        let mut shortcuts = config(None, None);
+        shortcuts.bindings = bindings;
+        shortcuts.long_press_ms = 400;
+        shortcuts.double_tap_ms = 300;
+        ShortcutController::new(shortcuts).unwrap()
+    }
+
+    fn timer(controller: &mut ShortcutController, now: Instant) -> Vec<ShortcutEvent> {
+        controller
+            .transition(ShortcutInput::TimerElapsed { now })
+            .unwrap()
+    }
+
     fn key_transition(
         controller: &mut ShortcutController,
         pressed_keys: &HashSet<Key>,
         now: Instant,
     ) -> Vec<ShortcutEvent> {
         controller
-            .transition(ShortcutInput::KeyStateChanged { pressed_keys, now })
+            .transition(ShortcutInput::KeyStateChanged {
+                pressed_keys,
+                device_keys: &HashMap::new(),
+                now,
+            })
             .unwrap()
     }
 
@@ -427,4 +735,304 @@ mod tests {
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].phase, ShortcutPhase::Cancel);
     }
+
+    #[test]
+    fn named_bindings_tag_their_events() {
+        let mut shortcuts = config(Some("SUPER+R"), None);
+        shortcuts.bindings = vec![
+            binding("groq", "SUPER+G", ShortcutAction::Toggle),
+            binding("local", "SUPER+L", ShortcutAction::Hold),
+        ];
+        let mut controller = ShortcutController::new(shortcuts).unwrap();
+        let now = Instant::now();
+
+        let press = key_transition(&mut controller, &parse_shortcut("SUPER+R").unwrap(), now);
+        assert_eq!(press.len(), 1);
+        assert_eq!(press[0].binding, None);
+
+        let groq = key_transition(
+            &mut controller,
+            &parse_shortcut("SUPER+G").unwrap(),
+            now + Duration::from_millis(10),
+        );
+        assert_eq!(groq.len(), 1);
+        assert_eq!(groq[0].kind, ShortcutKind::Press);
+        assert_eq!(groq[0].binding.as_deref(), Some("groq"));
+
+        let local_keys = parse_shortcut("SUPER+L").unwrap();
+        let start = key_transition(
+            &mut controller,
+            &local_keys,
+            now + Duration::from_millis(20),
+        );
+        assert_eq!(start.len(), 1);
+        assert_eq!(start[0].kind, ShortcutKind::Hold);
+        assert_eq!(start[0].binding.as_deref(), Some("local"));
+
+        let end = key_transition(
+            &mut controller,
+            &HashSet::new(),
+            now + Duration::from_millis(30),
+        );
+        assert_eq!(end.len(), 1);
+        assert_eq!(end[0].phase, ShortcutPhase::End);
+        assert_eq!(end[0].binding.as_deref(), Some("local"));
+    }
+
+    #[test]
+    fn duplicate_binding_names_are_rejected() {
+        let mut shortcuts = config(None, None);
+        shortcuts.bindings = vec![
+            binding("dictate", "SUPER+G", ShortcutAction::Toggle),
+            binding("dictate", "SUPER+L", ShortcutAction::Hold),
+        ];
+
+        let err = ShortcutController::new(shortcuts).unwrap_err();
+        assert!(err.to_string().contains("dictate"));
+    }
+
+    #[test]
+    fn cancel_shortcut_fires_on_every_press() {
+        let mut shortcuts = config(Some("SUPER+R"), None);
+        shortcuts.cancel = Some("ESC".to_string());
+        let mut controller = ShortcutController::new(shortcuts).unwrap();
+        let keys = parse_shortcut("ESC").unwrap();
+        let now = Instant::now();
+
+        let first = key_transition(&mut controller, &keys, now);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].kind, ShortcutKind::Cancel);
+        assert_eq!(first[0].phase, ShortcutPhase::Start);
+
+        let released = key_transition(
+            &mut controller,
+            &HashSet::new(),
+            now + Duration::from_millis(10),
+        );
+        assert!(released.is_empty());
+        let second = key_transition(&mut controller, &keys, now + Duration::from_millis(20));
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].kind, ShortcutKind::Cancel);
+    }
+
+    #[test]
+    fn double_tap_triggers_on_second_press_within_window() {
+        let mut controller = gestures(vec![gesture_binding(
+            "hands-free",
+            "RCTRL",
+            ShortcutAction::Toggle,
+            ShortcutGesture::DoubleTap,
+        )]);
+        let keys = parse_shortcut("RCTRL").unwrap();
+        let released = HashSet::new();
+        let now = Instant::now();
+        let at = |ms| now + Duration::from_millis(ms);
+
+        assert!(key_transition(&mut controller, &keys, now).is_empty());
+        assert!(key_transition(&mut controller, &released, at(80)).is_empty());
+        let second = key_transition(&mut controller, &keys, at(200));
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].phase, ShortcutPhase::Start);
+        assert_eq!(second[0].binding.as_deref(), Some("hands-free"));
+
+        // A third tap starts a new pair rather than triggering again.
+        key_transition(&mut controller, &released, at(260));
+        assert!(key_transition(&mut controller, &keys, at(320)).is_empty());
+        key_transition(&mut controller, &released, at(380));
+
+        // Too slow a second tap does not count.
+        assert!(key_transition(&mut controller, &keys, at(1000)).is_empty());
+    }
+
+    #[test]
+    fn gestures_do_not_fire_inside_chords() {
+        let mut controller = gestures(vec![
+            gesture_binding("tap", "RCTRL", ShortcutAction::Toggle, ShortcutGesture::Tap),
+            gesture_binding(
+                "hands-free",
+                "F9",
+                ShortcutAction::Toggle,
+                ShortcutGesture::DoubleTap,
+            ),
+        ]);
+        let rctrl = parse_shortcut("RCTRL").unwrap();
+        let chord = parse_shortcut("RCTRL+C").unwrap();
+        let f9 = parse_shortcut("F9").unwrap();
+        let ctrl_f9 = parse_shortcut("CTRL+F9").unwrap();
+        let released = HashSet::new();
+        let now = Instant::now();
+        let at = |ms| now + Duration::from_millis(ms);
+
+        // RCTRL+C released quickly is a copy, not a tap.
+        assert!(key_transition(&mut controller, &rctrl, now).is_empty());
+        assert!(key_transition(&mut controller, &chord, at(40)).is_empty());
+        assert!(key_transition(&mut controller, &rctrl, at(80)).is_empty());
+        assert!(key_transition(&mut controller, &released, at(120)).is_empty());
+
+        // A CTRL+F9 press between two F9 taps breaks the double tap.
+        key_transition(&mut controller, &f9, at(1000));
+        key_transition(&mut controller, &released, at(1050));
+        assert!(key_transition(&mut controller, &ctrl_f9, at(1100)).is_empty());
+        key_transition(&mut controller, &released, at(1150));
+        assert!(key_transition(&mut controller, &f9, at(1200)).is_empty());
+
+        // A plain tap still works afterwards.
+        key_transition(&mut controller, &rctrl, at(2000));
+        let tap = key_transition(&mut controller, &released, at(2050));
+        assert_eq!(tap.len(), 1);
+        assert_eq!(tap[0].binding.as_deref(), Some("tap"));
+    }
+
+    #[test]
+    fn long_press_starts_hold_after_threshold_and_ends_on_release() {
+        let mut controller = gestures(vec![gesture_binding(
+            "talk",
+            "RCTRL",
+            ShortcutAction::Hold,
+            ShortcutGesture::LongPress,
+        )]);
+        let keys = parse_shortcut("RCTRL").unwrap();
+        let now = Instant::now();
+
+        assert!(key_transition(&mut controller, &keys, now).is_empty());
+        assert!(controller.timer_pending());
+        assert!(timer(&mut controller, now + Duration::from_millis(200)).is_empty());
+
+        let start = timer(&mut controller, now + Duration::from_millis(400));
+        assert_eq!(start.len(), 1);
+        assert_eq!(start[0].kind, ShortcutKind::Hold);
+        assert_eq!(start[0].phase, ShortcutPhase::Start);
+        assert!(!controller.timer_pending());
+
+        let end = key_transition(
+            &mut controller,
+            &HashSet::new(),
+            now + Duration::from_millis(900),
+        );
+        assert_eq!(end.len(), 1);
+        assert_eq!(end[0].phase, ShortcutPhase::End);
+    }
+
+    #[test]
+    fn tap_and_long_press_on_the_same_key_pick_one_action() {
+        let mut controller = gestures(vec![
+            gesture_binding("tap", "F9", ShortcutAction::Toggle, ShortcutGesture::Tap),
+            gesture_binding(
+                "hold",
+                "F9",
+                ShortcutAction::Hold,
+                ShortcutGesture::LongPress,
+            ),
+        ]);
+        let keys = parse_shortcut("F9").unwrap();
+        let released = HashSet::new();
+        let now = Instant::now();
+        let at = |ms| now + Duration::from_millis(ms);
+
+        key_transition(&mut controller, &keys, now);
+        assert!(timer(&mut controller, at(100)).is_empty());
+        let tap = key_transition(&mut controller, &released, at(150));
+        assert_eq!(tap.len(), 1);
+        assert_eq!(tap[0].binding.as_deref(), Some("tap"));
+
+        key_transition(&mut controller, &keys, at(1000));
+        let hold = timer(&mut controller, at(1450));
+        assert_eq!(hold.len(), 1);
+        assert_eq!(hold[0].binding.as_deref(), Some("hold"));
+        let release = key_transition(&mut controller, &released, at(2000));
+        assert_eq!(release.len(), 1);
+        assert_eq!(release[0].binding.as_deref(), Some("hold"));
+        assert_eq!(release[0].phase, ShortcutPhase::End);
+    }
+
+    #[test]
+    fn tap_gesture_cannot_drive_a_hold_action() {
+        let mut shortcuts = config(None, None);
+        shortcuts.bindings = vec![gesture_binding(
+            "talk",
+            "F9",
+            ShortcutAction::Hold,
+            ShortcutGesture::Tap,
+        )];
+
+        assert!(ShortcutController::new(shortcuts).is_err());
+    }
+
+    #[test]
+    fn parses_raw_evdev_button_codes() {
+        assert_eq!(
+            parse_shortcut("btn_side").unwrap(),
+            HashSet::from([Key::BTN_SIDE])
+        );
+        assert_eq!(
+            parse_shortcut("SUPER+BTN_EXTRA").unwrap(),
+            HashSet::from([Key::KEY_LEFTMETA, Key::BTN_EXTRA])
+        );
+        assert!(parse_shortcut("BTN_NOPE").is_err());
+    }
+
+    #[test]
+    fn device_bound_binding_ignores_other_devices() {
+        let mut shortcuts = config(None, None);
+        shortcuts.bindings = vec![ShortcutBindingConfig {
+            device: Some("FootSwitch".to_string()),
+            ..binding("pedal", "KEY_B", ShortcutAction::Hold)
+        }];
+        let mut controller = ShortcutController::new(shortcuts).unwrap();
+        assert_eq!(controller.device_filters(), vec!["FootSwitch".to_string()]);
+        let keys = parse_shortcut("KEY_B").unwrap();
+        let now = Instant::now();
+
+        let from_keyboard = controller
+            .transition(ShortcutInput::KeyStateChanged {
+                pressed_keys: &keys,
+                device_keys: &HashMap::from([("FootSwitch".to_string(), HashSet::new())]),
+                now,
+            })
+            .unwrap();
+        assert!(from_keyboard.is_empty());
+
+        let from_pedal = controller
+            .transition(ShortcutInput::KeyStateChanged {
+                pressed_keys: &keys,
+                device_keys: &HashMap::from([("FootSwitch".to_string(), keys.clone())]),
+                now: now + Duration::from_millis(10),
+            })
+            .unwrap();
+        assert_eq!(from_pedal.len(), 1);
+        assert_eq!(from_pedal[0].binding.as_deref(), Some("pedal"));
+    }
+
+    #[test]
+    fn named_triggers_press_only_the_named_shortcut() {
+        let mut shortcuts = config(Some("SUPER+ALT+D"), Some("SUPER+ALT"));
+        shortcuts.bindings = vec![binding("notes", "SUPER+ALT", ShortcutAction::Hold)];
+        let mut controller = ShortcutController::new(shortcuts).unwrap();
+        let now = Instant::now();
+        let mut trigger = |name: &str, pressed: bool, now: Instant| {
+            controller.transition(ShortcutInput::Triggered {
+                trigger: &ShortcutTrigger {
+                    name: name.to_string(),
+                    pressed,
+                },
+                now,
+            })
+        };
+
+        let hold = trigger("hold", true, now).unwrap();
+        assert_eq!(hold.len(), 1);
+        assert_eq!(hold[0].kind, ShortcutKind::Hold);
+        assert_eq!(hold[0].binding, None);
+
+        let notes = trigger("notes", true, now).unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].binding.as_deref(), Some("notes"));
+
+        let release = trigger("hold", false, now + Duration::from_millis(50)).unwrap();
+        assert_eq!(release.len(), 1);
+        assert_eq!(release[0].phase, ShortcutPhase::End);
+        assert_eq!(release[0].binding, None);
+
+        assert!(trigger("missing", true, now).is_err());
+    }
 }
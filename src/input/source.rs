@@ -1,4 +1,4 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use futures_util::StreamExt;
 use std::collections::HashSet;
 use std::future;
@@ -8,6 +8,8 @@ use std::time::{Duration, Instant};
 use tokio_udev::{AsyncMonitorSocket, EventType, MonitorBuilder};
 use tracing::warn;
 
+use crate::config::InputDeviceClass;
+use crate::control::ShortcutTrigger;
 use crate::input::registry::{KeyboardRegistry, PollOutcome, is_input_event_node};
 
 const UDEV_DEBOUNCE: Duration = Duration::from_millis(150);
@@ -22,8 +24,25 @@ pub(super) enum InputSourceEvent {
 
 pub(super) trait InputEventSource {
     fn pressed_keys(&self) -> &HashSet<evdev::Key>;
+    /// Keys held on the devices a binding's `device` filter selects.
+    fn pressed_keys_on(&self, device: &str) -> HashSet<evdev::Key>;
+    /// Takes the bindings' `device` filters; keys on the devices they claim
+    /// are left out of [`InputEventSource::pressed_keys`].
+    fn set_device_filters(&mut self, filters: &[String]);
     fn device_count(&self) -> usize;
     fn device_paths(&self) -> Vec<PathBuf>;
+    fn set_device_classes(&mut self, classes: &[InputDeviceClass])
+        -> Result<Vec<InputSourceEvent>>;
+    /// Takes a shortcut press or release sent by name over the control socket.
+    fn accept_trigger(&mut self, trigger: &ShortcutTrigger) -> Result<()>;
+}
+
+/// Reads no devices; shortcuts arrive as named triggers from compositor
+/// binds, so no access to `/dev/input` is needed.
+#[derive(Debug, Default)]
+pub(super) struct CompositorEventSource {
+    /// Always empty; bindings never see keys from this source.
+    pressed_keys: HashSet<evdev::Key>,
 }
 
 pub(super) struct EvdevUdevEventSource {
@@ -47,8 +66,8 @@ struct UdevCoalescer {
 }
 
 impl EvdevUdevEventSource {
-    pub(super) fn open() -> Result<Self> {
-        let registry = KeyboardRegistry::open_initial()?;
+    pub(super) fn open(classes: &[InputDeviceClass]) -> Result<Self> {
+        let registry = KeyboardRegistry::open_initial(classes)?;
         let mut coalescer = UdevCoalescer::default();
         let udev_stream = match create_udev_stream() {
             Ok(stream) => Some(stream),
@@ -129,6 +148,14 @@ impl InputEventSource for EvdevUdevEventSource {
         self.registry.pressed_keys()
     }
 
+    fn pressed_keys_on(&self, device: &str) -> HashSet<evdev::Key> {
+        self.registry.pressed_keys_on(device)
+    }
+
+    fn set_device_filters(&mut self, filters: &[String]) {
+        self.registry.set_device_filters(filters);
+    }
+
     fn device_count(&self) -> usize {
         self.registry.device_count()
     }
@@ -136,6 +163,56 @@ impl InputEventSource for EvdevUdevEventSource {
     fn device_paths(&self) -> Vec<PathBuf> {
         self.registry.device_paths()
     }
+
+    fn set_device_classes(
+        &mut self,
+        classes: &[InputDeviceClass],
+    ) -> Result<Vec<InputSourceEvent>> {
+        Ok(if self.registry.set_classes(classes)? {
+            vec![InputSourceEvent::DeviceSetChanged]
+        } else {
+            Vec::new()
+        })
+    }
+
+    fn accept_trigger(&mut self, trigger: &ShortcutTrigger) -> Result<()> {
+        bail!(
+            "Cannot trigger shortcut '{}' while shortcuts come from input devices; \
+             set shortcuts.source to \"compositor\"",
+            trigger.name
+        )
+    }
+}
+
+impl InputEventSource for CompositorEventSource {
+    fn pressed_keys(&self) -> &HashSet<evdev::Key> {
+        &self.pressed_keys
+    }
+
+    fn pressed_keys_on(&self, _device: &str) -> HashSet<evdev::Key> {
+        HashSet::new()
+    }
+
+    fn set_device_filters(&mut self, _filters: &[String]) {}
+
+    fn device_count(&self) -> usize {
+        0
+    }
+
+    fn device_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    fn set_device_classes(
+        &mut self,
+        _classes: &[InputDeviceClass],
+    ) -> Result<Vec<InputSourceEvent>> {
+        Ok(Vec::new())
+    }
+
+    fn accept_trigger(&mut self, _trigger: &ShortcutTrigger) -> Result<()> {
+        Ok(())
+    }
 }
 
 fn events_from_poll(outcome: PollOutcome) -> Result<Vec<InputSourceEvent>> {
@@ -0,0 +1,220 @@
+use super::{backup_file, xdg_config_home};
+use crate::config::{ShortcutSource, ShortcutsConfig};
+use crate::input::parse_shortcut;
+use crate::ConfigManager;
+use anyhow::{anyhow, bail, Result};
+use evdev::Key;
+use owo_colors::OwoColorize;
+use std::fs;
+
+pub fn install(force: bool) -> Result<()> {
+    println!("{}", "Installing Hyprland binds...".blue());
+
+    let shortcuts = ConfigManager::load()?.get().shortcuts;
+    let exe = std::env::current_exe()?;
+    let exe = exe
+        .to_str()
+        .ok_or_else(|| anyhow!("Executable path is not valid UTF-8"))?;
+    let rendered = render_binds(&shortcuts, exe);
+
+    let hypr_dir = xdg_config_home().join("hypr");
+    fs::create_dir_all(&hypr_dir)?;
+    let dst = hypr_dir.join("hyprwhspr-rs.conf");
+
+    // Check if already installed and identical
+    if dst.exists() && !force && fs::read_to_string(&dst)? == rendered {
+        println!("  {} Binds already up to date", "○".yellow());
+    } else {
+        if dst.exists() && !force {
+            backup_file(&dst)?;
+        }
+        fs::write(&dst, &rendered)?;
+        println!("  {} Installed: {}", "✓".green(), dst.display());
+    }
+
+    println!("  Add to hyprland.conf: source = {}", dst.display());
+    if shortcuts.source == ShortcutSource::Evdev {
+        println!(
+            "  {} Set \"source\": \"compositor\" under \"shortcuts\" in the hyprwhspr-rs \
+             config, or every shortcut fires twice",
+            "!".yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Renders a Hyprland config snippet binding the configured shortcuts.
+fn render_binds(shortcuts: &ShortcutsConfig, exe: &str) -> String {
+    let mut out = String::from(
+        "# Generated by `hyprwhspr-rs install --hyprland` from the shortcuts config.\n\
+         # Expects \"source\": \"compositor\" under \"shortcuts\".\n",
+    );
+
+    if let Some(shortcut) = &shortcuts.press {
+        push_binds(&mut out, "press", shortcut, |mods, key| {
+            format!("bind = {mods}, {key}, exec, {exe} record toggle\n")
+        });
+    }
+    if let Some(shortcut) = &shortcuts.hold {
+        push_binds(&mut out, "hold", shortcut, |mods, key| {
+            format!(
+                "bind = {mods}, {key}, exec, {exe} record start\n\
+                 bindr = {mods}, {key}, exec, {exe} record stop\n"
+            )
+        });
+    }
+    if let Some(shortcut) = &shortcuts.cancel {
+        // A bind takes the key away from every window, even while idle.
+        out.push_str("\n# cancel: uncomment to give up the key everywhere\n");
+        if let Ok((mods, key)) = hyprland_keys(shortcut) {
+            out.push_str(&format!(
+                "# bind = {mods}, {key}, exec, {exe} shortcut press cancel\n\
+                 # bindr = {mods}, {key}, exec, {exe} shortcut release cancel\n"
+            ));
+        }
+    }
+    for binding in &shortcuts.bindings {
+        let name = shell_word(&binding.name);
+        if binding.device.is_some() {
+            out.push_str(&format!(
+                "\n# {}: skipped, compositor binds cannot tell devices apart\n",
+                binding.name
+            ));
+            continue;
+        }
+        push_binds(&mut out, &binding.name, &binding.shortcut, |mods, key| {
+            format!(
+                "bind = {mods}, {key}, exec, {exe} shortcut press {name}\n\
+                 bindr = {mods}, {key}, exec, {exe} shortcut release {name}\n"
+            )
+        });
+    }
+
+    out
+}
+
+fn push_binds(
+    out: &mut String,
+    name: &str,
+    shortcut: &str,
+    render: impl FnOnce(&str, &str) -> String,
+) {
+    out.push_str(&format!("\n# {name}: {shortcut}\n"));
+    match hyprland_keys(shortcut) {
+        Ok((mods, key)) => out.push_str(&render(&mods, &key)),
+        Err(err) => out.push_str(&format!("# skipped: {err}\n")),
+    }
+}
+
+/// Splits a shortcut into Hyprland's modifier list and key name.
+fn hyprland_keys(shortcut: &str) -> Result<(String, String)> {
+    let keys = parse_shortcut(shortcut)?;
+    let mut mods = Vec::new();
+    let mut others = Vec::new();
+    for key in keys {
+        match key {
+            Key::KEY_LEFTMETA | Key::KEY_RIGHTMETA => mods.push("SUPER"),
+            Key::KEY_LEFTCTRL | Key::KEY_RIGHTCTRL => mods.push("CTRL"),
+            Key::KEY_LEFTALT | Key::KEY_RIGHTALT => mods.push("ALT"),
+            Key::KEY_LEFTSHIFT | Key::KEY_RIGHTSHIFT => mods.push("SHIFT"),
+            other => others.push(other),
+        }
+    }
+    mods.sort_by_key(|name| {
+        ["SUPER", "CTRL", "ALT", "SHIFT"]
+            .iter()
+            .position(|m| m == name)
+    });
+    mods.dedup();
+
+    let [key] = others[..] else {
+        bail!("Hyprland binds need exactly one key besides the modifiers");
+    };
+    Ok((mods.join(" "), hyprland_key_name(key)?))
+}
+
+fn hyprland_key_name(key: Key) -> Result<String> {
+    let code = key.code();
+    if (Key::BTN_LEFT.code()..=Key::BTN_TASK.code()).contains(&code) {
+        return Ok(format!("mouse:{code}"));
+    }
+    if code >= Key::BTN_0.code() {
+        bail!("{key:?} has no Hyprland key name");
+    }
+
+    let name = format!("{key:?}");
+    let name = name.trim_start_matches("KEY_");
+    let is_function_key =
+        name.len() > 1 && name.starts_with('F') && name[1..].chars().all(|ch| ch.is_ascii_digit());
+    Ok(match name {
+        "ESC" => "Escape".to_string(),
+        "SPACE" => "space".to_string(),
+        "ENTER" => "Return".to_string(),
+        "TAB" => "Tab".to_string(),
+        _ if name.len() == 1 || is_function_key => name.to_string(),
+        // xkb keycodes are evdev codes offset by 8.
+        _ => format!("code:{}", code + 8),
+    })
+}
+
+fn shell_word(word: &str) -> String {
+    if word
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || "-_.".contains(ch))
+    {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ShortcutAction, ShortcutBindingConfig, ShortcutGesture};
+
+    #[test]
+    fn renders_binds_for_configured_shortcuts() {
+        let shortcuts = ShortcutsConfig {
+            press: Some("SUPER+ALT+D".to_string()),
+            hold: Some("SUPER+ALT".to_string()),
+            cancel: Some("ESC".to_string()),
+            bindings: vec![ShortcutBindingConfig {
+                name: "side note".to_string(),
+                shortcut: "BTN_SIDE".to_string(),
+                action: ShortcutAction::Hold,
+                gesture: ShortcutGesture::Press,
+                device: None,
+                profile: None,
+                provider: None,
+            }],
+            ..ShortcutsConfig::default()
+        };
+
+        let rendered = render_binds(&shortcuts, "/usr/bin/hyprwhspr-rs");
+
+        assert!(
+            rendered.contains("bind = SUPER ALT, D, exec, /usr/bin/hyprwhspr-rs record toggle\n")
+        );
+        assert!(rendered.contains("# hold: SUPER+ALT\n# skipped:"));
+        assert!(rendered
+            .contains("# bind = , Escape, exec, /usr/bin/hyprwhspr-rs shortcut press cancel\n"));
+        assert!(rendered.contains(
+            "bindr = , mouse:275, exec, /usr/bin/hyprwhspr-rs shortcut release 'side note'\n"
+        ));
+    }
+
+    #[test]
+    fn falls_back_to_keycodes_for_unnamed_keys() {
+        assert_eq!(
+            hyprland_keys("CTRL+SHIFT+F9").unwrap(),
+            ("CTRL SHIFT".to_string(), "F9".to_string())
+        );
+        assert_eq!(
+            hyprland_keys("SUPER+KEY_COMMA").unwrap().1,
+            format!("code:{}", Key::KEY_COMMA.code() + 8)
+        );
+        assert!(hyprland_keys("SUPER+A+B").is_err());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod elephant;
+pub mod hyprland;
 pub mod systemd;
 pub mod waybar;
 
@@ -17,6 +18,7 @@ pub enum Component {
     Waybar,
     Systemd,
     Elephant,
+    Hyprland,
 }
 
 impl Component {
@@ -25,11 +27,17 @@ impl Component {
             Component::Waybar => "Waybar module + CSS",
             Component::Systemd => "Systemd user service",
             Component::Elephant => "Elephant menu (Walker)",
+            Component::Hyprland => "Hyprland shortcut binds",
         }
     }
 
     pub fn all() -> &'static [Component] {
-        &[Component::Waybar, Component::Systemd, Component::Elephant]
+        &[
+            Component::Waybar,
+            Component::Systemd,
+            Component::Elephant,
+            Component::Hyprland,
+        ]
     }
 }
 
@@ -61,11 +69,14 @@ pub fn run_install(args: &InstallArgs) -> Result<()> {
         if args.all || args.elephant {
             selected.push(Component::Elephant);
         }
+        if args.all || args.hyprland {
+            selected.push(Component::Hyprland);
+        }
         selected
     } else {
         // Interactive mode
         if !io::stdin().is_terminal() {
-            anyhow::bail!("No TTY available for interactive mode. Use --waybar, --service, --elephant, or --all flags.");
+            anyhow::bail!("No TTY available for interactive mode. Use --waybar, --service, --elephant, --hyprland, or --all flags.");
         }
         interactive_select()?
     };
@@ -84,6 +95,7 @@ pub fn run_install(args: &InstallArgs) -> Result<()> {
             Component::Waybar => waybar::install(args.force)?,
             Component::Systemd => systemd::install(args.force)?,
             Component::Elephant => elephant::install(args.force)?,
+            Component::Hyprland => hyprland::install(args.force)?,
         }
     }
 
@@ -105,7 +117,7 @@ fn interactive_select() -> Result<Vec<Component>> {
     let selections = MultiSelect::with_theme(&theme)
         .with_prompt("Select components to install (Space to toggle, Enter to confirm)")
         .items(&items)
-        .defaults(&[true, true, false]) // waybar + systemd on by default
+        .defaults(&[true, true, false, false]) // waybar + systemd on by default
         .interact()?;
 
     Ok(selections
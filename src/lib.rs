@@ -9,11 +9,16 @@ pub mod input;
 pub mod install;
 pub mod logging;
 pub mod paths;
+pub mod profiles;
 pub mod status;
+pub mod streaming;
 pub mod text;
 pub mod transcription;
 pub mod whisper;
 
+#[cfg(test)]
+mod test_http;
+
 pub use app::HyprwhsprApp;
 pub use config::{Config, ConfigManager};
 pub use control::{send_record_command, RecordCommand, RecorderState};
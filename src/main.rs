@@ -1,9 +1,9 @@
 use anyhow::Result;
 use clap::Parser;
 use hyprwhspr_rs::{
-    cli::{Cli, Command, RecordAction},
+    cli::{Cli, Command, RecordAction, ShortcutAction},
     config::TranscriptionProvider,
-    control::RecordCommand,
+    control::{send_control_command, ControlCommand, RecordCommand, ShortcutTrigger},
     install,
     logging::TextPipelineFormatter,
     send_record_command, ConfigManager, HyprwhsprApp,
@@ -31,6 +31,21 @@ async fn main() -> Result<()> {
                 println!("{state}");
                 return Ok(());
             }
+            Command::Shortcut(args) => {
+                let trigger = match args.action {
+                    ShortcutAction::Press { name } => ShortcutTrigger {
+                        name,
+                        pressed: true,
+                    },
+                    ShortcutAction::Release { name } => ShortcutTrigger {
+                        name,
+                        pressed: false,
+                    },
+                };
+                let state = send_control_command(&ControlCommand::Shortcut(trigger)).await?;
+                println!("{state}");
+                return Ok(());
+            }
         }
     }
 
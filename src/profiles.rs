@@ -0,0 +1,150 @@
+use crate::config::{AppProfileConfig, Config, TranscriptionProvider};
+use crate::input::{class_hint_index, normalize_hint_classes};
+
+/// Returns the first profile whose `classes` match the window class.
+pub fn match_profile<'a>(
+    profiles: &'a [AppProfileConfig],
+    class: &str,
+) -> Option<&'a AppProfileConfig> {
+    profiles.iter().find(|profile| {
+        let classes = normalize_hint_classes(profile.classes.clone());
+        class_hint_index(class, &classes).is_some()
+    })
+}
+
+/// Builds the configuration a recording runs with while `profile` is active.
+/// Prompt and language land on the profile's provider so they take precedence
+/// over that provider's own settings.
+pub fn apply_profile(config: &Config, profile: &AppProfileConfig) -> Config {
+    let mut effective = config.clone();
+
+    if let Some(provider) = &profile.provider {
+        effective.transcription.provider = provider.clone();
+    }
+
+    let provider = effective.transcription.provider.clone();
+    let language = profile
+        .language
+        .as_deref()
+        .map(str::trim)
+        .filter(|language| !language.is_empty());
+    if let Some(language) = language {
+        effective.transcription.language = language.to_string();
+    }
+
+    if let Some((prompt_slot, language_slot)) = provider_overrides(&mut effective, &provider) {
+        if let Some(prompt) = &profile.prompt {
+            *prompt_slot = prompt.clone();
+        }
+        if let Some(language) = language {
+            *language_slot = Some(language.to_string());
+        }
+    }
+
+    if let Some(normalization) = &profile.normalization {
+        effective.normalization = normalization.clone();
+    }
+
+    effective.word_overrides.extend(
+        profile
+            .word_overrides
+            .iter()
+            .map(|(spoken, written)| (spoken.clone(), written.clone())),
+    );
+
+    effective
+}
+
+fn provider_overrides<'a>(
+    config: &'a mut Config,
+    provider: &TranscriptionProvider,
+) -> Option<(&'a mut String, &'a mut Option<String>)> {
+    let transcription = &mut config.transcription;
+    match provider {
+        TranscriptionProvider::WhisperCpp => Some((
+            &mut transcription.whisper_cpp.prompt,
+            &mut transcription.whisper_cpp.language,
+        )),
+        TranscriptionProvider::Groq => Some((
+            &mut transcription.groq.prompt,
+            &mut transcription.groq.language,
+        )),
+        TranscriptionProvider::Gemini => Some((
+            &mut transcription.gemini.prompt,
+            &mut transcription.gemini.language,
+        )),
+        TranscriptionProvider::Parakeet => Some((
+            &mut transcription.parakeet.prompt,
+            &mut transcription.parakeet.language,
+        )),
+        TranscriptionProvider::Custom(name) => transcription
+            .custom
+            .get_mut(name)
+            .map(|custom| (&mut custom.prompt, &mut custom.language)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcription::{provider_language, TranscriptionLanguage};
+
+    fn profile(name: &str, classes: &[&str]) -> AppProfileConfig {
+        AppProfileConfig {
+            name: name.to_string(),
+            classes: classes.iter().map(|class| class.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn matches_whole_class_or_component_and_first_profile_wins() {
+        let profiles = vec![
+            profile("code", &["kitty", "zed"]),
+            profile("prose", &["firefox", "dev.zed.Zed"]),
+        ];
+
+        assert_eq!(
+            match_profile(&profiles, "dev.zed.Zed").map(|p| p.name.as_str()),
+            Some("code")
+        );
+        assert_eq!(
+            match_profile(&profiles, "Firefox").map(|p| p.name.as_str()),
+            Some("prose")
+        );
+        assert!(match_profile(&profiles, "org.gnome.Nautilus").is_none());
+    }
+
+    #[test]
+    fn profile_overrides_land_on_the_selected_provider() {
+        let mut config = Config::default();
+        config.transcription.groq.language = Some("fr".to_string());
+        config
+            .word_overrides
+            .insert("hyper".to_string(), "Hyper".to_string());
+
+        let code = AppProfileConfig {
+            provider: Some(TranscriptionProvider::Groq),
+            prompt: Some("Rust identifiers.".to_string()),
+            language: Some("en".to_string()),
+            word_overrides: [("drop".to_string(), "Drop".to_string())].into(),
+            ..profile("code", &["kitty"])
+        };
+
+        let effective = apply_profile(&config, &code);
+        assert_eq!(
+            effective.transcription.provider,
+            TranscriptionProvider::Groq
+        );
+        assert_eq!(effective.transcription.groq.prompt, "Rust identifiers.");
+        assert_eq!(
+            provider_language(&effective, &TranscriptionProvider::Groq),
+            TranscriptionLanguage::Code("en".to_string())
+        );
+        assert_eq!(effective.word_overrides.len(), 2);
+        assert_eq!(
+            config.transcription.provider,
+            TranscriptionProvider::WhisperCpp
+        );
+    }
+}
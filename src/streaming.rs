@@ -0,0 +1,224 @@
+use anyhow::{Context, Result};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::app::{resample_audio, RecordingProfile};
+use crate::audio::{CapturedAudio, FastVadSettings, PhraseSegmenter};
+use crate::config::Config;
+use crate::input::TextInjector;
+use crate::text::LlmPostProcessor;
+use crate::transcription::{AudioLevel, HallucinationFilter};
+
+/// Builds the phrase segmenter for streaming mode. Phrase boundaries use the
+/// `fast_vad` detector tuning with `streaming.phrase_silence_ms` as the pause.
+pub fn phrase_segmenter(config: &Config, sample_rate_hz: u32) -> Result<PhraseSegmenter> {
+    let mut vad_config = config.fast_vad.clone();
+    vad_config.silence_timeout_ms = config.streaming.phrase_silence_ms;
+    PhraseSegmenter::new(FastVadSettings::from_config(&vad_config), sample_rate_hz)
+        .context("Failed to initialize streaming phrase detection")
+}
+
+/// What a finished streaming session produced.
+#[derive(Debug, Default)]
+pub struct StreamingReport {
+    pub phrases: usize,
+    pub text: String,
+}
+
+/// Background task that transcribes and injects each phrase as soon as the
+/// segmenter closes it, while the recording keeps running.
+pub struct StreamingDictation {
+    handle: JoinHandle<Result<StreamingReport>>,
+    abort: watch::Sender<bool>,
+}
+
+impl StreamingDictation {
+    pub(crate) fn spawn(
+        chunks: mpsc::UnboundedReceiver<CapturedAudio>,
+        segmenter: PhraseSegmenter,
+        profile: RecordingProfile,
+        post_processor: Option<LlmPostProcessor>,
+        hallucination_filter: HallucinationFilter,
+        text_injector: Arc<Mutex<TextInjector>>,
+    ) -> Self {
+        let (abort, aborted) = watch::channel(false);
+        let worker = StreamingWorker {
+            segmenter,
+            profile,
+            post_processor,
+            hallucination_filter,
+            text_injector,
+            sample_rate: None,
+            aborted,
+            report: StreamingReport::default(),
+        };
+        Self {
+            handle: tokio::spawn(worker.run(chunks)),
+            abort,
+        }
+    }
+
+    /// Waits for the last phrase to be transcribed and injected. The chunk
+    /// channel must already be closed, i.e. the recording stopped.
+    pub async fn finish(&mut self) -> Result<StreamingReport> {
+        (&mut self.handle)
+            .await
+            .context("Streaming transcription task panicked")?
+    }
+
+    /// Stops transcribing further phrases. A phrase already being injected
+    /// is typed out in full rather than cut off mid-edit.
+    pub fn abort(&self) {
+        self.abort.send_replace(true);
+    }
+}
+
+struct StreamingWorker {
+    segmenter: PhraseSegmenter,
+    profile: RecordingProfile,
+    post_processor: Option<LlmPostProcessor>,
+    hallucination_filter: HallucinationFilter,
+    text_injector: Arc<Mutex<TextInjector>>,
+    /// Latest capture rate measured by the recording session.
+    sample_rate: Option<u32>,
+    aborted: watch::Receiver<bool>,
+    report: StreamingReport,
+}
+
+impl StreamingWorker {
+    async fn run(
+        mut self,
+        mut chunks: mpsc::UnboundedReceiver<CapturedAudio>,
+    ) -> Result<StreamingReport> {
+        while let Some(chunk) = chunks.recv().await {
+            self.sample_rate = Some(chunk.sample_rate);
+            for phrase in self.segmenter.push(&chunk.samples)? {
+                self.handle_phrase(phrase).await;
+            }
+            if self.is_aborted() {
+                return Ok(self.report);
+            }
+        }
+
+        if let Some(phrase) = self.segmenter.finish()? {
+            self.handle_phrase(phrase).await;
+        }
+
+        Ok(self.report)
+    }
+
+    fn is_aborted(&self) -> bool {
+        *self.aborted.borrow()
+    }
+
+    /// Runs `future` unless the session is aborted first.
+    async fn unless_aborted<T>(&self, future: impl Future<Output = T>) -> Option<T> {
+        let mut aborted = self.aborted.clone();
+        tokio::select! {
+            output = future => Some(output),
+            _ = aborted.wait_for(|aborted| *aborted) => None,
+        }
+    }
+
+    async fn handle_phrase(&mut self, audio: Vec<f32>) {
+        if self.is_aborted() {
+            return;
+        }
+        let index = self.report.phrases + 1;
+        let sample_rate = self
+            .sample_rate
+            .unwrap_or_else(|| self.segmenter.sample_rate_hz());
+        let duration_secs = audio.len() as f32 / sample_rate as f32;
+        debug!("🎙️ Phrase {} closed ({:.2}s)", index, duration_secs);
+
+        let started_at = Instant::now();
+        let audio = if sample_rate == 16_000 {
+            audio
+        } else {
+            debug!(
+                "Resampling phrase {} from {} Hz to 16 kHz for transcription backend",
+                index, sample_rate
+            );
+            resample_audio(&audio, sample_rate, 16_000)
+        };
+        let level = AudioLevel::measure(&audio, 16_000);
+        let transcription = self
+            .profile
+            .transcriber
+            .transcribe_with(audio, self.profile.options.clone());
+        let text = match self.unless_aborted(transcription).await {
+            Some(Ok(result)) => self.hallucination_filter.filter(&result.text, level),
+            Some(Err(err)) => {
+                warn!("Failed to transcribe phrase {}: {:#}", index, err);
+                return;
+            }
+            None => return,
+        };
+
+        if let Some(command) = self.profile.text_normalizer.edit_command(&text).await {
+            info!("✏️ Phrase {} is an editing command: {:?}", index, command);
+            let mut injector = self.text_injector.lock().await;
+            match injector.apply_edit(&command).await {
+                Ok(true) => return,
+                Ok(false) => {}
+                Err(err) => {
+                    warn!("Failed to apply editing command: {:#}", err);
+                    return;
+                }
+            }
+        }
+
+        let mut normalized = self.profile.text_normalizer.normalize_async(&text).await;
+        if let Some(post_processor) = &self.post_processor {
+            if !normalized.trim().is_empty() {
+                match self
+                    .unless_aborted(post_processor.process(&normalized))
+                    .await
+                {
+                    Some(processed) => normalized = processed,
+                    None => return,
+                }
+            }
+        }
+        if normalized.trim().is_empty() {
+            debug!("Phrase {} produced no text", index);
+            return;
+        }
+
+        let mut injector = self.text_injector.lock().await;
+        let chunk = if self.profile.text_normalizer.smart_spacing_enabled() {
+            let mut context = injector.injection_context().await;
+            if !self.report.text.is_empty() {
+                // Later phrases continue the earlier ones of this recording.
+                context.preceding = Some(self.report.text.clone());
+            }
+            self.profile
+                .text_normalizer
+                .fit_to_context(&normalized, &text, &context)
+        } else if self.report.text.is_empty() {
+            normalized
+        } else {
+            // Phrases are separate transcriptions, so re-insert the pause as a space.
+            format!(" {normalized}")
+        };
+        let chunk = self.profile.text_normalizer.expand_snippets(&chunk);
+
+        if let Err(err) = injector.inject_text(&chunk).await {
+            warn!("Failed to inject phrase {}: {:#}", index, err);
+            return;
+        }
+
+        info!(
+            "📝 Phrase {} injected after {:.0} ms: \"{}\"",
+            index,
+            started_at.elapsed().as_secs_f64() * 1000.0,
+            chunk.trim_start()
+        );
+        self.report.phrases = index;
+        self.report.text.push_str(&chunk);
+    }
+}
@@ -0,0 +1,55 @@
+//! One-request HTTP/1.1 helpers for tests that stub a local server.
+
+use anyhow::{bail, Result};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Reads one request and returns its header block and body.
+pub(crate) async fn read_request(stream: &mut TcpStream) -> Result<(String, Vec<u8>)> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 4096];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            bail!("request closed before headers");
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position;
+        }
+    };
+
+    let headers = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+    let content_length = headers
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    let body_start = header_end + 4;
+    while buffer.len().saturating_sub(body_start) < content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    Ok((headers, buffer.split_off(body_start)))
+}
+
+/// Writes a JSON response and closes the connection.
+pub(crate) async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    body: &[u8],
+) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.write_all(body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
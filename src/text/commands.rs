@@ -0,0 +1,231 @@
+use super::tokens::{capitalize, tokenize, Token};
+
+/// Inserted after the first character of words escaped with "literal", so no
+/// later step recognizes them. Removed once the built-in steps have run.
+const LITERAL_MARKER: char = '\u{2060}';
+
+/// Punctuation Whisper attaches to words on its own.
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpokenCommand {
+    /// Joined to the previous word.
+    Attach(&'static str),
+    /// Joined to the next word.
+    Open(&'static str),
+    /// Newline and tab survive ITN only when inserted after it, so the
+    /// control_commands step handles them; listed here so "literal" escapes them.
+    Control,
+    NoSpace,
+    AllCaps,
+    EndCaps,
+    CamelCase,
+    SnakeCase,
+    Literal,
+}
+
+const SPOKEN_COMMANDS: &[(&str, SpokenCommand)] = &[
+    ("comma", SpokenCommand::Attach(",")),
+    ("period", SpokenCommand::Attach(".")),
+    ("full stop", SpokenCommand::Attach(".")),
+    ("question mark", SpokenCommand::Attach("?")),
+    ("exclamation mark", SpokenCommand::Attach("!")),
+    ("exclamation point", SpokenCommand::Attach("!")),
+    ("colon", SpokenCommand::Attach(":")),
+    ("semicolon", SpokenCommand::Attach(";")),
+    ("open quote", SpokenCommand::Open("\"")),
+    ("close quote", SpokenCommand::Attach("\"")),
+    ("new line", SpokenCommand::Control),
+    ("newline", SpokenCommand::Control),
+    ("tab", SpokenCommand::Control),
+    ("no space", SpokenCommand::NoSpace),
+    ("all caps", SpokenCommand::AllCaps),
+    ("end caps", SpokenCommand::EndCaps),
+    ("camel case", SpokenCommand::CamelCase),
+    ("snake case", SpokenCommand::SnakeCase),
+    ("literal", SpokenCommand::Literal),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IdentifierCase {
+    Camel,
+    Snake,
+}
+
+#[derive(Default)]
+struct Writer {
+    text: String,
+    /// The next word joins the previous output without a space.
+    glue: bool,
+    /// Bytes of transcript punctuation at the end of `text`.
+    dictated_tail: usize,
+}
+
+impl Writer {
+    fn word(&mut self, word: &str, trailing: &str) {
+        if !self.text.is_empty() && !self.glue {
+            self.text.push(' ');
+        }
+        self.text.push_str(word);
+        self.text.push_str(trailing);
+        self.glue = false;
+        self.dictated_tail = trailing.len();
+    }
+
+    fn attach(&mut self, symbol: &str) {
+        // A spoken mark replaces whatever punctuation Whisper guessed there.
+        self.text.truncate(self.text.len() - self.dictated_tail);
+        self.text.push_str(symbol);
+        self.glue = false;
+        self.dictated_tail = 0;
+    }
+
+    fn open(&mut self, symbol: &str) {
+        if !self.text.is_empty() && !self.glue {
+            self.text.push(' ');
+        }
+        self.text.push_str(symbol);
+        self.glue = true;
+        self.dictated_tail = 0;
+    }
+}
+
+/// Applies spoken punctuation and formatting commands word by word, so a
+/// command can be escaped with "literal" (e.g. "literal period").
+pub(super) fn apply_spoken_commands(input: &str) -> (String, usize) {
+    let tokens = tokenize(input, |ch| TRAILING_PUNCTUATION.contains(&ch));
+    let mut writer = Writer::default();
+    let mut count = 0;
+    let mut all_caps = false;
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let Some((command, len)) = match_command(&tokens[index..]) else {
+            let token = &tokens[index];
+            writer.word(&apply_caps(token.word, all_caps), token.trailing);
+            index += 1;
+            continue;
+        };
+        let words = &tokens[index..index + len];
+        index += len;
+        if command != SpokenCommand::Control {
+            count += 1;
+        }
+
+        match command {
+            SpokenCommand::Attach(symbol) => writer.attach(symbol),
+            SpokenCommand::Open(symbol) => writer.open(symbol),
+            SpokenCommand::Control => {
+                for token in words {
+                    writer.word(&apply_caps(token.word, all_caps), token.trailing);
+                }
+            }
+            SpokenCommand::NoSpace => writer.glue = true,
+            SpokenCommand::AllCaps => all_caps = true,
+            SpokenCommand::EndCaps => all_caps = false,
+            SpokenCommand::CamelCase | SpokenCommand::SnakeCase => {
+                let case = if command == SpokenCommand::CamelCase {
+                    IdentifierCase::Camel
+                } else {
+                    IdentifierCase::Snake
+                };
+                let len = identifier_run(&tokens[index..]);
+                if len > 0 {
+                    let run = &tokens[index..index + len];
+                    let identifier = format_identifier(run, case);
+                    writer.word(&apply_caps(&identifier, all_caps), run[len - 1].trailing);
+                    index += len;
+                }
+            }
+            SpokenCommand::Literal => {
+                let len = match_command(&tokens[index..]).map_or(1, |(_, len)| len);
+                let escaped = &tokens[index..(index + len).min(tokens.len())];
+                if escaped.is_empty() {
+                    // Nothing follows, so "literal" was just a word.
+                    writer.word(&apply_caps("literal", all_caps), "");
+                    count -= 1;
+                }
+                for token in escaped {
+                    let word = escape_literal(&apply_caps(token.word, all_caps));
+                    writer.word(&word, token.trailing);
+                }
+                index += escaped.len();
+            }
+        }
+    }
+
+    (writer.text, count)
+}
+
+/// Removes the markers left on words escaped with "literal".
+pub(super) fn strip_literal_markers(input: &str) -> String {
+    input.replace(LITERAL_MARKER, "")
+}
+
+fn match_command(tokens: &[Token<'_>]) -> Option<(SpokenCommand, usize)> {
+    SPOKEN_COMMANDS
+        .iter()
+        .filter_map(|(phrase, command)| {
+            let words = phrase.split(' ').collect::<Vec<_>>();
+            let matches = words.len() <= tokens.len()
+                && words
+                    .iter()
+                    .zip(tokens)
+                    .all(|(word, token)| token.key == *word);
+            matches.then_some((*command, words.len()))
+        })
+        .max_by_key(|(_, len)| *len)
+}
+
+/// Words a camel or snake case command applies to: up to the next command or
+/// the first word carrying punctuation.
+fn identifier_run(tokens: &[Token<'_>]) -> usize {
+    let mut len = 0;
+    for (offset, token) in tokens.iter().enumerate() {
+        if match_command(&tokens[offset..]).is_some() {
+            break;
+        }
+        len += 1;
+        if !token.trailing.is_empty() {
+            break;
+        }
+    }
+    len
+}
+
+fn format_identifier(tokens: &[Token<'_>], case: IdentifierCase) -> String {
+    match case {
+        IdentifierCase::Snake => tokens
+            .iter()
+            .map(|token| token.key.as_str())
+            .collect::<Vec<_>>()
+            .join("_"),
+        IdentifierCase::Camel => tokens
+            .iter()
+            .enumerate()
+            .map(|(index, token)| {
+                if index == 0 {
+                    token.key.clone()
+                } else {
+                    capitalize(&token.key)
+                }
+            })
+            .collect(),
+    }
+}
+
+fn apply_caps(word: &str, all_caps: bool) -> String {
+    if all_caps {
+        word.to_uppercase()
+    } else {
+        word.to_string()
+    }
+}
+
+fn escape_literal(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => format!("{first}{LITERAL_MARKER}{}", chars.as_str()),
+        None => String::new(),
+    }
+}
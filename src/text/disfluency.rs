@@ -0,0 +1,201 @@
+use super::tokens::{capitalize, tokenize, Token};
+
+/// Marks a restart: the words before it are dropped back to where the repair
+/// picks up again, e.g. "we should, I mean we must".
+const FALSE_START_MARKER: &[&str] = &["i", "mean"];
+
+/// Words that are grammatical when said twice ("I said that that was
+/// fine"), so their repetitions are kept.
+const LEGITIMATE_REPEATS: &[&str] = &["that", "had"];
+
+const SENTENCE_END: &[char] = &['.', '!', '?'];
+
+/// Punctuation that sets a filler phrase off from the words around it.
+const PHRASE_BOUNDARY: &[char] = &[',', '.', '!', '?'];
+
+impl Token<'_> {
+    fn ends_sentence(&self) -> bool {
+        self.trailing.contains(SENTENCE_END)
+    }
+
+    fn ends_phrase(&self) -> bool {
+        self.trailing.contains(PHRASE_BOUNDARY)
+    }
+
+    fn is_capitalized(&self) -> bool {
+        self.word.chars().next().is_some_and(char::is_uppercase)
+    }
+}
+
+/// A word that made it into the output.
+struct KeptWord {
+    text: String,
+    trailing: String,
+    key: String,
+}
+
+impl KeptWord {
+    fn ends_sentence(&self) -> bool {
+        self.trailing.contains(SENTENCE_END)
+    }
+
+    fn ends_phrase(&self) -> bool {
+        self.trailing.contains(PHRASE_BOUNDARY)
+    }
+}
+
+/// Removes filler words, immediate repetitions and "I mean" false starts.
+#[derive(Debug, Clone)]
+pub(super) struct DisfluencyFilter {
+    /// Lowercase filler phrases split into words, longest first.
+    fillers: Vec<Vec<String>>,
+}
+
+impl DisfluencyFilter {
+    pub(super) fn new(fillers: &[String]) -> Self {
+        let mut fillers = fillers
+            .iter()
+            .map(|filler| {
+                filler
+                    .split_whitespace()
+                    .map(str::to_lowercase)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|words| !words.is_empty())
+            .collect::<Vec<_>>();
+        fillers.sort_by_key(|words| std::cmp::Reverse(words.len()));
+        Self { fillers }
+    }
+
+    /// Returns the cleaned text and how many disfluencies were removed.
+    pub(super) fn apply(&self, input: &str) -> (String, usize) {
+        let mut count = 0;
+        let lines = input
+            .split('\n')
+            .map(|line| {
+                let (line, removed) = self.apply_line(line);
+                count += removed;
+                line
+            })
+            .collect::<Vec<_>>();
+        (lines.join("\n"), count)
+    }
+
+    fn apply_line(&self, input: &str) -> (String, usize) {
+        let tokens = tokenize(input, |ch| ch.is_ascii_punctuation() && ch != '\'');
+        let mut kept: Vec<KeptWord> = Vec::new();
+        let mut count = 0;
+        let mut capitalize_next = false;
+        let mut index = 0;
+
+        while index < tokens.len() {
+            let at_sentence_start = kept.last().is_none_or(KeptWord::ends_sentence);
+
+            let after_boundary = kept.last().is_none_or(KeptWord::ends_phrase);
+            if let Some(len) = self.match_filler(&tokens[index..], after_boundary) {
+                let removed = &tokens[index..index + len];
+                let at_line_end = index + len == tokens.len();
+                capitalize_next |= at_sentence_start && removed[0].is_capitalized();
+                // A filler closing the sentence hands its full stop back, and
+                // one closing the line takes the comma before it along.
+                if removed[len - 1].ends_sentence() || (at_line_end && !at_sentence_start) {
+                    if let Some(last) = kept.last_mut() {
+                        last.trailing = removed[len - 1].trailing.to_string();
+                    }
+                }
+                index += len;
+                count += 1;
+                continue;
+            }
+
+            if let Some(restart) = false_start(&kept, &tokens[index..]) {
+                capitalize_next |= kept[restart]
+                    .text
+                    .chars()
+                    .next()
+                    .is_some_and(char::is_uppercase);
+                kept.truncate(restart);
+                index += FALSE_START_MARKER.len();
+                count += 1;
+                continue;
+            }
+
+            let token = &tokens[index];
+            index += 1;
+            if let Some(last) = kept.last_mut() {
+                if last.trailing.is_empty()
+                    && !token.bare_key().is_empty()
+                    && last.key == token.bare_key()
+                    && !LEGITIMATE_REPEATS.contains(&last.key.as_str())
+                {
+                    last.trailing = token.trailing.to_string();
+                    count += 1;
+                    continue;
+                }
+            }
+
+            let text = if capitalize_next {
+                capitalize(token.word)
+            } else {
+                token.word.to_string()
+            };
+            capitalize_next = false;
+            kept.push(KeptWord {
+                text,
+                trailing: token.trailing.to_string(),
+                key: token.bare_key().to_string(),
+            });
+        }
+
+        let text = kept
+            .into_iter()
+            .map(|word| word.text + &word.trailing)
+            .collect::<Vec<_>>()
+            .join(" ");
+        (text, count)
+    }
+
+    /// Length of the filler `tokens` opens with. Single words match anywhere;
+    /// phrases such as "you know" only when a comma or the sentence edge sits
+    /// on both sides, as in "Do you know the way?" they are not fillers.
+    fn match_filler(&self, tokens: &[Token<'_>], after_boundary: bool) -> Option<usize> {
+        self.fillers
+            .iter()
+            .find(|filler| {
+                let len = filler.len();
+                len <= tokens.len()
+                    && filler
+                        .iter()
+                        .zip(tokens)
+                        .all(|(word, token)| token.bare_key() == word)
+                    && (len == 1
+                        || (after_boundary
+                            && (len == tokens.len() || tokens[len - 1].ends_phrase())))
+            })
+            .map(Vec::len)
+    }
+}
+
+/// Where the restart begins in the kept words when `tokens` opens with "I
+/// mean" followed by a word already said earlier in the same sentence.
+fn false_start(kept: &[KeptWord], tokens: &[Token<'_>]) -> Option<usize> {
+    let marker = FALSE_START_MARKER.len();
+    if tokens.len() <= marker
+        || !FALSE_START_MARKER
+            .iter()
+            .zip(tokens)
+            .all(|(word, token)| token.bare_key() == *word)
+    {
+        return None;
+    }
+
+    let repair = tokens[marker].bare_key();
+    let sentence_start = kept
+        .iter()
+        .rposition(KeptWord::ends_sentence)
+        .map_or(0, |position| position + 1);
+    kept[sentence_start..]
+        .iter()
+        .rposition(|word| word.key == repair)
+        .map(|position| sentence_start + position)
+}
@@ -0,0 +1,158 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+static REPLACE_COMMAND_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)^replace\s+(.+?)\s+with\s+(.+)$").expect("valid replace command regex")
+});
+
+const SENTENCE_END: &[char] = &['.', '!', '?', '\n'];
+
+/// A spoken command that edits the last injected dictation instead of
+/// typing new text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditCommand {
+    /// "scratch that": erase the whole last injection.
+    ScratchThat,
+    /// "undo last": erase the last sentence of the last injection.
+    UndoLast,
+    /// "replace X with Y": retype the last injection with X replaced.
+    Replace { from: String, to: String },
+}
+
+/// Keystrokes that carry out an edit on the focused window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditPlan {
+    /// Characters to delete with backspace.
+    pub erase: usize,
+    /// Text to inject after erasing.
+    pub insert: String,
+    /// What the last injection reads as once the edit is done.
+    pub remaining: String,
+}
+
+impl EditCommand {
+    /// Recognizes an utterance that consists of nothing but an edit command.
+    pub fn parse(text: &str) -> Option<Self> {
+        let command = trim_command(text);
+        match command.to_lowercase().as_str() {
+            "scratch that" => return Some(Self::ScratchThat),
+            "undo last" => return Some(Self::UndoLast),
+            _ => {}
+        }
+
+        let captures = REPLACE_COMMAND_REGEX.captures(command)?;
+        let from = trim_command(&captures[1]);
+        let to = trim_command(&captures[2]);
+        (!from.is_empty()).then(|| Self::Replace {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+
+    /// Works out the edit against the text that was last injected, or `None`
+    /// when the command does not apply to it.
+    pub fn plan(&self, last_injection: &str) -> Option<EditPlan> {
+        if last_injection.is_empty() {
+            return None;
+        }
+
+        match self {
+            Self::ScratchThat => Some(EditPlan {
+                erase: last_injection.chars().count(),
+                insert: String::new(),
+                remaining: String::new(),
+            }),
+            Self::UndoLast => {
+                let start = last_sentence_start(last_injection);
+                Some(EditPlan {
+                    erase: last_injection[start..].chars().count(),
+                    insert: String::new(),
+                    remaining: last_injection[..start].to_string(),
+                })
+            }
+            Self::Replace { from, to } => {
+                let pattern = format!(r"(?i)\b{}\b", regex::escape(from));
+                let regex = Regex::new(&pattern).ok()?;
+                if !regex.is_match(last_injection) {
+                    return None;
+                }
+                let corrected = regex.replace_all(last_injection, regex::NoExpand(to));
+                Some(EditPlan {
+                    erase: last_injection.chars().count(),
+                    insert: corrected.to_string(),
+                    remaining: corrected.to_string(),
+                })
+            }
+        }
+    }
+}
+
+fn trim_command(text: &str) -> &str {
+    text.trim()
+        .trim_end_matches(|ch: char| ch.is_ascii_punctuation())
+        .trim()
+}
+
+/// Byte offset where the final sentence begins, including the whitespace
+/// that separates it from the previous one.
+fn last_sentence_start(text: &str) -> usize {
+    let body = text.trim_end_matches(|ch: char| SENTENCE_END.contains(&ch) || ch == ' ');
+    body.rfind(SENTENCE_END).map_or(0, |boundary| boundary + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_whole_utterance_commands_only() {
+        assert_eq!(
+            EditCommand::parse("Scratch that."),
+            Some(EditCommand::ScratchThat)
+        );
+        assert_eq!(
+            EditCommand::parse(" undo last "),
+            Some(EditCommand::UndoLast)
+        );
+        assert_eq!(
+            EditCommand::parse("Replace Hyperland with Hyprland."),
+            Some(EditCommand::Replace {
+                from: "Hyperland".to_string(),
+                to: "Hyprland".to_string(),
+            })
+        );
+        assert_eq!(EditCommand::parse("please scratch that idea"), None);
+        assert_eq!(EditCommand::parse("replace with nothing"), None);
+    }
+
+    #[test]
+    fn scratch_and_undo_erase_from_the_end() {
+        let last = " First one. Second one!";
+
+        let scratch = EditCommand::ScratchThat.plan(last).expect("plan");
+        assert_eq!(scratch.erase, last.chars().count());
+        assert!(scratch.remaining.is_empty());
+
+        let undo = EditCommand::UndoLast.plan(last).expect("plan");
+        assert_eq!(undo.remaining, " First one.");
+        assert_eq!(undo.erase, " Second one!".chars().count());
+        assert!(undo.insert.is_empty());
+
+        let single = EditCommand::UndoLast.plan("only sentence").expect("plan");
+        assert_eq!(single.erase, "only sentence".len());
+    }
+
+    #[test]
+    fn replace_retypes_the_corrected_text() {
+        let command = EditCommand::Replace {
+            from: "hyperland".to_string(),
+            to: "Hyprland".to_string(),
+        };
+
+        let plan = command.plan("I use Hyperland daily").expect("plan");
+        assert_eq!(plan.insert, "I use Hyprland daily");
+        assert_eq!(plan.erase, "I use Hyperland daily".len());
+        assert!(command.plan("nothing to fix").is_none());
+        assert!(EditCommand::ScratchThat.plan("").is_none());
+    }
+}
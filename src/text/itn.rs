@@ -0,0 +1,270 @@
+use super::normalize::NormalizeRule;
+use crate::config::{ItnConfig, ItnEngineKind};
+use anyhow::{bail, Context, Result};
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use text_processing_rs::{NormalizeOptions, custom_rules, normalize_sentence_with_options};
+use tracing::warn;
+
+static CUSTOM_RULES_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItnOutput {
+    pub text: String,
+    /// Rules that were available to the engine, shown in the pipeline log.
+    pub rule_count: usize,
+}
+
+/// Inverse text normalization: rewrites spoken numbers, dates, currency and
+/// symbols into their written form.
+pub trait ItnEngine: std::fmt::Debug + Send + Sync {
+    fn normalize(&self, text: &str, overrides: &[NormalizeRule]) -> ItnOutput;
+}
+
+/// English ITN from text-processing-rs.
+#[derive(Debug, Clone)]
+pub(super) struct TextProcessingItnEngine {
+    options: NormalizeOptions,
+}
+
+/// Leaves text untouched.
+#[derive(Debug, Clone, Default)]
+struct DisabledItnEngine;
+
+/// Pipes text through an external program: text on stdin, normalized text on
+/// stdout. Failures and timeouts leave the text unchanged.
+#[derive(Debug, Clone)]
+struct CommandItnEngine {
+    program: String,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+pub(super) fn build_itn_engine(config: &ItnConfig) -> Result<Arc<dyn ItnEngine>> {
+    Ok(match config.engine {
+        ItnEngineKind::English => Arc::new(TextProcessingItnEngine::default()),
+        ItnEngineKind::None => Arc::new(DisabledItnEngine),
+        ItnEngineKind::Command => {
+            let Some((program, args)) = config.command.split_first() else {
+                bail!("normalization.itn.command is required when the ITN engine is \"command\"");
+            };
+            Arc::new(CommandItnEngine {
+                program: program.clone(),
+                args: args.to_vec(),
+                timeout: Duration::from_millis(config.timeout_ms),
+            })
+        }
+    })
+}
+
+const APP_NORMALIZATION_RULES: &[(&str, &str)] = &[
+    ("dash dash", "--"),
+    ("dash", "-"),
+    ("hyphen", "-"),
+    ("underscore", "_"),
+    ("under score", "_"),
+    ("open paren", "("),
+    ("open parenthesis", "("),
+    ("open parentheses", "("),
+    ("close paren", ")"),
+    ("close parenthesis", ")"),
+    ("close parentheses", ")"),
+    ("open bracket", "["),
+    ("close bracket", "]"),
+    ("open brace", "{"),
+    ("close brace", "}"),
+    ("at symbol", "@"),
+    ("at sign", "@"),
+    ("hash", "#"),
+    ("hash tag", "#"),
+    ("hashtag", "#"),
+    ("pound", "#"),
+    ("dollar sign", "$"),
+    ("percent", "%"),
+    ("caret", "^"),
+    ("ampersand", "&"),
+    ("asterisk", "*"),
+    ("plus", "+"),
+    ("equals", "="),
+    ("equal", "="),
+    ("less than", "<"),
+    ("greater than", ">"),
+    ("slash", "/"),
+    ("backslash", "\\"),
+    ("pipe", "|"),
+    ("tilde", "~"),
+    ("grave", "`"),
+    ("open quote", "\""),
+    ("close quote", "\""),
+    ("open double quote", "\""),
+    ("close double quote", "\""),
+    ("quote", "\""),
+    ("double quote", "\""),
+    ("open single quote", "'"),
+    ("close single quote", "'"),
+    ("apostrophe", "'"),
+    ("single quote", "'"),
+];
+
+impl Default for TextProcessingItnEngine {
+    fn default() -> Self {
+        Self {
+            options: NormalizeOptions::new().with_disable_bare_second(true),
+        }
+    }
+}
+
+impl ItnEngine for TextProcessingItnEngine {
+    fn normalize(&self, text: &str, overrides: &[NormalizeRule]) -> ItnOutput {
+        let _guard = CUSTOM_RULES_LOCK
+            .lock()
+            .expect("text-processing-rs custom rules lock poisoned");
+
+        custom_rules::clear_rules();
+        for (spoken, written) in APP_NORMALIZATION_RULES {
+            custom_rules::add_rule(spoken, written);
+        }
+        for rule in overrides {
+            custom_rules::add_rule(&rule.spoken, &rule.written);
+        }
+
+        let result = normalize_sentence_with_options(text, self.options);
+        let rule_count = custom_rules::rule_count();
+        custom_rules::clear_rules();
+
+        ItnOutput {
+            text: result,
+            rule_count,
+        }
+    }
+}
+
+impl ItnEngine for DisabledItnEngine {
+    fn normalize(&self, text: &str, _overrides: &[NormalizeRule]) -> ItnOutput {
+        ItnOutput {
+            text: text.to_string(),
+            rule_count: 0,
+        }
+    }
+}
+
+impl ItnEngine for CommandItnEngine {
+    fn normalize(&self, text: &str, _overrides: &[NormalizeRule]) -> ItnOutput {
+        let text = match self.run(text) {
+            Ok(output) => output,
+            Err(err) => {
+                warn!(
+                    "ITN command {} failed; leaving text unchanged: {err:#}",
+                    self.program
+                );
+                text.to_string()
+            }
+        };
+        ItnOutput {
+            text,
+            rule_count: 0,
+        }
+    }
+}
+
+impl CommandItnEngine {
+    fn run(&self, text: &str) -> Result<String> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to start {}", self.program))?;
+
+        let mut stdin = child
+            .stdin
+            .take()
+            .context("ITN command stdin unavailable")?;
+        let mut stdout = child
+            .stdout
+            .take()
+            .context("ITN command stdout unavailable")?;
+        let input = text.to_string();
+        // Feed and drain on threads so a chatty command cannot fill a pipe
+        // and stall while we wait for it to exit.
+        let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+        let reader = thread::spawn(move || {
+            let mut output = String::new();
+            stdout.read_to_string(&mut output).map(|_| output)
+        });
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait().context("Failed to poll ITN command")? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!("timed out after {} ms", self.timeout.as_millis());
+            }
+            thread::sleep(COMMAND_POLL_INTERVAL);
+        };
+
+        // A command that ignores stdin may exit before reading it all.
+        let _ = writer.join();
+        let output = reader
+            .join()
+            .map_err(|_| anyhow::anyhow!("ITN command reader panicked"))?
+            .context("Failed to read ITN command output")?;
+        if !status.success() {
+            bail!("exited with {status}");
+        }
+
+        Ok(output.trim_end_matches(['\r', '\n']).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command_engine(command: &[&str], timeout_ms: u64) -> Arc<dyn ItnEngine> {
+        build_itn_engine(&ItnConfig {
+            engine: ItnEngineKind::Command,
+            command: command.iter().map(|part| part.to_string()).collect(),
+            timeout_ms,
+        })
+        .expect("command engine")
+    }
+
+    #[test]
+    fn command_engine_pipes_text_through_the_program() {
+        let engine = command_engine(&["tr", "a-z", "A-Z"], 5_000);
+        assert_eq!(engine.normalize("zwei hundert", &[]).text, "ZWEI HUNDERT");
+    }
+
+    #[test]
+    fn command_engine_failures_leave_text_unchanged() {
+        let failing = command_engine(&["false"], 5_000);
+        assert_eq!(failing.normalize("drei", &[]).text, "drei");
+
+        let missing = command_engine(&["hyprwhspr-no-such-itn-command"], 5_000);
+        assert_eq!(missing.normalize("vier", &[]).text, "vier");
+
+        let slow = command_engine(&["sleep", "5"], 50);
+        let started = Instant::now();
+        assert_eq!(slow.normalize("fünf", &[]).text, "fünf");
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn command_engine_requires_a_program() {
+        let config = ItnConfig {
+            engine: ItnEngineKind::Command,
+            ..ItnConfig::default()
+        };
+        assert!(build_itn_engine(&config).is_err());
+    }
+}
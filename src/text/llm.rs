@@ -0,0 +1,255 @@
+use anyhow::{anyhow, Context, Result};
+use reqwest::{Client, Url};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use super::snippets::markers;
+use crate::config::PostProcessingConfig;
+use crate::transcription::{is_absolute_endpoint, resolve_endpoint};
+
+/// Rewrites normalized dictation through an OpenAI-compatible chat
+/// completions endpoint, e.g. to fix grammar or drop filler words.
+#[derive(Debug, Clone)]
+pub struct LlmPostProcessor {
+    client: Client,
+    endpoint: Url,
+    api_key: Option<String>,
+    model: String,
+    system_prompt: String,
+    temperature: f32,
+}
+
+impl LlmPostProcessor {
+    /// Builds the processor, or `None` when post-processing is disabled.
+    pub fn from_config(config: &PostProcessingConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let endpoint = if is_absolute_endpoint(&config.endpoint) {
+            resolve_endpoint(None, &config.endpoint)?
+        } else {
+            let base_url = config.base_url.resolve("post_processing.base_url")?;
+            resolve_endpoint(Some(&base_url), &config.endpoint)?
+        };
+        let api_key = config.api_key.resolve("post_processing.api_key")?;
+
+        let client = Client::builder()
+            .user_agent("hyprwhspr-rs (post-processing)")
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .context("Failed to build post-processing HTTP client")?;
+
+        Ok(Some(Self {
+            client,
+            endpoint,
+            api_key,
+            model: config.model.clone(),
+            system_prompt: config.system_prompt.clone(),
+            temperature: config.temperature,
+        }))
+    }
+
+    /// Returns the processed text, or `text` unchanged when the request
+    /// fails, times out, comes back empty or loses a snippet marker.
+    pub async fn process(&self, text: &str) -> String {
+        match self.request(text).await {
+            Ok(processed) if markers(&processed) != markers(text) => {
+                warn!("Post-processing rewrote a snippet, injecting unprocessed text");
+                text.to_string()
+            }
+            Ok(processed) => {
+                debug!("🤖 Post-processed transcription: \"{}\"", processed);
+                processed
+            }
+            Err(err) => {
+                warn!("Post-processing failed, injecting unprocessed text: {err:#}");
+                text.to_string()
+            }
+        }
+    }
+
+    async fn request(&self, text: &str) -> Result<String> {
+        let mut messages = Vec::new();
+        if !self.system_prompt.trim().is_empty() {
+            messages.push(json!({ "role": "system", "content": self.system_prompt }));
+        }
+        messages.push(json!({ "role": "user", "content": text }));
+
+        let mut body = json!({
+            "messages": messages,
+            "temperature": self.temperature,
+        });
+        if !self.model.is_empty() {
+            body["model"] = Value::String(self.model.clone());
+        }
+
+        let mut request = self.client.post(self.endpoint.clone()).json(&body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Post-processing request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Post-processing endpoint returned {status}: {body}"
+            ));
+        }
+
+        let payload: Value = response
+            .json()
+            .await
+            .context("Post-processing response was not JSON")?;
+        let content = payload
+            .pointer("/choices/0/message/content")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        if content.is_empty() {
+            return Err(anyhow!("Post-processing response had no message content"));
+        }
+
+        Ok(content.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{SecretSource, ValueSource};
+    use crate::test_http::{read_request, write_response};
+    use tokio::net::TcpListener;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn sends_prompt_and_returns_completion() {
+        let (base_url, request) = spawn_stub(
+            "200 OK",
+            r#"{"choices":[{"message":{"role":"assistant","content":" Fixed text. "}}]}"#,
+            Duration::ZERO,
+        )
+        .await;
+        let processor = processor(&base_url, 2000);
+
+        assert_eq!(processor.process("um fixed text").await, "Fixed text.");
+
+        let (headers, body) = request.await.expect("request");
+        assert!(headers.starts_with("POST /v1/chat/completions"));
+        assert!(headers
+            .to_lowercase()
+            .contains("authorization: bearer test-key"));
+        let body: Value = serde_json::from_slice(&body).expect("json body");
+        assert_eq!(body["model"], "cleanup");
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "um fixed text");
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_input_on_error_status() {
+        let (base_url, _request) = spawn_stub(
+            "500 Internal Server Error",
+            r#"{"error":"boom"}"#,
+            Duration::ZERO,
+        )
+        .await;
+        let processor = processor(&base_url, 2000);
+
+        assert_eq!(processor.process("keep me").await, "keep me");
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_input_on_timeout() {
+        let (base_url, _request) = spawn_stub(
+            "200 OK",
+            r#"{"choices":[{"message":{"content":"too late"}}]}"#,
+            Duration::from_millis(500),
+        )
+        .await;
+        let processor = processor(&base_url, 50);
+
+        assert_eq!(processor.process("keep me").await, "keep me");
+    }
+
+    #[tokio::test]
+    async fn keeps_snippet_markers_or_falls_back_to_input() {
+        let kept = "Sign off with \u{F0000}";
+        let (base_url, _request) = spawn_stub(
+            "200 OK",
+            r#"{"choices":[{"message":{"content":"Sign off with \udb80\udc00."}}]}"#,
+            Duration::ZERO,
+        )
+        .await;
+        let keeps = processor(&base_url, 2000);
+        assert_eq!(keeps.process(kept).await, format!("{kept}."));
+
+        let (base_url, _request) = spawn_stub(
+            "200 OK",
+            r#"{"choices":[{"message":{"content":"Sign off with signature."}}]}"#,
+            Duration::ZERO,
+        )
+        .await;
+        let drops = processor(&base_url, 2000);
+        assert_eq!(drops.process(kept).await, kept);
+    }
+
+    #[test]
+    fn disabled_config_builds_nothing() {
+        let processor =
+            LlmPostProcessor::from_config(&PostProcessingConfig::default()).expect("config");
+        assert!(processor.is_none());
+    }
+
+    fn processor(base_url: &str, timeout_ms: u64) -> LlmPostProcessor {
+        std::env::set_var("HYPRWHSPR_TEST_POST_PROCESSING_KEY", "test-key");
+        let config = PostProcessingConfig {
+            enabled: true,
+            base_url: ValueSource {
+                env: None,
+                value: Some(base_url.to_string()),
+            },
+            model: "cleanup".to_string(),
+            api_key: SecretSource {
+                env: Some("HYPRWHSPR_TEST_POST_PROCESSING_KEY".to_string()),
+                ..SecretSource::default()
+            },
+            timeout_ms,
+            ..PostProcessingConfig::default()
+        };
+        LlmPostProcessor::from_config(&config)
+            .expect("config")
+            .expect("enabled")
+    }
+
+    /// Serves one request, replying after `delay`, and hands back its headers
+    /// and body.
+    async fn spawn_stub(
+        status: &'static str,
+        body: &'static str,
+        delay: Duration,
+    ) -> (String, oneshot::Receiver<(String, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test server");
+        let addr = listener.local_addr().expect("local addr");
+        let (tx, rx) = oneshot::channel();
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept request");
+            let Ok(request) = read_request(&mut stream).await else {
+                return;
+            };
+            let _ = tx.send(request);
+
+            tokio::time::sleep(delay).await;
+            let _ = write_response(&mut stream, status, body.as_bytes()).await;
+        });
+
+        (format!("http://{addr}"), rx)
+    }
+}
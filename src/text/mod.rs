@@ -1,4 +1,16 @@
 mod cleanup;
+mod commands;
+mod disfluency;
+mod editing;
+mod itn;
+mod llm;
 mod normalize;
+mod snippets;
+mod spacing;
+mod tokens;
 
-pub use normalize::NormalizeTextService;
+pub use editing::{EditCommand, EditPlan};
+pub use itn::{ItnEngine, ItnOutput};
+pub use llm::LlmPostProcessor;
+pub use normalize::{normalize_step_names, NormalizeRule, NormalizeTextService};
+pub use spacing::InjectionContext;
@@ -2,89 +2,42 @@ use super::cleanup::{
     capitalize_after_period, clean_control_artifacts, collapse_spaces, collapse_underscore_spacing,
     merge_separated_identical_symbols, normalize_line_breaks, trim_spaces_around_newlines,
 };
+use super::commands::{apply_spoken_commands, strip_literal_markers};
+use super::disfluency::DisfluencyFilter;
+use super::editing::EditCommand;
+use super::itn::{build_itn_engine, ItnEngine, TextProcessingItnEngine};
+use super::snippets::Snippets;
+use super::spacing::{InjectionContext, SmartSpacing};
+use crate::config::{Config, TextRuleConfig, TextRulePosition};
 use crate::logging::{PipelineStepRecord, TextPipelineRecord, record_text_pipeline};
+use anyhow::{bail, Context, Result};
 use regex::Regex;
 use std::collections::HashMap;
-use std::sync::{Arc, LazyLock, Mutex};
-use text_processing_rs::{NormalizeOptions, custom_rules, normalize_sentence_with_options};
-
-static CUSTOM_RULES_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+use std::sync::Arc;
 
+/// A `word_overrides` entry; ITN engines may apply these as custom rules.
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct NormalizeRule {
-    spoken: String,
-    written: String,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct ItnOutput {
-    text: String,
-    rule_count: usize,
-}
-
-trait ItnEngine: std::fmt::Debug + Send + Sync {
-    fn normalize(&self, text: &str, overrides: &[NormalizeRule]) -> ItnOutput;
+pub struct NormalizeRule {
+    pub spoken: String,
+    pub written: String,
 }
 
 #[derive(Debug, Clone)]
-struct TextProcessingItnEngine {
-    options: NormalizeOptions,
+struct TextRule {
+    name: String,
+    regex: Regex,
+    replacement: String,
+    position: TextRulePosition,
 }
 
-const APP_NORMALIZATION_RULES: &[(&str, &str)] = &[
-    ("dash dash", "--"),
-    ("dash", "-"),
-    ("hyphen", "-"),
-    ("underscore", "_"),
-    ("under score", "_"),
-    ("open paren", "("),
-    ("open parenthesis", "("),
-    ("open parentheses", "("),
-    ("close paren", ")"),
-    ("close parenthesis", ")"),
-    ("close parentheses", ")"),
-    ("open bracket", "["),
-    ("close bracket", "]"),
-    ("open brace", "{"),
-    ("close brace", "}"),
-    ("at symbol", "@"),
-    ("at sign", "@"),
-    ("hash", "#"),
-    ("hash tag", "#"),
-    ("hashtag", "#"),
-    ("pound", "#"),
-    ("dollar sign", "$"),
-    ("percent", "%"),
-    ("caret", "^"),
-    ("ampersand", "&"),
-    ("asterisk", "*"),
-    ("plus", "+"),
-    ("equals", "="),
-    ("equal", "="),
-    ("less than", "<"),
-    ("greater than", ">"),
-    ("slash", "/"),
-    ("backslash", "\\"),
-    ("pipe", "|"),
-    ("tilde", "~"),
-    ("grave", "`"),
-    ("open quote", "\""),
-    ("close quote", "\""),
-    ("open double quote", "\""),
-    ("close double quote", "\""),
-    ("quote", "\""),
-    ("double quote", "\""),
-    ("open single quote", "'"),
-    ("close single quote", "'"),
-    ("apostrophe", "'"),
-    ("single quote", "'"),
-];
-
 const CONTROL_COMMAND_RULES: &[(&str, &str)] =
     &[("new line", "\n"), ("newline", "\n"), ("tab", "\t")];
 
 const NORMALIZE_PIPELINE: &[NormalizeStep] = &[
     NormalizeStep::LineBreaks,
+    NormalizeStep::RemoveDisfluencies,
+    NormalizeStep::Snippets,
+    NormalizeStep::SpokenCommands,
     NormalizeStep::InverseTextNormalization,
     NormalizeStep::ControlCommands,
     NormalizeStep::ControlArtifactCleanup,
@@ -100,6 +53,9 @@ const NORMALIZE_PIPELINE: &[NormalizeStep] = &[
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum NormalizeStep {
     LineBreaks,
+    RemoveDisfluencies,
+    Snippets,
+    SpokenCommands,
     InverseTextNormalization,
     ControlCommands,
     ControlArtifactCleanup,
@@ -120,25 +76,141 @@ struct StepOutput {
 #[derive(Debug, Clone)]
 pub struct NormalizeTextService {
     overrides: Vec<NormalizeRule>,
+    pipeline: Vec<NormalizeStep>,
+    text_rules: Vec<TextRule>,
+    disfluencies: Option<DisfluencyFilter>,
+    snippets: Snippets,
+    smart_spacing: SmartSpacing,
+    edit_commands: bool,
     itn: Arc<dyn ItnEngine>,
 }
 
 impl NormalizeTextService {
     pub fn new(word_overrides: HashMap<String, String>) -> Self {
-        Self {
-            overrides: sorted_overrides(word_overrides),
-            itn: Arc::new(TextProcessingItnEngine::default()),
+        Self::with_itn_engine(word_overrides, Arc::new(TextProcessingItnEngine::default()))
+    }
+
+    /// Builds the service from `word_overrides`, `vocabulary`, `text_rules`,
+    /// `snippets`, `normalization` and `smart_spacing`, failing on a rule
+    /// whose pattern does not compile, an empty snippet trigger, an unknown
+    /// step name, an enabled step left out of `steps` or an ITN command
+    /// engine without a command.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let normalization = &config.normalization;
+        let itn = build_itn_engine(&normalization.itn)?;
+        let mut service = Self::with_itn_engine(config.effective_word_overrides(), itn);
+        service.pipeline = match &normalization.steps {
+            Some(steps) => steps
+                .iter()
+                .map(|name| {
+                    NormalizeStep::from_name(name)
+                        .with_context(|| format!("Unknown normalization step '{name}'"))
+                })
+                .collect::<Result<_>>()?,
+            None => NORMALIZE_PIPELINE.to_vec(),
+        };
+        for (enabled, step, setting) in [
+            (
+                normalization.disfluencies.enabled,
+                NormalizeStep::RemoveDisfluencies,
+                "disfluencies",
+            ),
+            (
+                normalization.spoken_commands.enabled,
+                NormalizeStep::SpokenCommands,
+                "spoken_commands",
+            ),
+        ] {
+            if enabled && !service.pipeline.contains(&step) {
+                bail!(
+                    "normalization.{setting} is enabled but normalization.steps leaves out '{}'",
+                    step.name()
+                );
+            }
+        }
+        if normalization.disfluencies.enabled {
+            service.disfluencies = Some(DisfluencyFilter::new(&normalization.disfluencies.fillers));
+        } else {
+            service
+                .pipeline
+                .retain(|step| *step != NormalizeStep::RemoveDisfluencies);
+        }
+        if !normalization.spoken_commands.enabled {
+            service
+                .pipeline
+                .retain(|step| *step != NormalizeStep::SpokenCommands);
         }
+        // Both steps split on whitespace and rejoin with spaces, which would
+        // flatten the newlines and tabs `control_commands` inserts.
+        if let Some(control) = service
+            .pipeline
+            .iter()
+            .position(|step| *step == NormalizeStep::ControlCommands)
+        {
+            if let Some(step) = service.pipeline[control..].iter().find(|step| {
+                matches!(
+                    step,
+                    NormalizeStep::RemoveDisfluencies | NormalizeStep::SpokenCommands
+                )
+            }) {
+                bail!(
+                    "normalization.steps lists '{}' after '{}', which would turn the newlines and tabs it inserts into spaces",
+                    step.name(),
+                    NormalizeStep::ControlCommands.name()
+                );
+            }
+        }
+        service.edit_commands = normalization.edit_commands.enabled;
+        service.text_rules = compile_text_rules(&config.text_rules)?;
+        service.snippets = Snippets::new(&config.snippets)?;
+        service.smart_spacing = SmartSpacing::new(
+            config.smart_spacing.enabled,
+            config.smart_spacing.drop_trailing_period.clone(),
+        );
+        Ok(service)
     }
 
-    #[cfg(test)]
-    fn with_itn_engine(word_overrides: HashMap<String, String>, itn: Arc<dyn ItnEngine>) -> Self {
+    /// Like [`NormalizeTextService::new`], with a caller-supplied ITN engine.
+    pub fn with_itn_engine(
+        word_overrides: HashMap<String, String>,
+        itn: Arc<dyn ItnEngine>,
+    ) -> Self {
         Self {
             overrides: sorted_overrides(word_overrides),
+            pipeline: NORMALIZE_PIPELINE
+                .iter()
+                .copied()
+                .filter(|step| {
+                    !matches!(
+                        step,
+                        NormalizeStep::RemoveDisfluencies | NormalizeStep::SpokenCommands
+                    )
+                })
+                .collect(),
+            text_rules: Vec::new(),
+            disfluencies: None,
+            snippets: Snippets::default(),
+            smart_spacing: SmartSpacing::default(),
+            edit_commands: false,
             itn,
         }
     }
 
+    /// Runs [`NormalizeTextService::normalize`] on the blocking thread pool,
+    /// so an external ITN command waiting on its timeout does not hold up the
+    /// async runtime.
+    pub async fn normalize_async(&self, text: &str) -> String {
+        let service = self.clone();
+        let input = text.to_string();
+        match tokio::task::spawn_blocking(move || service.normalize(&input)).await {
+            Ok(normalized) => normalized,
+            Err(err) => {
+                tracing::warn!("Text normalization task failed; keeping raw text: {err}");
+                text.to_string()
+            }
+        }
+    }
+
     pub fn normalize(&self, text: &str) -> String {
         let mut logged_steps = if tracing::level_enabled!(tracing::Level::DEBUG) {
             Some(Vec::new())
@@ -147,19 +219,51 @@ impl NormalizeTextService {
         };
         let mut current = text.to_string();
 
-        for step in NORMALIZE_PIPELINE {
+        // `before_itn` rules see the words as spoken, so they go ahead of the
+        // first step that rewrites them. Rules anchored on a disabled step run
+        // at the start instead.
+        let before_itn_at = self.pipeline.iter().position(|step| {
+            matches!(
+                step,
+                NormalizeStep::SpokenCommands | NormalizeStep::InverseTextNormalization
+            )
+        });
+        let control_commands_at = self
+            .pipeline
+            .iter()
+            .position(|step| *step == NormalizeStep::ControlCommands);
+        if before_itn_at.is_none() {
+            current =
+                self.apply_text_rules(TextRulePosition::BeforeItn, current, &mut logged_steps);
+        }
+        if control_commands_at.is_none() {
+            current = self.apply_text_rules(
+                TextRulePosition::AfterControlCommands,
+                current,
+                &mut logged_steps,
+            );
+        }
+
+        for (index, step) in self.pipeline.iter().enumerate() {
+            if before_itn_at == Some(index) {
+                current =
+                    self.apply_text_rules(TextRulePosition::BeforeItn, current, &mut logged_steps);
+            }
+
             let before = current;
             let output = step.apply(self, &before);
-            if let Some(ref mut steps) = logged_steps {
-                steps.push(PipelineStepRecord::new(
-                    step.name(),
-                    before.clone(),
-                    output.text.clone(),
-                    output.change_count,
-                ));
+            current = record_step(&mut logged_steps, step.name(), before, output);
+
+            if control_commands_at == Some(index) {
+                current = self.apply_text_rules(
+                    TextRulePosition::AfterControlCommands,
+                    current,
+                    &mut logged_steps,
+                );
             }
-            current = output.text;
         }
+        current = strip_literal_markers(&current);
+        current = self.apply_text_rules(TextRulePosition::Last, current, &mut logged_steps);
 
         if let Some(logged_steps) = logged_steps {
             record_text_pipeline(TextPipelineRecord::new(
@@ -172,6 +276,78 @@ impl NormalizeTextService {
         current
     }
 
+    /// Recognizes an utterance that edits the last dictation ("scratch that",
+    /// "undo last", "replace X with Y") rather than being typed, when
+    /// `normalization.edit_commands` is enabled. Both sides of a replace are
+    /// normalized like dictation, so they match and restore the injected
+    /// spelling ("twenty" finds "20", overrides keep their capitals).
+    pub async fn edit_command(&self, text: &str) -> Option<EditCommand> {
+        if !self.edit_commands {
+            return None;
+        }
+        match EditCommand::parse(text)? {
+            EditCommand::Replace { from, to } => Some(EditCommand::Replace {
+                from: self.normalize_fragment_async(&from).await,
+                to: self.normalize_fragment_async(&to).await,
+            }),
+            command => Some(command),
+        }
+    }
+
+    /// [`NormalizeTextService::normalize_async`] for words out of the middle
+    /// of a sentence, whose first letter is not capitalized.
+    async fn normalize_fragment_async(&self, text: &str) -> String {
+        let mut fragment = self.clone();
+        fragment
+            .pipeline
+            .retain(|step| *step != NormalizeStep::CapitalizeAfterPeriod);
+        let normalized = fragment.normalize_async(text).await;
+        self.expand_snippets(&normalized)
+    }
+
+    /// Fits normalized text onto what was last injected into the focused
+    /// window. `raw` is the transcript `text` was normalized from. Override
+    /// and vocabulary spellings keep their case.
+    pub fn fit_to_context(&self, text: &str, raw: &str, context: &InjectionContext) -> String {
+        let protected = self
+            .overrides
+            .iter()
+            .map(|rule| rule.written.as_str())
+            .collect::<Vec<_>>();
+        self.smart_spacing.apply(text, raw, context, &protected)
+    }
+
+    /// Renders the snippets that [`Self::normalize`] left as markers. Runs
+    /// last, right before injection, so neither post-processing nor smart
+    /// spacing rewrites snippet text.
+    pub fn expand_snippets(&self, text: &str) -> String {
+        self.snippets.expand_markers(text)
+    }
+
+    pub fn smart_spacing_enabled(&self) -> bool {
+        self.smart_spacing.enabled
+    }
+
+    fn apply_text_rules(
+        &self,
+        position: TextRulePosition,
+        mut current: String,
+        logged_steps: &mut Option<Vec<PipelineStepRecord>>,
+    ) -> String {
+        for rule in self
+            .text_rules
+            .iter()
+            .filter(|rule| rule.position == position)
+        {
+            let before = current;
+            let mut text = before.clone();
+            let count = replace_with_regex(&mut text, &rule.regex, &rule.replacement);
+            let output = StepOutput::with_count(text, count);
+            current = record_step(logged_steps, &rule.name, before, output);
+        }
+        current
+    }
+
     fn apply_overrides(&self, text: &str) -> (String, usize) {
         let mut result = text.to_string();
         let mut count = 0;
@@ -185,17 +361,29 @@ impl NormalizeTextService {
 
         (result, count)
     }
+}
 
-    #[cfg(test)]
-    fn pipeline_step_names() -> Vec<&'static str> {
-        NORMALIZE_PIPELINE.iter().map(|step| step.name()).collect()
-    }
+/// Names of the built-in normalization steps, in default pipeline order.
+/// `remove_disfluencies` and `spoken_commands` only run when
+/// `normalization.disfluencies` and `normalization.spoken_commands` are enabled.
+pub fn normalize_step_names() -> Vec<&'static str> {
+    NORMALIZE_PIPELINE.iter().map(|step| step.name()).collect()
 }
 
 impl NormalizeStep {
+    fn from_name(name: &str) -> Option<Self> {
+        NORMALIZE_PIPELINE
+            .iter()
+            .copied()
+            .find(|step| step.name() == name)
+    }
+
     fn name(self) -> &'static str {
         match self {
             Self::LineBreaks => "normalize_line_breaks",
+            Self::RemoveDisfluencies => "remove_disfluencies",
+            Self::Snippets => "expand_snippets",
+            Self::SpokenCommands => "spoken_commands",
             Self::InverseTextNormalization => "inverse_text_normalization",
             Self::ControlCommands => "control_commands",
             Self::ControlArtifactCleanup => "control_artifact_cleanup",
@@ -212,6 +400,21 @@ impl NormalizeStep {
     fn apply(self, service: &NormalizeTextService, text: &str) -> StepOutput {
         match self {
             Self::LineBreaks => StepOutput::without_count(normalize_line_breaks(text)),
+            Self::RemoveDisfluencies => match &service.disfluencies {
+                Some(filter) => {
+                    let (text, count) = filter.apply(text);
+                    StepOutput::with_count(text, count)
+                }
+                None => StepOutput::without_count(text.to_string()),
+            },
+            Self::Snippets => {
+                let (text, count) = service.snippets.mark_triggers(text);
+                StepOutput::with_count(text, count)
+            }
+            Self::SpokenCommands => {
+                let (text, count) = apply_spoken_commands(text);
+                StepOutput::with_count(text, count)
+            }
             Self::InverseTextNormalization => {
                 let output = service.itn.normalize(text, &service.overrides);
                 StepOutput::with_count(output.text, output.rule_count)
@@ -265,38 +468,56 @@ impl StepOutput {
     }
 }
 
-impl Default for TextProcessingItnEngine {
-    fn default() -> Self {
-        Self {
-            options: NormalizeOptions::new().with_disable_bare_second(true),
-        }
+fn record_step(
+    logged_steps: &mut Option<Vec<PipelineStepRecord>>,
+    name: &str,
+    before: String,
+    output: StepOutput,
+) -> String {
+    if let Some(steps) = logged_steps {
+        steps.push(PipelineStepRecord::new(
+            name,
+            before,
+            output.text.clone(),
+            output.change_count,
+        ));
     }
+    output.text
 }
 
-impl ItnEngine for TextProcessingItnEngine {
-    fn normalize(&self, text: &str, overrides: &[NormalizeRule]) -> ItnOutput {
-        let _guard = CUSTOM_RULES_LOCK
-            .lock()
-            .expect("text-processing-rs custom rules lock poisoned");
-
-        custom_rules::clear_rules();
-        for (spoken, written) in APP_NORMALIZATION_RULES {
-            custom_rules::add_rule(spoken, written);
-        }
-        for rule in overrides {
-            custom_rules::add_rule(&rule.spoken, &rule.written);
-        }
-
-        let result = normalize_sentence_with_options(text, self.options);
-        let rule_count = custom_rules::rule_count();
-        custom_rules::clear_rules();
-
-        ItnOutput {
-            text: result,
-            rule_count,
-        }
-    }
+fn compile_text_rules(rules: &[TextRuleConfig]) -> Result<Vec<TextRule>> {
+    rules
+        .iter()
+        .enumerate()
+        .map(|(index, rule)| {
+            let label = if rule.name.trim().is_empty() {
+                (index + 1).to_string()
+            } else {
+                rule.name.trim().to_string()
+            };
+            if rule.pattern.is_empty() {
+                bail!("Text rule '{label}' has an empty pattern");
+            }
+            let mut pattern = rule.pattern.clone();
+            if rule.whole_word {
+                pattern = format!(r"\b(?:{pattern})\b");
+            }
+            if !rule.case_sensitive {
+                pattern = format!("(?i){pattern}");
+            }
+            let regex = Regex::new(&pattern)
+                .with_context(|| format!("Invalid pattern in text rule '{label}'"))?;
+
+            Ok(TextRule {
+                name: format!("text_rule:{label}"),
+                regex,
+                replacement: rule.replacement.clone(),
+                position: rule.position,
+            })
+        })
+        .collect()
 }
+
 fn sorted_overrides(overrides: HashMap<String, String>) -> Vec<NormalizeRule> {
     let mut rules = overrides
         .into_iter()
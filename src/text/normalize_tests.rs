@@ -1,4 +1,7 @@
 use super::*;
+use crate::config::{ItnEngineKind, SnippetConfig, VocabularyTerm};
+use crate::text::snippets::render_template;
+use crate::text::{InjectionContext, ItnOutput};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -11,6 +14,13 @@ fn service(overrides: &[(&str, &str)]) -> NormalizeTextService {
     )
 }
 
+/// A service built from the default config after `configure` ran on it.
+fn service_with(configure: impl FnOnce(&mut Config)) -> NormalizeTextService {
+    let mut config = Config::default();
+    configure(&mut config);
+    NormalizeTextService::from_config(&config).expect("valid config")
+}
+
 #[derive(Debug)]
 struct FakeItnEngine {
     output: &'static str,
@@ -118,9 +128,12 @@ fn fake_itn_engine_exercises_cleanup_and_override_order_without_globals() {
 #[test]
 fn normalization_pipeline_order_is_explicit() {
     assert_eq!(
-        NormalizeTextService::pipeline_step_names(),
+        normalize_step_names(),
         vec![
             "normalize_line_breaks",
+            "remove_disfluencies",
+            "expand_snippets",
+            "spoken_commands",
             "inverse_text_normalization",
             "control_commands",
             "control_artifact_cleanup",
@@ -170,3 +183,558 @@ fn merge_identical_symbols_collapses_spaced_pairs() {
     assert_eq!(merged, "77 -- go and __ done");
     assert_eq!(count, 2);
 }
+
+fn rule(pattern: &str, replacement: &str, position: TextRulePosition) -> TextRuleConfig {
+    TextRuleConfig {
+        pattern: pattern.to_string(),
+        replacement: replacement.to_string(),
+        position,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn text_rules_support_capture_groups_case_and_word_boundaries() {
+    let normalizer = service_with(|config| {
+        config.text_rules = vec![
+            TextRuleConfig {
+                whole_word: true,
+                ..rule(r"ticket (\w+)", "#$1", TextRulePosition::Last)
+            },
+            TextRuleConfig {
+                case_sensitive: true,
+                ..rule("Foo", "Bar", TextRulePosition::Last)
+            },
+            TextRuleConfig {
+                whole_word: true,
+                ..rule("cat", "dog", TextRulePosition::Last)
+            },
+        ]
+    });
+
+    assert_eq!(normalizer.normalize("see TICKET abc"), "See #abc");
+    assert_eq!(normalizer.normalize("say foo and Foo"), "Say foo and Bar");
+    assert_eq!(normalizer.normalize("cat concatenate"), "dog concatenate");
+}
+
+#[test]
+fn text_rule_positions_run_around_builtin_steps() {
+    // Before ITN the currency word is still spoken, so ITN no longer sees money.
+    let before_itn = service_with(|config| {
+        config.text_rules = vec![rule("dollars", "bucks", TextRulePosition::BeforeItn)]
+    });
+    assert_eq!(before_itn.normalize("two hundred dollars"), "200 bucks");
+
+    // After control commands the newline exists, and capitalization still follows.
+    let after_control = service_with(|config| {
+        config.text_rules = vec![rule(r"\n", " NL ", TextRulePosition::AfterControlCommands)]
+    });
+    assert_eq!(
+        after_control.normalize("hello new line world"),
+        "Hello NL world"
+    );
+
+    // Last sees the fully normalized text and nothing touches it afterwards.
+    let last = service_with(|config| {
+        config.text_rules = vec![rule("^Hello", "hello", TextRulePosition::Last)]
+    });
+    assert_eq!(last.normalize("hello world"), "hello world");
+}
+
+#[test]
+fn invalid_or_empty_text_rule_patterns_are_rejected() {
+    let mut config = Config::default();
+    config.text_rules = vec![TextRuleConfig {
+        name: "broken".to_string(),
+        ..rule("(unclosed", "", TextRulePosition::Last)
+    }];
+    let err = NormalizeTextService::from_config(&config).unwrap_err();
+    assert!(format!("{err:#}").contains("text rule 'broken'"));
+
+    config.text_rules = vec![rule("", "x", TextRulePosition::Last)];
+    assert!(NormalizeTextService::from_config(&config).is_err());
+}
+
+#[test]
+fn configured_steps_disable_and_reorder_the_pipeline() {
+    // Without ITN and capitalization, lowercase chat stays as spoken.
+    let chat = service_with(|config| {
+        config.normalization.steps = Some(vec![
+            "collapse_spaces".to_string(),
+            "trim_whitespace".to_string(),
+        ])
+    });
+    assert_eq!(
+        chat.normalize("  two hundred  dollars. ok "),
+        "two hundred dollars. ok"
+    );
+
+    // Overrides ahead of capitalization get capitalized like any other word.
+    let mut config = Config::default();
+    config
+        .word_overrides
+        .insert("hyperland".to_string(), "hyprland".to_string());
+    config.normalization.steps = Some(vec![
+        "word_overrides".to_string(),
+        "capitalize_after_period".to_string(),
+    ]);
+    let reordered = NormalizeTextService::from_config(&config).expect("valid config");
+    assert_eq!(reordered.normalize("done. hyperland"), "Done. Hyprland");
+
+    let mut config = Config::default();
+    config.normalization.steps = Some(vec!["shout".to_string()]);
+    assert!(NormalizeTextService::from_config(&config).is_err());
+}
+
+#[test]
+fn enabled_steps_must_be_listed_in_configured_steps() {
+    let mut config = Config::default();
+    config.normalization.steps = Some(vec!["collapse_spaces".to_string()]);
+    config.normalization.disfluencies.enabled = true;
+    let err = NormalizeTextService::from_config(&config).unwrap_err();
+    assert!(err.to_string().contains("remove_disfluencies"));
+
+    config.normalization.disfluencies.enabled = false;
+    config.normalization.spoken_commands.enabled = true;
+    let err = NormalizeTextService::from_config(&config).unwrap_err();
+    assert!(err.to_string().contains("spoken_commands"));
+
+    config
+        .normalization
+        .steps
+        .as_mut()
+        .unwrap()
+        .push("spoken_commands".to_string());
+    assert!(NormalizeTextService::from_config(&config).is_ok());
+}
+
+#[test]
+fn enabled_tokenizing_steps_must_run_before_control_commands() {
+    let mut config = Config::default();
+    config.normalization.steps = Some(vec![
+        "control_commands".to_string(),
+        "spoken_commands".to_string(),
+    ]);
+    // Disabled steps are dropped from the pipeline, so their position is moot.
+    assert!(NormalizeTextService::from_config(&config).is_ok());
+
+    config.normalization.spoken_commands.enabled = true;
+    let err = NormalizeTextService::from_config(&config).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("'spoken_commands' after 'control_commands'"));
+
+    config.normalization.spoken_commands.enabled = false;
+    config.normalization.disfluencies.enabled = true;
+    config.normalization.steps = Some(vec![
+        "control_commands".to_string(),
+        "remove_disfluencies".to_string(),
+    ]);
+    let err = NormalizeTextService::from_config(&config).unwrap_err();
+    assert!(err.to_string().contains("'remove_disfluencies' after"));
+
+    config.normalization.steps = Some(vec![
+        "remove_disfluencies".to_string(),
+        "control_commands".to_string(),
+    ]);
+    let normalizer = NormalizeTextService::from_config(&config).expect("valid config");
+    assert_eq!(normalizer.normalize("um one new line two"), "one \n two");
+}
+
+#[test]
+fn text_rules_anchored_on_disabled_steps_run_first() {
+    let normalizer = service_with(|config| {
+        config.normalization.steps = Some(vec!["trim_whitespace".to_string()]);
+        config.text_rules = vec![
+            rule("b", "c", TextRulePosition::AfterControlCommands),
+            rule("a", "b", TextRulePosition::BeforeItn),
+        ];
+    });
+    assert_eq!(normalizer.normalize("a"), "c");
+}
+
+#[test]
+fn spoken_punctuation_replaces_guessed_punctuation() {
+    let normalizer = service_with(|config| config.normalization.spoken_commands.enabled = true);
+
+    assert_eq!(
+        normalizer.normalize("is it done question mark yes, exclamation mark"),
+        "Is it done? Yes!"
+    );
+    assert_eq!(
+        normalizer.normalize("he said open quote, hi, close quote. and left full stop"),
+        "He said \"hi\" and left."
+    );
+    assert_eq!(
+        normalizer.normalize("items colon a semicolon b"),
+        "Items: a; b"
+    );
+}
+
+#[test]
+fn formatting_commands_shape_following_words() {
+    let normalizer = service_with(|config| config.normalization.spoken_commands.enabled = true);
+
+    assert_eq!(
+        normalizer.normalize("set camel case max retry count to three"),
+        "Set maxRetryCountToThree"
+    );
+    assert_eq!(
+        normalizer.normalize("call snake case parse config, then stop"),
+        "Call parse_config, then stop"
+    );
+    assert_eq!(
+        normalizer.normalize("this is all caps very important end caps ok"),
+        "This is VERY IMPORTANT ok"
+    );
+    assert_eq!(normalizer.normalize("pass no space word"), "Password");
+
+    // Commands are plain words until the step is enabled.
+    assert_eq!(
+        service(&[]).normalize("set camel case max retry count"),
+        "Set camel case max retry count"
+    );
+}
+
+#[test]
+fn literal_escapes_a_spoken_command() {
+    let normalizer = service_with(|config| config.normalization.spoken_commands.enabled = true);
+
+    assert_eq!(
+        normalizer.normalize("the trial literal period ended period"),
+        "The trial period ended."
+    );
+    assert_eq!(
+        normalizer.normalize("say literal question mark and literal new line"),
+        "Say question mark and new line"
+    );
+    assert_eq!(normalizer.normalize("read the literal"), "Read the literal");
+}
+
+#[test]
+fn spoken_commands_count_applied_commands() {
+    let (text, count) = apply_spoken_commands("a comma b literal comma c no space d");
+    assert_eq!(strip_literal_markers(&text), "a, b comma cd");
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn itn_engine_is_selected_from_config() {
+    let mut config = Config::default();
+    config.normalization.itn.engine = ItnEngineKind::None;
+    let disabled = NormalizeTextService::from_config(&config).expect("valid config");
+    assert_eq!(
+        disabled.normalize("two hundred dollars"),
+        "Two hundred dollars"
+    );
+
+    config.normalization.itn.engine = ItnEngineKind::Command;
+    assert!(NormalizeTextService::from_config(&config).is_err());
+
+    config.normalization.itn.command = vec!["tr".to_string(), "a-z".to_string(), "A-Z".to_string()];
+    let external = NormalizeTextService::from_config(&config).expect("valid config");
+    assert_eq!(external.normalize("zwei euro"), "ZWEI EURO");
+}
+
+#[tokio::test]
+async fn external_itn_command_runs_off_the_async_runtime() {
+    let external = service_with(|config| {
+        config.normalization.itn.engine = ItnEngineKind::Command;
+        config.normalization.itn.command =
+            vec!["tr".to_string(), "a-z".to_string(), "A-Z".to_string()];
+    });
+
+    assert_eq!(external.normalize_async("drei euro").await, "DREI EURO");
+}
+
+#[test]
+fn removes_fillers_repetitions_and_false_starts() {
+    let normalizer = service_with(|config| config.normalization.disfluencies.enabled = true);
+
+    assert_eq!(
+        normalizer.normalize("Um, I think, uh, the the build is green, you know."),
+        "I think, the build is green."
+    );
+    assert_eq!(
+        normalizer.normalize("We should, I mean we must ship it"),
+        "We must ship it"
+    );
+    assert_eq!(
+        normalizer.normalize("meet on Tuesday, I mean Wednesday"),
+        "Meet on Tuesday, I mean Wednesday"
+    );
+}
+
+#[test]
+fn disfluency_removal_keeps_phrases_and_repeats_that_belong_to_the_sentence() {
+    let normalizer = service_with(|config| config.normalization.disfluencies.enabled = true);
+
+    assert_eq!(
+        normalizer.normalize("Do you know the way?"),
+        "Do you know the way?"
+    );
+    assert_eq!(
+        normalizer.normalize("I said that that was fine"),
+        "I said that that was fine"
+    );
+    assert_eq!(
+        normalizer.normalize("It works, you know, most of the time"),
+        "It works, most of the time"
+    );
+    assert_eq!(
+        normalizer.normalize("You know, it had had enough"),
+        "It had had enough"
+    );
+}
+
+#[test]
+fn disfluency_removal_is_opt_in_with_configurable_fillers() {
+    assert_eq!(service(&[]).normalize("um the the plan"), "Um the the plan");
+
+    let (text, count) = DisfluencyFilter::new(&["like".to_string(), "sort of".to_string()])
+        .apply("it was like um done done, sort of");
+    assert_eq!(text, "it was um done");
+    assert_eq!(count, 3);
+
+    let normalizer = service_with(|config| {
+        config.normalization.disfluencies.enabled = true;
+        config.normalization.disfluencies.fillers = vec!["basically".to_string()];
+    });
+    assert_eq!(normalizer.normalize("basically um yes"), "Um yes");
+}
+
+#[test]
+fn vocabulary_terms_and_aliases_become_overrides() {
+    let mut config = Config::default();
+    config.vocabulary = vec![VocabularyTerm {
+        term: "Hyprland".to_string(),
+        aliases: vec!["hyper land".to_string()],
+    }];
+    config
+        .word_overrides
+        .insert("hyprland".to_string(), "hyprland".to_string());
+    config.normalization.steps = Some(vec!["word_overrides".to_string()]);
+
+    let overrides = config.effective_word_overrides();
+    assert_eq!(
+        overrides.get("hyper land").map(String::as_str),
+        Some("Hyprland")
+    );
+    // Explicit word_overrides keep priority over generated rules.
+    assert_eq!(
+        overrides.get("hyprland").map(String::as_str),
+        Some("hyprland")
+    );
+    assert_eq!(overrides.len(), 2);
+
+    config.word_overrides.clear();
+    let normalizer = NormalizeTextService::from_config(&config).expect("valid config");
+    assert_eq!(
+        normalizer.normalize("i run hyper land and HYPRLAND"),
+        "i run Hyprland and Hyprland"
+    );
+}
+
+fn context(class: &str, preceding: Option<&str>) -> InjectionContext {
+    InjectionContext {
+        window_class: Some(class.to_string()),
+        preceding: preceding.map(str::to_string),
+    }
+}
+
+#[test]
+fn smart_spacing_continues_the_previous_injection() {
+    let normalizer = service_with(|config| {
+        config.smart_spacing.enabled = true;
+        config.vocabulary = vec![VocabularyTerm {
+            term: "Hyprland".to_string(),
+            aliases: Vec::new(),
+        }];
+    });
+    let fit = |text: &str, raw: &str, preceding: Option<&str>| {
+        normalizer.fit_to_context(text, raw, &context("foot", preceding))
+    };
+
+    assert_eq!(fit("And then.", "and then", Some("Hello.")), " And then.");
+    assert_eq!(
+        fit("And then.", "um and then", Some("I said")),
+        " and then."
+    );
+    assert_eq!(
+        fit("Hyprland works.", "hyprland works", Some("I think")),
+        " Hyprland works."
+    );
+    assert_eq!(fit("I agree.", "i agree", Some("Well,")), " I agree.");
+    assert_eq!(fit(", right?", ", right?", Some("Done")), ", right?");
+    assert_eq!(
+        fit("Next line.", "next line", Some("First.\n")),
+        "Next line."
+    );
+    assert_eq!(fit("Fresh start.", "fresh start", None), "Fresh start.");
+}
+
+#[test]
+fn smart_spacing_lowercases_only_words_the_transcript_did_not_capitalize() {
+    let normalizer = service_with(|config| config.smart_spacing.enabled = true);
+    let fit = |text: &str, raw: &str, preceding: &str| {
+        normalizer.fit_to_context(text, raw, &context("foot", Some(preceding)))
+    };
+
+    assert_eq!(
+        fit("Monday works.", "Monday works.", "We could meet"),
+        " Monday works."
+    );
+    assert_eq!(
+        fit("Paris, I think.", "Paris, I think.", "The trip is to"),
+        " Paris, I think."
+    );
+    assert_eq!(
+        fit("Then we left.", "and, then we left", "We ate"),
+        " then we left."
+    );
+}
+
+#[test]
+fn smart_spacing_capitalizes_after_a_finished_sentence() {
+    let normalizer = service_with(|config| {
+        config.smart_spacing.enabled = true;
+        config.vocabulary = vec![VocabularyTerm {
+            term: "iPhone".to_string(),
+            aliases: Vec::new(),
+        }];
+    });
+    let fit = |text: &str, preceding: &str| {
+        normalizer.fit_to_context(text, text, &context("foot", Some(preceding)))
+    };
+
+    assert_eq!(fit("and then we left.", "We ate."), " And then we left.");
+    assert_eq!(fit("really?", "You did what?"), " Really?");
+    assert_eq!(fit("wow.", "It worked!"), " Wow.");
+    assert_eq!(fit("iPhone is charging.", "Done."), " iPhone is charging.");
+    assert_eq!(fit("and then.", "We ate"), " and then.");
+}
+
+#[test]
+fn smart_spacing_drops_trailing_period_for_chat_classes_and_is_off_by_default() {
+    let mut config = Config::default();
+    config.smart_spacing.enabled = true;
+    config.smart_spacing.drop_trailing_period = vec!["Slack".to_string()];
+    let normalizer = NormalizeTextService::from_config(&config).expect("valid config");
+
+    assert_eq!(
+        normalizer.fit_to_context(
+            "See you soon.",
+            "see you soon",
+            &context("com.slack.Slack", None)
+        ),
+        "See you soon"
+    );
+    assert_eq!(
+        normalizer.fit_to_context("Wait...", "wait", &context("slack", None)),
+        "Wait..."
+    );
+    assert_eq!(
+        normalizer.fit_to_context("See you soon.", "see you soon", &context("foot", None)),
+        "See you soon."
+    );
+
+    let disabled = NormalizeTextService::from_config(&Config::default()).expect("valid config");
+    assert_eq!(
+        disabled.fit_to_context("And then.", "and then", &context("slack", Some("Hello"))),
+        "And then."
+    );
+}
+
+#[test]
+fn snippets_expand_after_normalization_without_being_rewritten() {
+    let mut config = Config::default();
+    config.snippets.insert(
+        "insert signature".to_string(),
+        SnippetConfig::Text("--\nJane doe, two 2 three\nsent at {time}".to_string()),
+    );
+    let normalizer = NormalizeTextService::from_config(&config).expect("valid config");
+
+    let signed =
+        normalizer.expand_snippets(&normalizer.normalize("thanks for the help. Insert signature."));
+    let (body, tail) = signed.split_once("sent at ").expect("rendered template");
+    assert_eq!(body, "Thanks for the help. --\nJane doe, two 2 three\n");
+    assert_eq!(tail.len(), "12:34".len());
+    assert_eq!(
+        normalizer.normalize("insert the signature"),
+        "Insert the signature"
+    );
+
+    config
+        .snippets
+        .insert(" ".to_string(), SnippetConfig::Text("x".to_string()));
+    assert!(NormalizeTextService::from_config(&config).is_err());
+}
+
+#[test]
+fn smart_spacing_leaves_snippet_text_alone() {
+    let mut config = Config::default();
+    config.smart_spacing.enabled = true;
+    config.smart_spacing.drop_trailing_period = vec!["slack".to_string()];
+    config.snippets.insert(
+        "insert address".to_string(),
+        SnippetConfig::Text("Baker Street.".to_string()),
+    );
+    let normalizer = NormalizeTextService::from_config(&config).expect("valid config");
+
+    let marked = normalizer.fit_to_context(
+        &normalizer.normalize("Insert address."),
+        "insert address.",
+        &context("slack", Some("I live at")),
+    );
+    assert_eq!(normalizer.expand_snippets(&marked), " Baker Street.");
+}
+
+#[test]
+fn snippet_placeholders_render_date_and_time() {
+    let now = time::macros::datetime!(2026-03-07 09:05 UTC);
+    assert_eq!(
+        render_template("Standup {date} {time}: {date}", Some(now)),
+        "Standup 2026-03-07 09:05: 2026-03-07"
+    );
+}
+
+#[tokio::test]
+async fn edit_commands_are_opt_in() {
+    assert_eq!(service(&[]).edit_command("Scratch that.").await, None);
+
+    let normalizer = service_with(|config| config.normalization.edit_commands.enabled = true);
+    assert_eq!(
+        normalizer.edit_command("Scratch that.").await,
+        Some(EditCommand::ScratchThat)
+    );
+}
+
+#[tokio::test]
+async fn replace_commands_are_normalized_like_the_injected_text() {
+    let normalizer = service_with(|config| {
+        config.normalization.edit_commands.enabled = true;
+        config
+            .word_overrides
+            .insert("hyperland".to_string(), "Hyprland".to_string());
+    });
+
+    let override_command = normalizer
+        .edit_command("replace waybar with hyperland")
+        .await
+        .expect("replace command");
+    assert_eq!(
+        override_command,
+        EditCommand::Replace {
+            from: "waybar".to_string(),
+            to: "Hyprland".to_string(),
+        }
+    );
+
+    let injected = normalizer.normalize("it costs twenty dollars");
+    let itn_command = normalizer
+        .edit_command("replace twenty with thirty")
+        .await
+        .expect("replace command");
+    let plan = itn_command.plan(&injected).expect("matches the ITN output");
+    assert_eq!(plan.insert, injected.replace("20", "30"));
+}
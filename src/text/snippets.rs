@@ -0,0 +1,170 @@
+use anyhow::{bail, Context, Result};
+use arboard::Clipboard;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use time::{macros::format_description, OffsetDateTime};
+use tracing::warn;
+
+use crate::config::SnippetConfig;
+use crate::paths::expand_tilde;
+
+/// Snippet `n` is held in the text as this code point plus `n`, a private-use
+/// character no later step rewrites, until the text is about to be injected.
+const MARKER_BASE: u32 = 0xF0000;
+
+#[derive(Debug, Clone)]
+struct Snippet {
+    trigger: String,
+    pattern: Regex,
+    source: SnippetConfig,
+}
+
+/// Trigger phrases that expand to stored text blocks.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Snippets {
+    snippets: Vec<Snippet>,
+}
+
+impl Snippets {
+    pub(super) fn new(config: &HashMap<String, SnippetConfig>) -> Result<Self> {
+        let mut snippets = config
+            .iter()
+            .map(|(trigger, source)| {
+                let words = trigger.split_whitespace().collect::<Vec<_>>();
+                if words.is_empty() {
+                    bail!("Snippet trigger must not be empty");
+                }
+                let words = words.iter().map(|word| regex::escape(word));
+                let pattern = format!(
+                    r"(?i)\b{}\b[.,!?]*",
+                    words.collect::<Vec<_>>().join(r"[\s,]+")
+                );
+                Ok(Snippet {
+                    trigger: trigger.trim().to_string(),
+                    pattern: Regex::new(&pattern)
+                        .with_context(|| format!("Invalid snippet trigger '{trigger}'"))?,
+                    source: source.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // Longer triggers first, so "insert standup template" beats "insert standup".
+        snippets.sort_by(|a, b| {
+            b.trigger
+                .split_whitespace()
+                .count()
+                .cmp(&a.trigger.split_whitespace().count())
+                .then_with(|| a.trigger.cmp(&b.trigger))
+        });
+        Ok(Self { snippets })
+    }
+
+    /// Replaces spoken triggers with markers, leaving the expansion for
+    /// [`Snippets::expand_markers`] once post-processing and smart spacing
+    /// are done.
+    pub(super) fn mark_triggers(&self, text: &str) -> (String, usize) {
+        let mut result = text.to_string();
+        let mut count = 0;
+        for (index, snippet) in self.snippets.iter().enumerate() {
+            let marker = format!(" {} ", marker(index));
+            let replaced = snippet.pattern.replace_all(&result, |_: &Captures<'_>| {
+                count += 1;
+                marker.clone()
+            });
+            result = replaced.into_owned();
+        }
+        (result, count)
+    }
+
+    /// Swaps markers for the rendered snippet text.
+    pub(super) fn expand_markers(&self, text: &str) -> String {
+        if !text.chars().any(|ch| marker_index(ch).is_some()) {
+            return text.to_string();
+        }
+
+        let mut result = String::with_capacity(text.len());
+        for ch in text.chars() {
+            match marker_index(ch).and_then(|index| self.snippets.get(index)) {
+                Some(snippet) => result.push_str(&snippet.render()),
+                None => result.push(ch),
+            }
+        }
+        result
+    }
+}
+
+impl Snippet {
+    fn render(&self) -> String {
+        let template = match &self.source {
+            SnippetConfig::Text(text) => text.clone(),
+            SnippetConfig::File { file } => match fs::read_to_string(snippet_path(file)) {
+                Ok(text) => text.trim_end_matches('\n').to_string(),
+                Err(err) => {
+                    warn!(
+                        "Failed to read snippet '{}' from {}: {err}",
+                        self.trigger, file
+                    );
+                    return self.trigger.clone();
+                }
+            },
+        };
+        render_template(&template, OffsetDateTime::now_local().ok())
+    }
+}
+
+/// Fills in `{date}`, `{time}` and `{clipboard}`.
+pub(super) fn render_template(template: &str, now: Option<OffsetDateTime>) -> String {
+    let now = now.unwrap_or_else(OffsetDateTime::now_utc);
+    let mut rendered = template.to_string();
+    if rendered.contains("{date}") {
+        let date = now
+            .format(format_description!("[year]-[month]-[day]"))
+            .unwrap_or_default();
+        rendered = rendered.replace("{date}", &date);
+    }
+    if rendered.contains("{time}") {
+        let time = now
+            .format(format_description!("[hour]:[minute]"))
+            .unwrap_or_default();
+        rendered = rendered.replace("{time}", &time);
+    }
+    if rendered.contains("{clipboard}") {
+        let clipboard = Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .unwrap_or_else(|err| {
+                warn!("Clipboard unavailable for snippet: {err}");
+                String::new()
+            });
+        rendered = rendered.replace("{clipboard}", &clipboard);
+    }
+    rendered
+}
+
+fn snippet_path(file: &str) -> PathBuf {
+    let path = expand_tilde(file);
+    if path.is_relative() {
+        if let Some(project_dirs) = directories::ProjectDirs::from("", "", "hyprwhspr-rs") {
+            return project_dirs.config_dir().join(path);
+        }
+    }
+    path
+}
+
+fn marker(index: usize) -> char {
+    char::from_u32(MARKER_BASE + index as u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// The snippet markers in `text`, in order.
+pub(super) fn markers(text: &str) -> Vec<char> {
+    text.chars()
+        .filter(|ch| marker_index(*ch).is_some())
+        .collect()
+}
+
+fn marker_index(ch: char) -> Option<usize> {
+    (ch as u32)
+        .checked_sub(MARKER_BASE)
+        .filter(|offset| *offset < 0xFFFE)
+        .map(|offset| offset as usize)
+}
@@ -0,0 +1,127 @@
+use super::tokens::capitalize;
+use crate::input::{class_hint_index, normalize_hint_classes};
+
+/// Punctuation that attaches to the text before it.
+const CLOSING_PUNCTUATION: &[char] = &[',', '.', ';', ':', '!', '?', ')', ']', '}'];
+
+const OPENING_BRACKETS: &[char] = &['(', '[', '{'];
+
+const SENTENCE_END: &[char] = &['.', '!', '?', '\n'];
+
+/// What the focused window holds from earlier dictation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InjectionContext {
+    pub window_class: Option<String>,
+    /// The last text injected into this window, if any.
+    pub preceding: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct SmartSpacing {
+    pub(super) enabled: bool,
+    /// Normalized window classes that get a single trailing period dropped.
+    pub(super) chat_classes: Vec<String>,
+}
+
+impl SmartSpacing {
+    pub(super) fn new(enabled: bool, chat_classes: Vec<String>) -> Self {
+        Self {
+            enabled,
+            chat_classes: normalize_hint_classes(chat_classes),
+        }
+    }
+
+    /// Adds a leading space and fixes the case of the first word against the
+    /// text already in the window: capitalized after a finished sentence,
+    /// lowercased mid-sentence unless `raw`, the transcript before
+    /// normalization, capitalizes it too. `protected` words keep their
+    /// spelling either way, e.g. vocabulary terms.
+    pub(super) fn apply(
+        &self,
+        text: &str,
+        raw: &str,
+        context: &InjectionContext,
+        protected: &[&str],
+    ) -> String {
+        if !self.enabled || text.is_empty() {
+            return text.to_string();
+        }
+
+        let mut result = text.to_string();
+        let is_chat = context
+            .window_class
+            .as_deref()
+            .is_some_and(|class| class_hint_index(class, &self.chat_classes).is_some());
+        if is_chat && result.ends_with('.') && !result.ends_with("..") {
+            result.pop();
+        }
+
+        let Some(preceding) = context.preceding.as_deref().filter(|p| !p.is_empty()) else {
+            return result;
+        };
+
+        let tail = preceding.trim_end_matches([' ', '\t']);
+        if tail.is_empty() || tail.ends_with(SENTENCE_END) {
+            result = capitalize_first_word(&result, protected);
+        } else {
+            result = lowercase_first_word(&result, raw, protected);
+        }
+
+        let needs_space = !preceding.ends_with(char::is_whitespace)
+            && !preceding.ends_with(OPENING_BRACKETS)
+            && !result.starts_with(char::is_whitespace)
+            && !result.starts_with(CLOSING_PUNCTUATION);
+        if needs_space {
+            result.insert(0, ' ');
+        }
+        result
+    }
+}
+
+/// Capitalizes a lowercase first word that starts a new sentence.
+fn capitalize_first_word(text: &str, protected: &[&str]) -> String {
+    let (word, rest) = split_first_word(text);
+    let lowercase = word.chars().all(|ch| !ch.is_uppercase());
+    if word.is_empty() || !lowercase || protected.contains(&word) {
+        return text.to_string();
+    }
+    format!("{}{rest}", capitalize(word))
+}
+
+/// Lowercases a first word that is only capitalized for starting a sentence,
+/// i.e. one the raw transcript spells in lowercase. Names such as "Monday"
+/// or "Paris" come capitalized from the model and stay that way.
+fn lowercase_first_word(text: &str, raw: &str, protected: &[&str]) -> String {
+    let (word, rest) = split_first_word(text);
+    let mut chars = word.chars();
+    let Some(first) = chars.next() else {
+        return text.to_string();
+    };
+
+    let sentence_case = first.is_uppercase() && chars.clone().all(|ch| !ch.is_uppercase());
+    let is_pronoun = word == "I" || word.starts_with("I'");
+    let lowercase_in_raw = raw
+        .split(|ch: char| !is_word_char(ch))
+        .find(|candidate| candidate.to_lowercase() == word.to_lowercase())
+        .is_some_and(|candidate| candidate.starts_with(char::is_lowercase));
+    if !sentence_case || is_pronoun || !lowercase_in_raw || protected.contains(&word) {
+        return text.to_string();
+    }
+
+    first
+        .to_lowercase()
+        .chain(chars)
+        .chain(rest.chars())
+        .collect()
+}
+
+fn split_first_word(text: &str) -> (&str, &str) {
+    let end = text
+        .find(|ch: char| !is_word_char(ch))
+        .unwrap_or(text.len());
+    text.split_at(end)
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '\''
+}
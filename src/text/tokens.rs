@@ -0,0 +1,48 @@
+/// A whitespace-separated transcript word with its trailing punctuation split
+/// off.
+pub(super) struct Token<'a> {
+    pub(super) word: &'a str,
+    pub(super) trailing: &'a str,
+    /// `word` lowercased, for matching against phrase lists.
+    pub(super) key: String,
+}
+
+impl<'a> Token<'a> {
+    /// Splits the characters accepted by `is_trailing` off the end of `raw`.
+    /// A word made only of such characters is kept whole.
+    pub(super) fn new(raw: &'a str, is_trailing: impl Fn(char) -> bool) -> Self {
+        let word = raw.trim_end_matches(is_trailing);
+        let (word, trailing) = if word.is_empty() {
+            (raw, "")
+        } else {
+            raw.split_at(word.len())
+        };
+        Self {
+            word,
+            trailing,
+            key: word.to_lowercase(),
+        }
+    }
+
+    /// `key` without leading punctuation such as an opening quote.
+    pub(super) fn bare_key(&self) -> &str {
+        self.key
+            .trim_start_matches(|ch: char| ch.is_ascii_punctuation())
+    }
+}
+
+/// Splits `line` into tokens, see [`Token::new`].
+pub(super) fn tokenize(line: &str, is_trailing: impl Fn(char) -> bool + Copy) -> Vec<Token<'_>> {
+    line.split_whitespace()
+        .map(|raw| Token::new(raw, is_trailing))
+        .collect()
+}
+
+/// Uppercases the first character of `word`.
+pub(super) fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
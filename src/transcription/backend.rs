@@ -0,0 +1,540 @@
+use crate::config::{Config, ConfigManager, TranscriptionProvider, WhisperCppMode};
+#[cfg(feature = "parakeet")]
+use crate::paths::expand_tilde;
+use crate::transcription::prompt::provider_prompt;
+#[cfg(feature = "parakeet")]
+use crate::transcription::ParakeetTranscriber;
+use crate::transcription::{
+    provider_language, CustomOpenAiTranscriber, FallbackTranscriber, GeminiTranscriber,
+    GroqTranscriber, TranscriptionLanguage, TranscriptionResult,
+};
+#[cfg(feature = "whisper-native")]
+use crate::whisper::WhisperNativeTranscriber;
+use crate::whisper::{
+    WhisperManager, WhisperServerLaunch, WhisperServerPool, WhisperServerTranscriber,
+    WhisperVadOptions,
+};
+#[cfg(any(not(feature = "parakeet"), not(feature = "whisper-native")))]
+use anyhow::bail;
+use anyhow::{anyhow, Context, Result};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::env;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+pub type TranscribeFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<TranscriptionResult>> + Send + 'a>>;
+
+/// Per-request settings layered over what a backend was built with.
+///
+/// The prompt and language are resolved from the request's config, so
+/// profiles and config reloads that only change those reuse the running
+/// backend instead of building another one.
+#[derive(Clone, Default)]
+pub struct TranscribeOptions {
+    config: Option<Arc<Config>>,
+}
+
+impl TranscribeOptions {
+    /// Resolves the prompt and language from `config`, e.g. with an
+    /// application profile applied.
+    pub fn new(config: Arc<Config>) -> Self {
+        Self {
+            config: Some(config),
+        }
+    }
+
+    /// Prompt `provider` should use, or `built` when the request carries no
+    /// config.
+    pub fn prompt(&self, provider: &TranscriptionProvider, built: &str) -> String {
+        match &self.config {
+            Some(config) => provider_prompt(config, provider),
+            None => built.to_string(),
+        }
+    }
+
+    /// Request timeout, or `built` when the request carries no config.
+    pub fn request_timeout(&self, built: Duration) -> Duration {
+        self.config.as_deref().map_or(built, request_timeout)
+    }
+
+    /// Retry budget, or `built` when the request carries no config.
+    pub fn max_retries(&self, built: u32) -> u32 {
+        self.config
+            .as_deref()
+            .map_or(built, |config| config.transcription.max_retries)
+    }
+
+    /// Language `provider` should use, or `built` when the request carries no
+    /// config.
+    pub fn language(
+        &self,
+        provider: &TranscriptionProvider,
+        built: &TranscriptionLanguage,
+    ) -> TranscriptionLanguage {
+        match &self.config {
+            Some(config) => provider_language(config, provider),
+            None => built.clone(),
+        }
+    }
+}
+
+/// A speech-to-text backend driven by the app.
+///
+/// Backends are created by a [`TranscriberRegistry`] factory and rebuilt when
+/// the provider changes or [`Transcriber::needs_refresh`] reports a relevant
+/// config change.
+pub trait Transcriber: Send + Sync {
+    /// Validates that the backend can serve requests before the first dictation.
+    fn initialize(&self) -> Result<()>;
+
+    /// Transcribes 16 kHz mono samples with the prompt and language the
+    /// backend was built with.
+    fn transcribe(&self, audio_data: Vec<f32>) -> TranscribeFuture<'_> {
+        self.transcribe_with(audio_data, TranscribeOptions::default())
+    }
+
+    /// Transcribes 16 kHz mono samples, taking the prompt and language from
+    /// `options`.
+    fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> TranscribeFuture<'_>;
+
+    /// Provider key this backend was built for.
+    fn provider(&self) -> TranscriptionProvider;
+
+    /// Display name used in logs and benchmark output.
+    fn label(&self) -> Cow<'_, str> {
+        self.provider().label()
+    }
+
+    /// Returns true when moving from `current` to `new` requires rebuilding
+    /// this backend. Provider switches are covered by
+    /// [`TranscriberRegistry::needs_rebuild`]; prompt and language changes
+    /// arrive per request through [`TranscribeOptions`].
+    fn needs_refresh(&self, current: &Config, new: &Config) -> bool;
+
+    /// Stops external resources such as a supervised server process, called
+    /// before a replacement backend is built so it can claim them.
+    fn shutdown(&self) {}
+}
+
+/// Inputs handed to a [`TranscriberFactory`] when building a backend.
+pub struct TranscriberContext<'a> {
+    pub config_manager: &'a ConfigManager,
+    pub config: &'a Config,
+    pub vad: WhisperVadOptions,
+    /// `whisper-server` processes shared by every backend of the registry.
+    pub whisper_servers: &'a WhisperServerPool,
+}
+
+impl TranscriberContext<'_> {
+    pub fn provider(&self) -> &TranscriptionProvider {
+        &self.config.transcription.provider
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        request_timeout(self.config)
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.config.transcription.max_retries
+    }
+
+    pub fn prompt(&self) -> String {
+        provider_prompt(self.config, self.provider())
+    }
+
+    pub fn language(&self) -> TranscriptionLanguage {
+        provider_language(self.config, self.provider())
+    }
+}
+
+pub type TranscriberFactory =
+    Arc<dyn Fn(&TranscriberContext<'_>) -> Result<Box<dyn Transcriber>> + Send + Sync>;
+
+/// Maps providers to the factories that build their backends.
+///
+/// `custom.<name>` providers without an explicit registration fall back to the
+/// built-in kinds declared under `transcription.custom`.
+#[derive(Clone)]
+pub struct TranscriberRegistry {
+    factories: HashMap<TranscriptionProvider, TranscriberFactory>,
+    whisper_servers: WhisperServerPool,
+}
+
+impl TranscriberRegistry {
+    /// Creates a registry with no backends, not even the built-in ones.
+    pub fn empty() -> Self {
+        Self {
+            factories: HashMap::new(),
+            whisper_servers: WhisperServerPool::default(),
+        }
+    }
+
+    pub fn with_builtin_backends() -> Self {
+        let mut registry = Self::empty();
+        registry.register(TranscriptionProvider::WhisperCpp, build_whisper_cpp);
+        registry.register(TranscriptionProvider::Groq, build_groq);
+        registry.register(TranscriptionProvider::Gemini, build_gemini);
+        registry.register(TranscriptionProvider::Parakeet, build_parakeet);
+        registry
+    }
+
+    /// Registers `factory` for `provider`, replacing any existing registration.
+    pub fn register<F>(&mut self, provider: TranscriptionProvider, factory: F)
+    where
+        F: Fn(&TranscriberContext<'_>) -> Result<Box<dyn Transcriber>> + Send + Sync + 'static,
+    {
+        self.factories.insert(provider, Arc::new(factory));
+    }
+
+    pub fn is_registered(&self, provider: &TranscriptionProvider) -> bool {
+        self.factories.contains_key(provider)
+    }
+
+    /// Builds the configured provider, wrapped in a [`FallbackTranscriber`] when
+    /// `transcription.fallback` lists other providers.
+    pub fn build(
+        &self,
+        config_manager: &ConfigManager,
+        config: &Config,
+        vad: WhisperVadOptions,
+    ) -> Result<Box<dyn Transcriber>> {
+        let primary = self.build_provider(config_manager, config, vad.clone())?;
+
+        let mut seen = vec![config.transcription.provider.clone()];
+        let mut fallbacks = Vec::new();
+        for provider in &config.transcription.fallback {
+            if seen.contains(provider) {
+                continue;
+            }
+            seen.push(provider.clone());
+
+            let mut fallback_config = config.clone();
+            fallback_config.transcription.provider = provider.clone();
+            match self.build_provider(config_manager, &fallback_config, vad.clone()) {
+                Ok(backend) => fallbacks.push(backend),
+                Err(err) => warn!(
+                    "Skipping fallback transcription provider {}: {:#}",
+                    provider.label(),
+                    err
+                ),
+            }
+        }
+
+        if fallbacks.is_empty() {
+            return Ok(primary);
+        }
+
+        info!(
+            "🔁 Transcription fallback chain: {}",
+            std::iter::once(primary.label().into_owned())
+                .chain(fallbacks.iter().map(|backend| backend.label().into_owned()))
+                .collect::<Vec<_>>()
+                .join(" → ")
+        );
+        Ok(Box::new(FallbackTranscriber::new(primary, fallbacks)))
+    }
+
+    /// Returns true when the active backend must be rebuilt to apply `new`.
+    pub fn needs_rebuild(active: &dyn Transcriber, current: &Config, new: &Config) -> bool {
+        active.provider() != new.transcription.provider
+            || current.transcription.fallback != new.transcription.fallback
+            || active.needs_refresh(current, new)
+    }
+
+    fn build_provider(
+        &self,
+        config_manager: &ConfigManager,
+        config: &Config,
+        vad: WhisperVadOptions,
+    ) -> Result<Box<dyn Transcriber>> {
+        let context = TranscriberContext {
+            config_manager,
+            config,
+            vad,
+            whisper_servers: &self.whisper_servers,
+        };
+        let provider = context.provider();
+
+        if let Some(factory) = self.factories.get(provider) {
+            return factory(&context);
+        }
+
+        match provider {
+            TranscriptionProvider::Custom(name) => build_custom(&context, name),
+            other => Err(anyhow!(
+                "no transcription backend registered for provider '{}'",
+                other.label()
+            )),
+        }
+    }
+}
+
+impl Default for TranscriberRegistry {
+    fn default() -> Self {
+        Self::with_builtin_backends()
+    }
+}
+
+fn request_timeout(config: &Config) -> Duration {
+    Duration::from_secs(config.transcription.request_timeout_secs.max(5))
+}
+
+/// Shared refresh check for HTTP backends whose clients capture the request
+/// timeout and retry budget at build time.
+pub(crate) fn request_settings_changed(current: &Config, new: &Config) -> bool {
+    current.transcription.request_timeout_secs != new.transcription.request_timeout_secs
+        || current.transcription.max_retries != new.transcription.max_retries
+}
+
+fn build_whisper_cpp(context: &TranscriberContext<'_>) -> Result<Box<dyn Transcriber>> {
+    let whisper_cfg = &context.config.transcription.whisper_cpp;
+    match whisper_cfg.mode {
+        WhisperCppMode::Cli => {}
+        WhisperCppMode::Server => return build_whisper_server(context),
+        WhisperCppMode::Native => return build_whisper_native(context),
+    }
+
+    let whisper_binaries = context
+        .config_manager
+        .get_whisper_binary_candidates(whisper_cfg.fallback_cli);
+    let manager = WhisperManager::new(
+        context.config_manager.get_model_path()?,
+        whisper_binaries,
+        whisper_cfg.threads,
+        context.prompt(),
+        context.config_manager.get_temp_dir(),
+        whisper_cfg.gpu_layers,
+        context.vad.clone(),
+        whisper_cfg.no_speech_threshold,
+        context.language(),
+    )?;
+    Ok(Box::new(manager))
+}
+
+fn build_whisper_server(context: &TranscriberContext<'_>) -> Result<Box<dyn Transcriber>> {
+    let whisper_cfg = &context.config.transcription.whisper_cpp;
+    let binary = context
+        .config_manager
+        .get_whisper_server_binary_candidates(context.config)
+        .into_iter()
+        .next()
+        .context(
+            "No whisper-server binary found. Install whisper.cpp or set transcription.whisper_cpp.server.binary",
+        )?;
+    let launch = WhisperServerLaunch {
+        binary,
+        model_path: context.config_manager.get_model_path()?,
+        host: whisper_cfg.server.host.clone(),
+        port: whisper_cfg.server.port,
+        threads: whisper_cfg.threads,
+        gpu_layers: whisper_cfg.gpu_layers,
+        no_speech_threshold: whisper_cfg.no_speech_threshold,
+        vad: context.vad.clone(),
+        startup_timeout: Duration::from_secs(whisper_cfg.server.startup_timeout_secs.max(1)),
+    };
+    let transcriber = WhisperServerTranscriber::new(
+        context.whisper_servers,
+        launch,
+        context.request_timeout(),
+        context.max_retries(),
+        context.prompt(),
+        context.language(),
+    )?;
+    Ok(Box::new(transcriber))
+}
+
+fn build_whisper_native(context: &TranscriberContext<'_>) -> Result<Box<dyn Transcriber>> {
+    #[cfg(feature = "whisper-native")]
+    {
+        let provider = WhisperNativeTranscriber::new(
+            &context.config.transcription.whisper_cpp,
+            context.config_manager.get_model_path()?,
+            context.vad.clone(),
+            context.prompt(),
+            context.language(),
+        )?;
+        Ok(Box::new(provider))
+    }
+    #[cfg(not(feature = "whisper-native"))]
+    {
+        let _ = context;
+        bail!(
+            "In-process whisper backend is disabled in this build. Rebuild with --features whisper-native."
+        )
+    }
+}
+
+fn build_groq(context: &TranscriberContext<'_>) -> Result<Box<dyn Transcriber>> {
+    let api_key =
+        env::var("GROQ_API_KEY").context("GROQ_API_KEY environment variable is not set")?;
+    let provider = GroqTranscriber::new(
+        api_key,
+        &context.config.transcription.groq,
+        context.request_timeout(),
+        context.max_retries(),
+        context.prompt(),
+        context.language(),
+    )?;
+    Ok(Box::new(provider))
+}
+
+fn build_gemini(context: &TranscriberContext<'_>) -> Result<Box<dyn Transcriber>> {
+    let api_key =
+        env::var("GEMINI_API_KEY").context("GEMINI_API_KEY environment variable is not set")?;
+    let provider = GeminiTranscriber::new(
+        api_key,
+        &context.config.transcription.gemini,
+        context.request_timeout(),
+        context.max_retries(),
+        context.prompt(),
+        context.language(),
+    )?;
+    Ok(Box::new(provider))
+}
+
+fn build_parakeet(context: &TranscriberContext<'_>) -> Result<Box<dyn Transcriber>> {
+    #[cfg(feature = "parakeet")]
+    {
+        let par_cfg = &context.config.transcription.parakeet;
+
+        let expanded = expand_tilde(&par_cfg.model_dir);
+        let model_dir = if expanded.is_relative() {
+            directories::ProjectDirs::from("", "", "hyprwhspr-rs")
+                .map(|dirs| dirs.data_dir().join(&expanded))
+                .unwrap_or(expanded)
+        } else {
+            expanded
+        };
+
+        let provider =
+            ParakeetTranscriber::new(par_cfg, model_dir, context.prompt(), context.language())?;
+        Ok(Box::new(provider))
+    }
+    #[cfg(not(feature = "parakeet"))]
+    {
+        let _ = context;
+        bail!("Parakeet backend is disabled in this build. Rebuild with --features parakeet.")
+    }
+}
+
+fn build_custom(context: &TranscriberContext<'_>, name: &str) -> Result<Box<dyn Transcriber>> {
+    let custom_cfg = context
+        .config
+        .transcription
+        .custom
+        .get(name)
+        .ok_or_else(|| anyhow!("custom transcription provider '{name}' is not configured"))?;
+
+    // Every kind shares auth, retries and response parsing; the kind only
+    // selects how the request body is shaped.
+    let provider = CustomOpenAiTranscriber::new(
+        name,
+        custom_cfg,
+        context.request_timeout(),
+        context.max_retries(),
+        context.prompt(),
+        context.language(),
+    )?;
+    Ok(Box::new(provider))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcription::BackendMetrics;
+
+    struct EchoTranscriber;
+
+    impl Transcriber for EchoTranscriber {
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn transcribe_with(
+            &self,
+            audio_data: Vec<f32>,
+            options: TranscribeOptions,
+        ) -> TranscribeFuture<'_> {
+            Box::pin(async move {
+                let language = options.language(&self.provider(), &TranscriptionLanguage::Auto);
+                Ok(TranscriptionResult {
+                    text: format!("{} samples in {language}", audio_data.len()),
+                    metrics: BackendMetrics::default(),
+                    language: None,
+                    segments: Vec::new(),
+                })
+            })
+        }
+
+        fn provider(&self) -> TranscriptionProvider {
+            TranscriptionProvider::Custom("echo".to_string())
+        }
+
+        fn needs_refresh(&self, _current: &Config, _new: &Config) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn builtin_registry_covers_first_party_providers() {
+        let registry = TranscriberRegistry::default();
+        assert!(registry.is_registered(&TranscriptionProvider::WhisperCpp));
+        assert!(registry.is_registered(&TranscriptionProvider::Groq));
+        assert!(registry.is_registered(&TranscriptionProvider::Gemini));
+        assert!(registry.is_registered(&TranscriptionProvider::Parakeet));
+        assert!(!registry.is_registered(&TranscriptionProvider::Custom("echo".to_string())));
+
+        assert!(!TranscriberRegistry::empty().is_registered(&TranscriptionProvider::WhisperCpp));
+    }
+
+    #[tokio::test]
+    async fn registered_backends_are_usable_as_trait_objects() {
+        let provider = TranscriptionProvider::Custom("echo".to_string());
+        let mut registry = TranscriberRegistry::empty();
+        registry.register(provider.clone(), |_| Ok(Box::new(EchoTranscriber)));
+        assert!(registry.is_registered(&provider));
+
+        let mut config = Config::default();
+        config.transcription.provider = provider.clone();
+        let config_manager = ConfigManager::in_memory(config.clone());
+        let backend = registry
+            .build(&config_manager, &config, WhisperVadOptions::disabled())
+            .expect("build registered backend");
+        assert_eq!(backend.provider(), provider);
+        assert_eq!(backend.label(), "Custom (echo)");
+        let result = backend.transcribe(vec![0.0; 4]).await.expect("transcribe");
+        assert_eq!(result.text, "4 samples in auto");
+    }
+
+    #[tokio::test]
+    async fn request_options_override_the_built_language() {
+        let mut config = Config::default();
+        config.transcription.language = "de".to_string();
+        let backend: Box<dyn Transcriber> = Box::new(EchoTranscriber);
+
+        let result = backend
+            .transcribe_with(vec![0.0; 4], TranscribeOptions::new(Arc::new(config)))
+            .await
+            .expect("transcribe");
+        assert_eq!(result.text, "4 samples in de");
+    }
+
+    #[test]
+    fn request_settings_changes_require_refresh() {
+        let current = Config::default();
+        let mut new = current.clone();
+        assert!(!request_settings_changed(&current, &new));
+
+        new.transcription.max_retries += 1;
+        assert!(request_settings_changed(&current, &new));
+    }
+}
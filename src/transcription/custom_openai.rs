@@ -1,10 +1,19 @@
-use crate::config::{CustomProviderConfig, SubscriptionAuthSource};
+use crate::config::{
+    Config, CustomProviderConfig, CustomProviderKind, SubscriptionAuthSource, TranscriptionProvider,
+};
 use crate::transcription::audio::{encode_to_flac, encode_to_wav, EncodedAudio};
 use crate::transcription::postprocess::clean_transcription;
-use crate::transcription::{BackendMetrics, TranscriptionResult};
-use anyhow::{Context, Result};
-use reqwest::{header, multipart, Client, Url};
+use crate::transcription::{
+    request_settings_changed, verbose_segments_from_value, BackendMetrics, TranscribeFuture,
+    TranscribeOptions, Transcriber, TranscriptSegment, TranscriptionLanguage, TranscriptionResult,
+};
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use reqwest::{header, multipart, Client, RequestBuilder, Url};
 use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
 use std::cmp;
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
@@ -15,13 +24,21 @@ pub struct CustomOpenAiTranscriber {
     name: String,
     label: String,
     client: Client,
+    kind: CustomProviderKind,
     endpoint: Url,
     auth: CustomAuth,
+    auth_scheme: String,
     model: String,
     audio_format: AudioFormat,
     headers: Vec<(String, String)>,
     body: Vec<(String, String)>,
+    query: Vec<(String, String)>,
+    content_type: Option<String>,
+    audio_field: String,
+    transcript_pointer: String,
+    language_pointer: String,
     prompt: String,
+    language: TranscriptionLanguage,
     request_timeout: Duration,
     max_retries: u32,
 }
@@ -33,6 +50,7 @@ impl CustomOpenAiTranscriber {
         request_timeout: Duration,
         max_retries: u32,
         prompt: String,
+        language: TranscriptionLanguage,
     ) -> Result<Self> {
         let endpoint = if is_absolute_endpoint(&config.endpoint) {
             resolve_endpoint(None, &config.endpoint)?
@@ -71,8 +89,10 @@ impl CustomOpenAiTranscriber {
                 .clone()
                 .unwrap_or_else(|| format!("Custom ({name})")),
             client,
+            kind: config.kind.clone(),
             endpoint,
             auth,
+            auth_scheme: config.auth_scheme.trim().to_string(),
             model: config.model.clone(),
             audio_format: AudioFormat::from_config(&config.audio_format)?,
             headers: config
@@ -85,14 +105,31 @@ impl CustomOpenAiTranscriber {
                 .iter()
                 .map(|(key, value)| (key.clone(), value.clone()))
                 .collect(),
+            query: config
+                .query
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            content_type: config
+                .content_type
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string),
+            audio_field: config.audio_field.clone(),
+            transcript_pointer: config.transcript_pointer.clone(),
+            language_pointer: config.language_pointer.clone(),
             prompt,
+            language,
             request_timeout,
             max_retries,
         })
     }
 
     pub fn initialize(&self) -> Result<()> {
-        if self.model.trim().is_empty() {
+        if self.kind == CustomProviderKind::OpenAiAudioTranscriptions
+            && self.model.trim().is_empty()
+        {
             anyhow::bail!(
                 "model is required for custom transcription provider '{}'",
                 self.name
@@ -100,8 +137,8 @@ impl CustomOpenAiTranscriber {
         }
 
         info!(
-            "✅ {} transcription ready (model: {}, endpoint: {}, timeout: {:?})",
-            self.label, self.model, self.endpoint, self.request_timeout
+            "✅ {} transcription ready (model: {}, endpoint: {}, language: {}, timeout: {:?})",
+            self.label, self.model, self.endpoint, self.language, self.request_timeout
         );
         Ok(())
     }
@@ -110,20 +147,30 @@ impl CustomOpenAiTranscriber {
         &self.label
     }
 
-    pub async fn transcribe(&self, audio_data: Vec<f32>) -> Result<TranscriptionResult> {
+    pub async fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult> {
         if audio_data.is_empty() {
             return Ok(TranscriptionResult {
                 text: String::new(),
                 metrics: BackendMetrics::default(),
+                language: None,
+                segments: Vec::new(),
             });
         }
 
         let duration_secs = audio_data.len() as f32 / 16000.0;
         info!(
             provider = self.provider_name(),
-            "🧠 Transcribing {:.2}s of audio via custom OpenAI-compatible provider", duration_secs
+            "🧠 Transcribing {:.2}s of audio via custom provider", duration_secs
         );
 
+        let provider = self.provider();
+        let prompt = options.prompt(&provider, &self.prompt);
+        let language = options.language(&provider, &self.language);
+
         let encode_start = Instant::now();
         let encoded = match self.audio_format {
             AudioFormat::Wav => encode_to_wav(&audio_data).await?,
@@ -133,9 +180,10 @@ impl CustomOpenAiTranscriber {
         let encoded_len = encoded.data.len();
 
         let transcribe_start = Instant::now();
-        let (raw, timings) = self.send_with_retry(&encoded).await?;
+        let (payload, timings) = self.send_with_retry(&encoded, &prompt, &language).await?;
+        let raw = payload.text;
         let transcription_duration = transcribe_start.elapsed();
-        let cleaned = clean_transcription(&raw, &self.prompt);
+        let cleaned = clean_transcription(&raw, &prompt);
 
         if cleaned.is_empty() {
             warn!("{} returned empty or non-speech transcription", self.label);
@@ -150,19 +198,28 @@ impl CustomOpenAiTranscriber {
             response_duration: Some(timings.response),
             transcription_duration,
             phases: Vec::new(),
+            fallback_attempts: Vec::new(),
+            served_by: None,
         };
 
         Ok(TranscriptionResult {
             text: cleaned,
             metrics,
+            language: payload.language,
+            segments: payload.segments,
         })
     }
 
-    async fn send_with_retry(&self, audio: &EncodedAudio) -> Result<(String, NetworkTimings)> {
+    async fn send_with_retry(
+        &self,
+        audio: &EncodedAudio,
+        prompt: &str,
+        language: &TranscriptionLanguage,
+    ) -> Result<(TranscriptPayload, NetworkTimings)> {
         let attempts = cmp::max(1, self.max_retries.saturating_add(1));
 
         for attempt in 0..attempts {
-            match self.send_once(audio).await {
+            match self.send_once(audio, prompt, language).await {
                 Ok(result) => return Ok(result),
                 Err(err) => {
                     if attempt + 1 == attempts {
@@ -185,27 +242,19 @@ impl CustomOpenAiTranscriber {
         Err(anyhow::anyhow!("Unknown custom transcription failure"))
     }
 
-    async fn send_once(&self, audio: &EncodedAudio) -> Result<(String, NetworkTimings)> {
-        let mut form = multipart::Form::new()
-            .text("model", self.model.clone())
-            .text("response_format", "json".to_string());
-
-        for (key, value) in &self.body {
-            form = form.text(key.clone(), value.clone());
-        }
-
-        if !self.prompt.trim().is_empty() && !self.body.iter().any(|(key, _)| key == "prompt") {
-            form = form.text("prompt", self.prompt.clone());
-        }
-
-        let file_part = multipart::Part::stream(audio.data.clone())
-            .file_name(self.audio_format.file_name())
-            .mime_str(audio.content_type)
-            .context("Failed to set custom provider audio content type")?;
-
-        form = form.part("file", file_part);
-
-        let mut request = self.client.post(self.endpoint.clone()).multipart(form);
+    async fn send_once(
+        &self,
+        audio: &EncodedAudio,
+        prompt: &str,
+        language: &TranscriptionLanguage,
+    ) -> Result<(TranscriptPayload, NetworkTimings)> {
+        let mut request = match self.kind {
+            CustomProviderKind::OpenAiAudioTranscriptions => {
+                self.multipart_request(audio, prompt, language)?
+            }
+            CustomProviderKind::RawAudio => self.raw_audio_request(audio, language),
+            CustomProviderKind::JsonBase64 => self.json_base64_request(audio, prompt, language),
+        };
         let mut has_authorization = false;
 
         for (key, value) in &self.headers {
@@ -217,7 +266,12 @@ impl CustomOpenAiTranscriber {
 
         if !has_authorization {
             if let Some(auth_token) = self.resolve_auth_token()? {
-                request = request.bearer_auth(auth_token);
+                let value = if self.auth_scheme.is_empty() {
+                    auth_token
+                } else {
+                    format!("{} {auth_token}", self.auth_scheme)
+                };
+                request = request.header(header::AUTHORIZATION, value);
             }
         }
 
@@ -231,13 +285,14 @@ impl CustomOpenAiTranscriber {
 
         if response.status().is_success() {
             let parse_start = Instant::now();
-            let payload: OpenAiTranscriptionResponse = response
+            let body: Value = response
                 .json()
                 .await
                 .context("Failed to deserialize custom transcription response")?;
+            let payload = self.extract_payload(&body)?;
             let response_duration = parse_start.elapsed();
             return Ok((
-                payload.text.unwrap_or_default(),
+                payload,
                 NetworkTimings {
                     upload: upload_duration,
                     response: response_duration,
@@ -259,6 +314,130 @@ impl CustomOpenAiTranscriber {
         Err(anyhow::anyhow!(message).context(format!("Custom request failed ({status})")))
     }
 
+    fn multipart_request(
+        &self,
+        audio: &EncodedAudio,
+        prompt: &str,
+        language: &TranscriptionLanguage,
+    ) -> Result<RequestBuilder> {
+        let mut form = multipart::Form::new()
+            .text("model", self.model.clone())
+            .text("response_format", "json".to_string());
+
+        for (key, value) in &self.body {
+            form = form.text(key.clone(), value.clone());
+        }
+
+        if !prompt.trim().is_empty() && !self.body.iter().any(|(key, _)| key == "prompt") {
+            form = form.text("prompt", prompt.to_string());
+        }
+
+        if let Some(code) = language.code() {
+            if !self.body.iter().any(|(key, _)| key == "language") {
+                form = form.text("language", code.to_string());
+            }
+        }
+
+        let file_part = multipart::Part::stream(audio.data.clone())
+            .file_name(self.audio_format.file_name())
+            .mime_str(audio.content_type)
+            .context("Failed to set custom provider audio content type")?;
+
+        form = form.part("file", file_part);
+
+        Ok(self
+            .client
+            .post(self.endpoint.clone())
+            .query(&self.query)
+            .multipart(form))
+    }
+
+    fn raw_audio_request(
+        &self,
+        audio: &EncodedAudio,
+        language: &TranscriptionLanguage,
+    ) -> RequestBuilder {
+        let mut query = self.query.clone();
+        if !self.model.trim().is_empty() && !has_key(&query, "model") {
+            query.push(("model".to_string(), self.model.clone()));
+        }
+        if let Some(code) = language.code() {
+            if !has_key(&query, "language") {
+                query.push(("language".to_string(), code.to_string()));
+            }
+        }
+
+        let content_type = self.content_type.as_deref().unwrap_or(audio.content_type);
+        self.client
+            .post(self.endpoint.clone())
+            .query(&query)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(audio.data.clone())
+    }
+
+    fn json_base64_request(
+        &self,
+        audio: &EncodedAudio,
+        prompt: &str,
+        language: &TranscriptionLanguage,
+    ) -> RequestBuilder {
+        let mut body = Map::new();
+        for (key, value) in &self.body {
+            body.insert(key.clone(), Value::String(value.clone()));
+        }
+
+        if !self.model.trim().is_empty() && !body.contains_key("model") {
+            body.insert("model".to_string(), Value::String(self.model.clone()));
+        }
+        if !prompt.trim().is_empty() && !body.contains_key("prompt") {
+            body.insert("prompt".to_string(), Value::String(prompt.to_string()));
+        }
+        if let Some(code) = language.code() {
+            if !body.contains_key("language") {
+                body.insert("language".to_string(), Value::String(code.to_string()));
+            }
+        }
+        body.insert(
+            self.audio_field.clone(),
+            Value::String(BASE64.encode(&audio.data)),
+        );
+
+        self.client
+            .post(self.endpoint.clone())
+            .query(&self.query)
+            .json(&Value::Object(body))
+    }
+
+    fn extract_payload(&self, body: &Value) -> Result<TranscriptPayload> {
+        let transcript = body.pointer(&self.transcript_pointer);
+        let text = match transcript.and_then(Value::as_str) {
+            Some(text) => text,
+            // OpenAI-compatible servers leave `text` out or null for silence.
+            None if self.kind == CustomProviderKind::OpenAiAudioTranscriptions
+                && transcript.is_none_or(Value::is_null) =>
+            {
+                ""
+            }
+            None => bail!(
+                "{} response has no transcript at JSON pointer {}",
+                self.label,
+                self.transcript_pointer
+            ),
+        };
+        let language = body
+            .pointer(&self.language_pointer)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string);
+
+        Ok(TranscriptPayload {
+            text: text.to_string(),
+            language,
+            segments: verbose_segments_from_value(body),
+        })
+    }
+
     fn resolve_auth_token(&self) -> Result<Option<String>> {
         match &self.auth {
             CustomAuth::None => Ok(None),
@@ -271,6 +450,43 @@ impl CustomOpenAiTranscriber {
     }
 }
 
+impl Transcriber for CustomOpenAiTranscriber {
+    fn initialize(&self) -> Result<()> {
+        CustomOpenAiTranscriber::initialize(self)
+    }
+
+    fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> TranscribeFuture<'_> {
+        Box::pin(CustomOpenAiTranscriber::transcribe_with(
+            self, audio_data, options,
+        ))
+    }
+
+    fn provider(&self) -> TranscriptionProvider {
+        TranscriptionProvider::Custom(self.name.clone())
+    }
+
+    fn label(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.label)
+    }
+
+    fn needs_refresh(&self, current: &Config, new: &Config) -> bool {
+        let connection = |config: &Config| {
+            config.transcription.custom.get(&self.name).map(|custom| {
+                // Prompt and language are resolved per request.
+                let mut custom = custom.clone();
+                custom.prompt.clear();
+                custom.language = None;
+                custom
+            })
+        };
+        request_settings_changed(current, new) || connection(current) != connection(new)
+    }
+}
+
 #[derive(Clone)]
 enum CustomAuth {
     None,
@@ -303,11 +519,15 @@ impl AudioFormat {
     }
 }
 
-fn is_absolute_endpoint(endpoint: &str) -> bool {
+fn has_key(pairs: &[(String, String)], key: &str) -> bool {
+    pairs.iter().any(|(name, _)| name == key)
+}
+
+pub(crate) fn is_absolute_endpoint(endpoint: &str) -> bool {
     endpoint.starts_with("http://") || endpoint.starts_with("https://")
 }
 
-fn resolve_endpoint(base_url: Option<&str>, endpoint: &str) -> Result<Url> {
+pub(crate) fn resolve_endpoint(base_url: Option<&str>, endpoint: &str) -> Result<Url> {
     if is_absolute_endpoint(endpoint) {
         return Url::parse(endpoint)
             .with_context(|| format!("Invalid custom endpoint: {endpoint}"));
@@ -331,9 +551,11 @@ struct NetworkTimings {
     response: Duration,
 }
 
-#[derive(Debug, Deserialize, Default)]
-struct OpenAiTranscriptionResponse {
-    text: Option<String>,
+#[derive(Debug)]
+struct TranscriptPayload {
+    text: String,
+    language: Option<String>,
+    segments: Vec<TranscriptSegment>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -350,8 +572,8 @@ struct OpenAiErrorDetail {
 mod tests {
     use super::*;
     use crate::config::{SecretSource, ValueSource};
+    use crate::test_http::{read_request, write_response};
     use bytes::Bytes;
-    use tokio::io::{AsyncReadExt, AsyncWriteExt};
     use tokio::net::{TcpListener, TcpStream};
     use tokio::sync::mpsc;
 
@@ -378,28 +600,27 @@ mod tests {
             },
             ..Default::default()
         };
-        let transcriber = CustomOpenAiTranscriber::new(
-            "auth_refresh",
-            &config,
-            Duration::from_secs(5),
-            0,
-            String::new(),
-        )
-        .expect("transcriber");
+        let transcriber = transcriber("auth_refresh", &config);
 
         let audio = EncodedAudio {
             data: Bytes::from_static(b"not-a-real-wav"),
             content_type: "audio/wav",
         };
 
-        transcriber.send_once(&audio).await.expect("first request");
+        transcriber
+            .send_once(&audio, &transcriber.prompt, &transcriber.language)
+            .await
+            .expect("first request");
         assert_eq!(
             auth_headers.recv().await.as_deref(),
             Some("Bearer first-token")
         );
 
         write_auth_token(&auth_path, "second-token");
-        transcriber.send_once(&audio).await.expect("second request");
+        transcriber
+            .send_once(&audio, &transcriber.prompt, &transcriber.language)
+            .await
+            .expect("second request");
         assert_eq!(
             auth_headers.recv().await.as_deref(),
             Some("Bearer second-token")
@@ -408,6 +629,204 @@ mod tests {
         let _ = std::fs::remove_dir_all(&root);
     }
 
+    #[tokio::test]
+    async fn server_errors_surface_so_fallbacks_can_run() {
+        let endpoint = spawn_failing_server().await;
+        let config = CustomProviderConfig {
+            endpoint,
+            model: "whisper-1".to_string(),
+            audio_format: "wav".to_string(),
+            ..Default::default()
+        };
+        let transcriber = transcriber("flaky", &config);
+
+        let audio = EncodedAudio {
+            data: Bytes::from_static(b"not-a-real-wav"),
+            content_type: "audio/wav",
+        };
+
+        let err = transcriber
+            .send_once(&audio, &transcriber.prompt, &transcriber.language)
+            .await
+            .expect_err("500 response must fail");
+        let message = format!("{err:#}");
+        assert!(message.contains("500"));
+        assert!(message.contains("upstream unavailable"));
+    }
+
+    #[tokio::test]
+    async fn raw_audio_kind_posts_body_with_query_and_auth_scheme() {
+        let (endpoint, mut requests) = spawn_capture_server(
+            br#"{"results":{"channels":[{"alternatives":[{"transcript":"hello raw"}]}]}}"#,
+        )
+        .await;
+        std::env::set_var("HYPRWHSPR_TEST_RAW_AUDIO_KEY", "raw-secret");
+        let config = CustomProviderConfig {
+            kind: CustomProviderKind::RawAudio,
+            endpoint,
+            model: "nova-3".to_string(),
+            content_type: Some("audio/x-wav".to_string()),
+            auth_scheme: "Token".to_string(),
+            api_key: SecretSource {
+                env: Some("HYPRWHSPR_TEST_RAW_AUDIO_KEY".to_string()),
+                ..Default::default()
+            },
+            query: [("smart_format".to_string(), "true".to_string())].into(),
+            transcript_pointer: "/results/channels/0/alternatives/0/transcript".to_string(),
+            ..Default::default()
+        };
+        let transcriber = CustomOpenAiTranscriber {
+            language: TranscriptionLanguage::Code("de".to_string()),
+            ..transcriber("raw", &config)
+        };
+
+        let (payload, _) = transcriber
+            .send_once(&test_audio(), &transcriber.prompt, &transcriber.language)
+            .await
+            .expect("raw request");
+        assert_eq!(payload.text, "hello raw");
+        assert_eq!(payload.language, None);
+
+        let (headers, body) = requests.recv().await.expect("captured request");
+        let request_line = headers.lines().next().unwrap_or_default();
+        assert!(request_line.contains("smart_format=true"));
+        assert!(request_line.contains("model=nova-3"));
+        assert!(request_line.contains("language=de"));
+        assert_eq!(header_value(&headers, "content-type"), Some("audio/x-wav"));
+        assert_eq!(
+            header_value(&headers, "authorization"),
+            Some("Token raw-secret")
+        );
+        assert_eq!(body, b"not-a-real-wav");
+    }
+
+    #[tokio::test]
+    async fn json_base64_kind_embeds_audio_and_reads_pointers() {
+        let (endpoint, mut requests) =
+            spawn_capture_server(br#"{"result":{"text":"hello json","lang":"en"}}"#).await;
+        let config = CustomProviderConfig {
+            kind: CustomProviderKind::JsonBase64,
+            endpoint,
+            model: "large-v3".to_string(),
+            audio_field: "audio_data".to_string(),
+            body: [("task".to_string(), "transcribe".to_string())].into(),
+            transcript_pointer: "/result/text".to_string(),
+            language_pointer: "/result/lang".to_string(),
+            ..Default::default()
+        };
+        let transcriber = CustomOpenAiTranscriber {
+            prompt: "Technical notes.".to_string(),
+            ..transcriber("json", &config)
+        };
+
+        let (payload, _) = transcriber
+            .send_once(&test_audio(), &transcriber.prompt, &transcriber.language)
+            .await
+            .expect("json request");
+        assert_eq!(payload.text, "hello json");
+        assert_eq!(payload.language.as_deref(), Some("en"));
+
+        let (headers, body) = requests.recv().await.expect("captured request");
+        assert_eq!(
+            header_value(&headers, "content-type"),
+            Some("application/json")
+        );
+        let body: Value = serde_json::from_slice(&body).expect("json body");
+        assert_eq!(body["audio_data"], BASE64.encode(b"not-a-real-wav"));
+        assert_eq!(body["model"], "large-v3");
+        assert_eq!(body["task"], "transcribe");
+        assert_eq!(body["prompt"], "Technical notes.");
+        assert!(body.get("language").is_none());
+    }
+
+    #[tokio::test]
+    async fn missing_transcript_pointer_is_an_error() {
+        let (endpoint, _requests) = spawn_capture_server(br#"{"status":"queued"}"#).await;
+        let config = CustomProviderConfig {
+            kind: CustomProviderKind::JsonBase64,
+            endpoint,
+            transcript_pointer: "/text".to_string(),
+            ..Default::default()
+        };
+        let transcriber = transcriber("json", &config);
+
+        let err = transcriber
+            .send_once(&test_audio(), &transcriber.prompt, &transcriber.language)
+            .await
+            .expect_err("response without transcript must fail");
+        assert!(format!("{err:#}").contains("no transcript at JSON pointer /text"));
+    }
+
+    #[tokio::test]
+    async fn openai_responses_without_text_are_empty_transcripts() {
+        for body in [&b"{}"[..], br#"{"text":null}"#] {
+            let (endpoint, _requests) = spawn_capture_server(body).await;
+            let config = CustomProviderConfig {
+                endpoint,
+                model: "whisper-1".to_string(),
+                ..Default::default()
+            };
+            let transcriber = transcriber("silence", &config);
+
+            let (payload, _) = transcriber
+                .send_once(&test_audio(), &transcriber.prompt, &transcriber.language)
+                .await
+                .expect("silence is not an error");
+            assert_eq!(payload.text, "");
+        }
+    }
+
+    fn transcriber(name: &str, config: &CustomProviderConfig) -> CustomOpenAiTranscriber {
+        CustomOpenAiTranscriber::new(
+            name,
+            config,
+            Duration::from_secs(5),
+            0,
+            String::new(),
+            TranscriptionLanguage::Auto,
+        )
+        .expect("transcriber")
+    }
+
+    fn test_audio() -> EncodedAudio {
+        EncodedAudio {
+            data: Bytes::from_static(b"not-a-real-wav"),
+            content_type: "audio/wav",
+        }
+    }
+
+    fn header_value<'a>(headers: &'a str, name: &str) -> Option<&'a str> {
+        headers
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+    }
+
+    async fn spawn_capture_server(
+        response: &'static [u8],
+    ) -> (String, mpsc::Receiver<(String, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test server");
+        let endpoint = format!(
+            "http://{}/v1/listen",
+            listener.local_addr().expect("local addr")
+        );
+        let (tx, rx) = mpsc::channel(1);
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept request");
+            let request = read_request(&mut stream).await.expect("read request");
+            write_response(&mut stream, "200 OK", response)
+                .await
+                .expect("write response");
+            tx.send(request).await.expect("send request");
+        });
+
+        (endpoint, rx)
+    }
+
     fn write_auth_token(path: &std::path::Path, token: &str) {
         std::fs::write(
             path,
@@ -449,35 +868,7 @@ mod tests {
     }
 
     async fn read_authorization_header(mut stream: TcpStream) -> Result<String> {
-        let mut buffer = Vec::new();
-        let mut chunk = [0; 1024];
-        let header_end = loop {
-            let read = stream.read(&mut chunk).await?;
-            if read == 0 {
-                anyhow::bail!("request closed before headers");
-            }
-            buffer.extend_from_slice(&chunk[..read]);
-            if let Some(position) = find_header_end(&buffer) {
-                break position;
-            }
-        };
-
-        let headers = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
-        let content_length = headers
-            .lines()
-            .filter_map(|line| line.split_once(':'))
-            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
-            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
-            .unwrap_or(0);
-        let body_start = header_end + 4;
-        while buffer.len().saturating_sub(body_start) < content_length {
-            let read = stream.read(&mut chunk).await?;
-            if read == 0 {
-                break;
-            }
-            buffer.extend_from_slice(&chunk[..read]);
-        }
-
+        let headers = read_request_headers(&mut stream).await?;
         let auth = headers
             .lines()
             .filter_map(|line| line.split_once(':'))
@@ -485,19 +876,38 @@ mod tests {
             .map(|(_, value)| value.trim().to_string())
             .context("missing authorization header")?;
 
-        let body = br#"{"text":""}"#;
-        let response = format!(
-            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
-            body.len()
-        );
-        stream.write_all(response.as_bytes()).await?;
-        stream.write_all(body).await?;
-        stream.shutdown().await?;
+        write_response(&mut stream, "200 OK", br#"{"text":""}"#).await?;
 
         Ok(auth)
     }
 
-    fn find_header_end(buffer: &[u8]) -> Option<usize> {
-        buffer.windows(4).position(|window| window == b"\r\n\r\n")
+    async fn spawn_failing_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test server");
+        let endpoint = format!(
+            "http://{}/v1/audio/transcriptions",
+            listener.local_addr().expect("local addr")
+        );
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept request");
+            read_request_headers(&mut stream)
+                .await
+                .expect("read request");
+            write_response(
+                &mut stream,
+                "500 Internal Server Error",
+                br#"{"error":{"message":"upstream unavailable"}}"#,
+            )
+            .await
+            .expect("write response");
+        });
+
+        endpoint
+    }
+
+    async fn read_request_headers(stream: &mut TcpStream) -> Result<String> {
+        read_request(stream).await.map(|(headers, _)| headers)
     }
 }
@@ -0,0 +1,241 @@
+use crate::config::{Config, TranscriptionProvider};
+use crate::transcription::{
+    FallbackAttempt, TranscribeFuture, TranscribeOptions, Transcriber, TranscriptionResult,
+};
+use anyhow::{anyhow, Result};
+use std::borrow::Cow;
+use std::time::Instant;
+use tracing::{info, warn};
+
+/// Runs the primary backend and, when it errors, retries the same audio on
+/// each fallback backend in order.
+pub struct FallbackTranscriber {
+    primary: Box<dyn Transcriber>,
+    fallbacks: Vec<Box<dyn Transcriber>>,
+}
+
+impl FallbackTranscriber {
+    pub fn new(primary: Box<dyn Transcriber>, fallbacks: Vec<Box<dyn Transcriber>>) -> Self {
+        Self { primary, fallbacks }
+    }
+
+    async fn transcribe_chain(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult> {
+        let mut failures = Vec::new();
+        let mut last_error = None;
+
+        for (index, backend) in self.backends().enumerate() {
+            let label = backend.label().into_owned();
+            let started_at = Instant::now();
+
+            match backend
+                .transcribe_with(audio_data.clone(), options.clone())
+                .await
+            {
+                Ok(mut result) => {
+                    if index > 0 {
+                        info!(
+                            provider = label.as_str(),
+                            failed = failures.len(),
+                            "🔁 Transcription produced by fallback provider {}",
+                            label
+                        );
+                        result.metrics.served_by = Some(label);
+                    }
+                    result.metrics.fallback_attempts = failures;
+                    return Ok(result);
+                }
+                Err(err) => {
+                    warn!(
+                        provider = label.as_str(),
+                        "Transcription provider {} failed: {:#}", label, err
+                    );
+                    failures.push(FallbackAttempt {
+                        provider_label: label,
+                        duration: started_at.elapsed(),
+                        error: format!("{err:#}"),
+                    });
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        let err = last_error.unwrap_or_else(|| anyhow!("no transcription providers configured"));
+        Err(err.context(format!(
+            "All {} transcription providers failed",
+            failures.len()
+        )))
+    }
+
+    fn backends(&self) -> impl Iterator<Item = &dyn Transcriber> {
+        std::iter::once(self.primary.as_ref()).chain(self.fallbacks.iter().map(|b| b.as_ref()))
+    }
+}
+
+impl Transcriber for FallbackTranscriber {
+    fn initialize(&self) -> Result<()> {
+        self.primary.initialize()?;
+
+        // A broken fallback should not block dictation through the primary.
+        for backend in &self.fallbacks {
+            if let Err(err) = backend.initialize() {
+                warn!(
+                    "Fallback transcription provider {} failed to initialize: {:#}",
+                    backend.label(),
+                    err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> TranscribeFuture<'_> {
+        Box::pin(self.transcribe_chain(audio_data, options))
+    }
+
+    fn provider(&self) -> TranscriptionProvider {
+        self.primary.provider()
+    }
+
+    fn label(&self) -> Cow<'_, str> {
+        self.primary.label()
+    }
+
+    fn needs_refresh(&self, current: &Config, new: &Config) -> bool {
+        current.transcription.fallback != new.transcription.fallback
+            || self
+                .backends()
+                .any(|backend| backend.needs_refresh(current, new))
+    }
+
+    fn shutdown(&self) {
+        for backend in self.backends() {
+            backend.shutdown();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcription::BackendMetrics;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct StaticTranscriber {
+        name: &'static str,
+        text: Option<&'static str>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl StaticTranscriber {
+        fn boxed(
+            name: &'static str,
+            text: Option<&'static str>,
+            calls: &Arc<AtomicUsize>,
+        ) -> Box<dyn Transcriber> {
+            Box::new(Self {
+                name,
+                text,
+                calls: Arc::clone(calls),
+            })
+        }
+    }
+
+    impl Transcriber for StaticTranscriber {
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn transcribe_with(
+            &self,
+            _audio_data: Vec<f32>,
+            _options: TranscribeOptions,
+        ) -> TranscribeFuture<'_> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                match self.text {
+                    Some(text) => Ok(TranscriptionResult {
+                        text: text.to_string(),
+                        metrics: BackendMetrics::default(),
+                        language: None,
+                        segments: Vec::new(),
+                    }),
+                    None => Err(anyhow!("{} returned 500", self.name)),
+                }
+            })
+        }
+
+        fn provider(&self) -> TranscriptionProvider {
+            TranscriptionProvider::Custom(self.name.to_string())
+        }
+
+        fn needs_refresh(&self, _current: &Config, _new: &Config) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn primary_success_skips_fallbacks() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let chain = FallbackTranscriber::new(
+            StaticTranscriber::boxed("primary", Some("hello"), &calls),
+            vec![StaticTranscriber::boxed("backup", Some("unused"), &calls)],
+        );
+
+        let result = chain.transcribe(vec![0.0; 16]).await.expect("transcribe");
+        assert_eq!(result.text, "hello");
+        assert!(result.metrics.fallback_attempts.is_empty());
+        assert_eq!(result.metrics.served_by, None);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_providers_fall_through_in_order() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let chain = FallbackTranscriber::new(
+            StaticTranscriber::boxed("primary", None, &calls),
+            vec![
+                StaticTranscriber::boxed("second", None, &calls),
+                StaticTranscriber::boxed("third", Some("recovered"), &calls),
+            ],
+        );
+
+        let result = chain.transcribe(vec![0.0; 16]).await.expect("transcribe");
+        assert_eq!(result.text, "recovered");
+        assert_eq!(result.metrics.served_by.as_deref(), Some("Custom (third)"));
+        let failed: Vec<_> = result
+            .metrics
+            .fallback_attempts
+            .iter()
+            .map(|attempt| attempt.provider_label.as_str())
+            .collect();
+        assert_eq!(failed, ["Custom (primary)", "Custom (second)"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn exhausted_chain_reports_last_error() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let chain = FallbackTranscriber::new(
+            StaticTranscriber::boxed("primary", None, &calls),
+            vec![StaticTranscriber::boxed("backup", None, &calls)],
+        );
+
+        let err = chain
+            .transcribe(vec![0.0; 16])
+            .await
+            .expect_err("all providers fail");
+        let message = format!("{err:#}");
+        assert!(message.contains("All 2 transcription providers failed"));
+        assert!(message.contains("backup returned 500"));
+    }
+}
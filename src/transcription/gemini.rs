@@ -1,7 +1,10 @@
-use crate::config::GeminiConfig;
+use crate::config::{Config, GeminiConfig, TranscriptionProvider};
 use crate::transcription::audio::{encode_to_flac, EncodedAudio};
 use crate::transcription::postprocess::clean_transcription;
-use crate::transcription::{BackendMetrics, TranscriptionResult};
+use crate::transcription::{
+    request_settings_changed, BackendMetrics, TranscribeFuture, TranscribeOptions, Transcriber,
+    TranscriptionLanguage, TranscriptionResult,
+};
 use anyhow::{Context, Result};
 use base64::engine::general_purpose::STANDARD as BASE64;
 use base64::Engine;
@@ -18,6 +21,7 @@ pub struct GeminiTranscriber {
     endpoint: Url,
     api_key: String,
     prompt: String,
+    language: TranscriptionLanguage,
     temperature: f32,
     max_output_tokens: u32,
     model: String,
@@ -32,6 +36,7 @@ impl GeminiTranscriber {
         request_timeout: Duration,
         max_retries: u32,
         prompt: String,
+        language: TranscriptionLanguage,
     ) -> Result<Self> {
         let trimmed_endpoint = config.endpoint.trim_end_matches('/');
         let endpoint = Url::parse(&format!(
@@ -53,6 +58,7 @@ impl GeminiTranscriber {
             endpoint,
             api_key,
             prompt,
+            language,
             temperature: config.temperature,
             max_output_tokens: config.max_output_tokens,
             model: config.model.clone(),
@@ -67,8 +73,8 @@ impl GeminiTranscriber {
         }
 
         info!(
-            "✅ Gemini transcription ready (model: {}, timeout: {:?})",
-            self.model, self.request_timeout
+            "✅ Gemini transcription ready (model: {}, language: {}, timeout: {:?})",
+            self.model, self.language, self.request_timeout
         );
         Ok(())
     }
@@ -77,11 +83,17 @@ impl GeminiTranscriber {
         "Gemini 2.5 Pro Flash"
     }
 
-    pub async fn transcribe(&self, audio_data: Vec<f32>) -> Result<TranscriptionResult> {
+    pub async fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult> {
         if audio_data.is_empty() {
             return Ok(TranscriptionResult {
                 text: String::new(),
                 metrics: BackendMetrics::default(),
+                language: None,
+                segments: Vec::new(),
             });
         }
 
@@ -91,6 +103,10 @@ impl GeminiTranscriber {
             "🧠 Transcribing {:.2}s of audio via Gemini", duration_secs
         );
 
+        let prompt = options.prompt(&TranscriptionProvider::Gemini, &self.prompt);
+        let language = options.language(&TranscriptionProvider::Gemini, &self.language);
+        let instruction = build_instruction(&prompt, &language);
+
         let encode_start = Instant::now();
         let encoded = encode_to_flac(&audio_data).await?;
         let audio_payload = BASE64.encode(encoded.data.as_ref());
@@ -98,9 +114,11 @@ impl GeminiTranscriber {
         let payload_bytes = audio_payload.len();
 
         let transcribe_start = Instant::now();
-        let (raw, timings) = self.send_with_retry(&encoded, &audio_payload).await?;
+        let (raw, timings) = self
+            .send_with_retry(&encoded, &audio_payload, &instruction)
+            .await?;
         let transcription_duration = transcribe_start.elapsed();
-        let cleaned = clean_transcription(&raw, &self.prompt);
+        let cleaned = clean_transcription(&raw, &prompt);
 
         if cleaned.is_empty() {
             warn!("Gemini returned empty or non-speech transcription");
@@ -115,11 +133,15 @@ impl GeminiTranscriber {
             response_duration: Some(timings.response),
             transcription_duration,
             phases: Vec::new(),
+            fallback_attempts: Vec::new(),
+            served_by: None,
         };
 
         Ok(TranscriptionResult {
             text: cleaned,
             metrics,
+            language: None,
+            segments: Vec::new(),
         })
     }
 
@@ -127,11 +149,12 @@ impl GeminiTranscriber {
         &self,
         audio: &EncodedAudio,
         payload: &str,
+        instruction: &str,
     ) -> Result<(String, NetworkTimings)> {
         let attempts = cmp::max(1, self.max_retries.saturating_add(1));
 
         for attempt in 0..attempts {
-            match self.send_once(audio, payload).await {
+            match self.send_once(audio, payload, instruction).await {
                 Ok(result) => return Ok(result),
                 Err(err) => {
                     if attempt + 1 == attempts {
@@ -158,17 +181,16 @@ impl GeminiTranscriber {
         &self,
         audio: &EncodedAudio,
         payload: &str,
+        instruction: &str,
     ) -> Result<(String, NetworkTimings)> {
         let mut url = self.endpoint.clone();
         url.query_pairs_mut().append_pair("key", &self.api_key);
 
-        let instruction = build_instruction(&self.prompt);
-
         let body = GeminiRequest {
             contents: vec![GeminiContent {
                 role: "user",
                 parts: vec![
-                    GeminiPart::Text { text: &instruction },
+                    GeminiPart::Text { text: instruction },
                     GeminiPart::InlineData {
                         inline_data: InlineData {
                             mime_type: audio.content_type,
@@ -225,17 +247,59 @@ impl GeminiTranscriber {
     }
 }
 
+impl Transcriber for GeminiTranscriber {
+    fn initialize(&self) -> Result<()> {
+        GeminiTranscriber::initialize(self)
+    }
+
+    fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> TranscribeFuture<'_> {
+        Box::pin(GeminiTranscriber::transcribe_with(
+            self, audio_data, options,
+        ))
+    }
+
+    fn provider(&self) -> TranscriptionProvider {
+        TranscriptionProvider::Gemini
+    }
+
+    fn needs_refresh(&self, current: &Config, new: &Config) -> bool {
+        let (current_gemini, new_gemini) =
+            (&current.transcription.gemini, &new.transcription.gemini);
+        request_settings_changed(current, new)
+            || current_gemini.endpoint != new_gemini.endpoint
+            || current_gemini.model != new_gemini.model
+            || current_gemini.temperature != new_gemini.temperature
+            || current_gemini.max_output_tokens != new_gemini.max_output_tokens
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct NetworkTimings {
     upload: Duration,
     response: Duration,
 }
 
-fn build_instruction(prompt: &str) -> String {
+fn build_instruction(prompt: &str, language: &TranscriptionLanguage) -> String {
     let mut instruction = String::from(
         "You are a dedicated speech-to-text engine. Return only the verbatim transcription of the provided audio.\n",
     );
 
+    // Gemini has no language parameter, so the hint travels in the instruction.
+    match language.code() {
+        Some(code) => {
+            instruction.push_str("\nThe speech is in the language with ISO 639-1 code '");
+            instruction.push_str(code);
+            instruction.push_str("'. Transcribe it in that language without translating.\n");
+        }
+        None => instruction.push_str(
+            "\nTranscribe the speech in whatever language is spoken without translating.\n",
+        ),
+    }
+
     if !prompt.trim().is_empty() {
         instruction.push_str("\nTranscription style guidance: ");
         instruction.push_str(prompt.trim());
@@ -1,7 +1,11 @@
-use crate::config::GroqConfig;
+use crate::config::{Config, GroqConfig, TranscriptionProvider};
 use crate::transcription::audio::{encode_to_flac, EncodedAudio};
 use crate::transcription::postprocess::clean_transcription;
-use crate::transcription::{BackendMetrics, BackendPhaseProbe, TranscriptionResult};
+use crate::transcription::{
+    from_verbose_segments, request_settings_changed, BackendMetrics, BackendPhaseProbe,
+    TranscribeFuture, TranscribeOptions, Transcriber, TranscriptionLanguage, TranscriptionResult,
+    VerboseSegment,
+};
 use anyhow::{Context, Result};
 use reqwest::{multipart, Client, Url};
 use serde::Deserialize;
@@ -17,6 +21,7 @@ pub struct GroqTranscriber {
     api_key: String,
     model: String,
     prompt: String,
+    language: TranscriptionLanguage,
     request_timeout: Duration,
     max_retries: u32,
 }
@@ -28,6 +33,7 @@ impl GroqTranscriber {
         request_timeout: Duration,
         max_retries: u32,
         prompt: String,
+        language: TranscriptionLanguage,
     ) -> Result<Self> {
         let endpoint = Url::parse(&config.endpoint)
             .with_context(|| format!("Invalid Groq endpoint: {}", config.endpoint))?;
@@ -46,6 +52,7 @@ impl GroqTranscriber {
             api_key,
             model: config.model.clone(),
             prompt,
+            language,
             request_timeout,
             max_retries,
         })
@@ -57,8 +64,8 @@ impl GroqTranscriber {
         }
 
         info!(
-            "✅ Groq transcription ready (model: {}, timeout: {:?})",
-            self.model, self.request_timeout
+            "✅ Groq transcription ready (model: {}, language: {}, timeout: {:?})",
+            self.model, self.language, self.request_timeout
         );
         Ok(())
     }
@@ -67,11 +74,17 @@ impl GroqTranscriber {
         "Groq Whisper"
     }
 
-    pub async fn transcribe(&self, audio_data: Vec<f32>) -> Result<TranscriptionResult> {
+    pub async fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult> {
         if audio_data.is_empty() {
             return Ok(TranscriptionResult {
                 text: String::new(),
                 metrics: BackendMetrics::default(),
+                language: None,
+                segments: Vec::new(),
             });
         }
 
@@ -81,6 +94,9 @@ impl GroqTranscriber {
             "🧠 Transcribing {:.2}s of audio via Groq", duration_secs
         );
 
+        let prompt = options.prompt(&TranscriptionProvider::Groq, &self.prompt);
+        let language = options.language(&TranscriptionProvider::Groq, &self.language);
+
         let mut phases = Vec::new();
         let (encoded_result, mut encode_phase) = BackendPhaseProbe::measure_async(
             "backend.encode.flac",
@@ -96,14 +112,15 @@ impl GroqTranscriber {
 
         let (send_result, mut send_phase) =
             BackendPhaseProbe::measure_async("backend.groq.request", Some(encoded_len), || {
-                self.send_with_retry(&encoded)
+                self.send_with_retry(&encoded, &prompt, &language)
             })
             .await;
-        let (raw, timings) = send_result?;
+        let (payload, timings) = send_result?;
+        let raw = payload.text.unwrap_or_default();
         send_phase.set_bytes_out(raw.len());
         let transcription_duration = send_phase.wall_duration;
         phases.push(send_phase);
-        let cleaned = clean_transcription(&raw, &self.prompt);
+        let cleaned = clean_transcription(&raw, &prompt);
 
         if cleaned.is_empty() {
             warn!("Groq returned empty or non-speech transcription");
@@ -118,19 +135,28 @@ impl GroqTranscriber {
             response_duration: Some(timings.response),
             transcription_duration,
             phases,
+            fallback_attempts: Vec::new(),
+            served_by: None,
         };
 
         Ok(TranscriptionResult {
             text: cleaned,
             metrics,
+            language: payload.language,
+            segments: from_verbose_segments(payload.segments),
         })
     }
 
-    async fn send_with_retry(&self, audio: &EncodedAudio) -> Result<(String, NetworkTimings)> {
+    async fn send_with_retry(
+        &self,
+        audio: &EncodedAudio,
+        prompt: &str,
+        language: &TranscriptionLanguage,
+    ) -> Result<(GroqTranscriptionResponse, NetworkTimings)> {
         let attempts = cmp::max(1, self.max_retries.saturating_add(1));
 
         for attempt in 0..attempts {
-            match self.send_once(audio).await {
+            match self.send_once(audio, prompt, language).await {
                 Ok(result) => return Ok(result),
                 Err(err) => {
                     let is_last_attempt = attempt + 1 == attempts;
@@ -154,14 +180,24 @@ impl GroqTranscriber {
         Err(anyhow::anyhow!("Unknown Groq transcription failure"))
     }
 
-    async fn send_once(&self, audio: &EncodedAudio) -> Result<(String, NetworkTimings)> {
+    async fn send_once(
+        &self,
+        audio: &EncodedAudio,
+        prompt: &str,
+        language: &TranscriptionLanguage,
+    ) -> Result<(GroqTranscriptionResponse, NetworkTimings)> {
+        // verbose_json carries segment timings and the detected language.
         let mut form = multipart::Form::new()
             .text("model", self.model.clone())
-            .text("response_format", "json".to_string())
+            .text("response_format", "verbose_json")
             .text("temperature", "0");
 
-        if !self.prompt.trim().is_empty() {
-            form = form.text("prompt", self.prompt.clone());
+        if let Some(code) = language.code() {
+            form = form.text("language", code.to_string());
+        }
+
+        if !prompt.trim().is_empty() {
+            form = form.text("prompt", prompt.to_string());
         }
 
         let file_part = multipart::Part::stream(audio.data.clone())
@@ -191,7 +227,7 @@ impl GroqTranscriber {
                 .context("Failed to deserialize Groq transcription response")?;
             let response_duration = parse_start.elapsed();
             return Ok((
-                payload.text.unwrap_or_default(),
+                payload,
                 NetworkTimings {
                     upload: upload_duration,
                     response: response_duration,
@@ -214,6 +250,31 @@ impl GroqTranscriber {
     }
 }
 
+impl Transcriber for GroqTranscriber {
+    fn initialize(&self) -> Result<()> {
+        GroqTranscriber::initialize(self)
+    }
+
+    fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> TranscribeFuture<'_> {
+        Box::pin(GroqTranscriber::transcribe_with(self, audio_data, options))
+    }
+
+    fn provider(&self) -> TranscriptionProvider {
+        TranscriptionProvider::Groq
+    }
+
+    fn needs_refresh(&self, current: &Config, new: &Config) -> bool {
+        let (current_groq, new_groq) = (&current.transcription.groq, &new.transcription.groq);
+        request_settings_changed(current, new)
+            || current_groq.endpoint != new_groq.endpoint
+            || current_groq.model != new_groq.model
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct NetworkTimings {
     upload: Duration,
@@ -223,6 +284,9 @@ struct NetworkTimings {
 #[derive(Debug, Deserialize, Default)]
 struct GroqTranscriptionResponse {
     text: Option<String>,
+    language: Option<String>,
+    #[serde(default)]
+    segments: Vec<VerboseSegment>,
 }
 
 #[derive(Debug, Deserialize, Default)]
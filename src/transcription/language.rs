@@ -0,0 +1,127 @@
+use crate::config::{Config, TranscriptionProvider};
+use std::fmt;
+
+pub const AUTO_LANGUAGE: &str = "auto";
+
+/// Spoken language requested from a transcription backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptionLanguage {
+    /// Let the backend detect the language.
+    Auto,
+    /// Lowercased ISO 639-1 code such as `en` or `de`.
+    Code(String),
+}
+
+impl TranscriptionLanguage {
+    pub fn parse(value: &str) -> Self {
+        let trimmed = value.trim();
+        if trimmed.is_empty() || trimmed.eq_ignore_ascii_case(AUTO_LANGUAGE) {
+            Self::Auto
+        } else {
+            Self::Code(trimmed.to_ascii_lowercase())
+        }
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            Self::Auto => None,
+            Self::Code(code) => Some(code),
+        }
+    }
+
+    pub fn is_auto(&self) -> bool {
+        matches!(self, Self::Auto)
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.code().unwrap_or(AUTO_LANGUAGE)
+    }
+}
+
+impl fmt::Display for TranscriptionLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Resolves the language for `provider`, preferring its own override over
+/// `transcription.language`.
+pub(crate) fn provider_language(
+    config: &Config,
+    provider: &TranscriptionProvider,
+) -> TranscriptionLanguage {
+    let transcription = &config.transcription;
+    let override_value = match provider {
+        TranscriptionProvider::WhisperCpp => transcription.whisper_cpp.language.as_deref(),
+        TranscriptionProvider::Groq => transcription.groq.language.as_deref(),
+        TranscriptionProvider::Gemini => transcription.gemini.language.as_deref(),
+        TranscriptionProvider::Parakeet => transcription.parakeet.language.as_deref(),
+        TranscriptionProvider::Custom(name) => transcription
+            .custom
+            .get(name)
+            .and_then(|custom| custom.language.as_deref()),
+    };
+
+    TranscriptionLanguage::parse(
+        override_value
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or(&transcription.language),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CustomProviderConfig;
+
+    #[test]
+    fn parses_codes_and_auto() {
+        assert_eq!(
+            TranscriptionLanguage::parse("auto"),
+            TranscriptionLanguage::Auto
+        );
+        assert_eq!(
+            TranscriptionLanguage::parse(" AUTO "),
+            TranscriptionLanguage::Auto
+        );
+        assert_eq!(
+            TranscriptionLanguage::parse(""),
+            TranscriptionLanguage::Auto
+        );
+        assert_eq!(
+            TranscriptionLanguage::parse(" DE "),
+            TranscriptionLanguage::Code("de".to_string())
+        );
+    }
+
+    #[test]
+    fn provider_override_beats_global_language() {
+        let mut config = Config::default();
+        config.transcription.language = "fr".to_string();
+        config.transcription.groq.language = Some("auto".to_string());
+        config.transcription.custom.insert(
+            "remote".to_string(),
+            CustomProviderConfig {
+                language: Some("de".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            provider_language(&config, &TranscriptionProvider::WhisperCpp),
+            TranscriptionLanguage::Code("fr".to_string())
+        );
+        assert_eq!(
+            provider_language(&config, &TranscriptionProvider::Groq),
+            TranscriptionLanguage::Auto
+        );
+        assert_eq!(
+            provider_language(
+                &config,
+                &TranscriptionProvider::Custom("remote".to_string())
+            ),
+            TranscriptionLanguage::Code("de".to_string())
+        );
+    }
+}
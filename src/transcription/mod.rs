@@ -1,42 +1,43 @@
 mod audio;
+mod backend;
 mod custom_openai;
+mod fallback;
 mod gemini;
 mod groq;
+mod language;
 #[cfg(feature = "parakeet")]
 mod parakeet;
 mod phase_metrics;
 mod postprocess;
 mod prompt;
+mod segments;
 
-use crate::config::{Config, ConfigManager, CustomProviderKind, TranscriptionProvider};
-#[cfg(feature = "parakeet")]
-use crate::paths::expand_tilde;
-use crate::whisper::{WhisperManager, WhisperVadOptions};
-#[cfg(not(feature = "parakeet"))]
-use anyhow::bail;
-use anyhow::{Context, Result};
-use std::env;
 use std::time::Duration;
 
 pub use audio::{encode_to_flac, encode_to_wav, EncodedAudio};
+pub(crate) use backend::request_settings_changed;
+pub use backend::{
+    TranscribeFuture, TranscribeOptions, Transcriber, TranscriberContext, TranscriberFactory,
+    TranscriberRegistry,
+};
 pub use custom_openai::CustomOpenAiTranscriber;
+pub(crate) use custom_openai::{is_absolute_endpoint, resolve_endpoint};
+pub use fallback::FallbackTranscriber;
 pub use gemini::GeminiTranscriber;
 pub use groq::GroqTranscriber;
+pub(crate) use language::provider_language;
+pub use language::{TranscriptionLanguage, AUTO_LANGUAGE};
 #[cfg(feature = "parakeet")]
 pub use parakeet::ParakeetTranscriber;
 pub(crate) use phase_metrics::BackendPhaseProbe;
 pub use phase_metrics::{BackendPhaseMetric, BackendResourceDelta};
-pub use postprocess::{clean_transcription, contains_only_non_speech_markers, is_prompt_artifact};
+pub use postprocess::{
+    clean_transcription, contains_only_non_speech_markers, is_prompt_artifact, AudioLevel,
+    HallucinationFilter,
+};
 pub use prompt::{PromptBlueprint, DEFAULT_PROMPT};
-
-pub enum TranscriptionBackend {
-    Whisper(WhisperManager),
-    Groq(GroqTranscriber),
-    Gemini(GeminiTranscriber),
-    CustomOpenAi(CustomOpenAiTranscriber, String),
-    #[cfg(feature = "parakeet")]
-    Parakeet(ParakeetTranscriber),
-}
+pub use segments::TranscriptSegment;
+pub(crate) use segments::{from_verbose_segments, verbose_segments_from_value, VerboseSegment};
 
 #[derive(Debug, Clone, Default)]
 pub struct BackendMetrics {
@@ -46,205 +47,25 @@ pub struct BackendMetrics {
     pub response_duration: Option<Duration>,
     pub transcription_duration: Duration,
     pub phases: Vec<BackendPhaseMetric>,
+    /// Providers that failed before the one that produced the text.
+    pub fallback_attempts: Vec<FallbackAttempt>,
+    /// Label of the fallback provider that produced the text, if not the primary.
+    pub served_by: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FallbackAttempt {
+    pub provider_label: String,
+    pub duration: Duration,
+    pub error: String,
 }
 
 #[derive(Debug, Clone)]
 pub struct TranscriptionResult {
     pub text: String,
     pub metrics: BackendMetrics,
-}
-
-impl TranscriptionBackend {
-    pub fn build(
-        config_manager: &ConfigManager,
-        config: &Config,
-        vad: WhisperVadOptions,
-    ) -> Result<Self> {
-        let timeout = Duration::from_secs(config.transcription.request_timeout_secs.max(5));
-        let retries = config.transcription.max_retries;
-
-        match &config.transcription.provider {
-            TranscriptionProvider::WhisperCpp => {
-                let prompt = Self::prompt_for(config, &TranscriptionProvider::WhisperCpp);
-                let whisper_cfg = &config.transcription.whisper_cpp;
-                let whisper_binaries =
-                    config_manager.get_whisper_binary_candidates(whisper_cfg.fallback_cli);
-                let manager = WhisperManager::new(
-                    config_manager.get_model_path()?,
-                    whisper_binaries,
-                    whisper_cfg.threads,
-                    prompt,
-                    config_manager.get_temp_dir(),
-                    whisper_cfg.gpu_layers,
-                    vad,
-                    whisper_cfg.no_speech_threshold,
-                )?;
-                Ok(Self::Whisper(manager))
-            }
-            TranscriptionProvider::Groq => {
-                let prompt = Self::prompt_for(config, &TranscriptionProvider::Groq);
-                let api_key = env::var("GROQ_API_KEY")
-                    .context("GROQ_API_KEY environment variable is not set")?;
-                let provider = GroqTranscriber::new(
-                    api_key,
-                    &config.transcription.groq,
-                    timeout,
-                    retries,
-                    prompt,
-                )?;
-                Ok(Self::Groq(provider))
-            }
-            TranscriptionProvider::Gemini => {
-                let prompt = Self::prompt_for(config, &TranscriptionProvider::Gemini);
-                let api_key = env::var("GEMINI_API_KEY")
-                    .context("GEMINI_API_KEY environment variable is not set")?;
-                let provider = GeminiTranscriber::new(
-                    api_key,
-                    &config.transcription.gemini,
-                    timeout,
-                    retries,
-                    prompt,
-                )?;
-                Ok(Self::Gemini(provider))
-            }
-            TranscriptionProvider::Parakeet => {
-                #[cfg(feature = "parakeet")]
-                {
-                    let prompt = Self::prompt_for(config, &TranscriptionProvider::Parakeet);
-                    let par_cfg = &config.transcription.parakeet;
-
-                    let expanded = expand_tilde(&par_cfg.model_dir);
-                    let model_dir = if expanded.is_relative() {
-                        directories::ProjectDirs::from("", "", "hyprwhspr-rs")
-                            .map(|dirs| dirs.data_dir().join(&expanded))
-                            .unwrap_or(expanded)
-                    } else {
-                        expanded
-                    };
-
-                    let provider = ParakeetTranscriber::new(par_cfg, model_dir, prompt)?;
-                    Ok(Self::Parakeet(provider))
-                }
-                #[cfg(not(feature = "parakeet"))]
-                {
-                    bail!("Parakeet backend is disabled in this build. Rebuild with --features parakeet.")
-                }
-            }
-            TranscriptionProvider::Custom(name) => {
-                let custom_cfg = config.transcription.custom.get(name).ok_or_else(|| {
-                    anyhow::anyhow!("custom transcription provider '{name}' is not configured")
-                })?;
-                let prompt = Self::prompt_for(config, &TranscriptionProvider::Custom(name.clone()));
-
-                match custom_cfg.kind {
-                    CustomProviderKind::OpenAiAudioTranscriptions => {
-                        let provider = CustomOpenAiTranscriber::new(
-                            name, custom_cfg, timeout, retries, prompt,
-                        )?;
-                        Ok(Self::CustomOpenAi(provider, name.clone()))
-                    }
-                }
-            }
-        }
-    }
-
-    pub fn initialize(&self) -> Result<()> {
-        match self {
-            TranscriptionBackend::Whisper(manager) => manager.initialize(),
-            TranscriptionBackend::Groq(provider) => provider.initialize(),
-            TranscriptionBackend::Gemini(provider) => provider.initialize(),
-            TranscriptionBackend::CustomOpenAi(provider, _) => provider.initialize(),
-            #[cfg(feature = "parakeet")]
-            TranscriptionBackend::Parakeet(provider) => provider.initialize(),
-        }
-    }
-
-    pub fn provider(&self) -> TranscriptionProvider {
-        match self {
-            TranscriptionBackend::Whisper(_) => TranscriptionProvider::WhisperCpp,
-            TranscriptionBackend::Groq(_) => TranscriptionProvider::Groq,
-            TranscriptionBackend::Gemini(_) => TranscriptionProvider::Gemini,
-            TranscriptionBackend::CustomOpenAi(_, name) => {
-                TranscriptionProvider::Custom(name.clone())
-            }
-            #[cfg(feature = "parakeet")]
-            TranscriptionBackend::Parakeet(_) => TranscriptionProvider::Parakeet,
-        }
-    }
-
-    pub fn needs_refresh(current: &Config, new: &Config) -> bool {
-        if current.transcription.provider != new.transcription.provider {
-            return true;
-        }
-
-        match &new.transcription.provider {
-            TranscriptionProvider::WhisperCpp => {
-                current.transcription.whisper_cpp != new.transcription.whisper_cpp
-            }
-            TranscriptionProvider::Groq => {
-                current.transcription.request_timeout_secs != new.transcription.request_timeout_secs
-                    || current.transcription.max_retries != new.transcription.max_retries
-                    || current.transcription.groq != new.transcription.groq
-                    || Self::prompt_for(current, &TranscriptionProvider::Groq)
-                        != Self::prompt_for(new, &TranscriptionProvider::Groq)
-            }
-            TranscriptionProvider::Gemini => {
-                current.transcription.request_timeout_secs != new.transcription.request_timeout_secs
-                    || current.transcription.max_retries != new.transcription.max_retries
-                    || current.transcription.gemini != new.transcription.gemini
-                    || Self::prompt_for(current, &TranscriptionProvider::Gemini)
-                        != Self::prompt_for(new, &TranscriptionProvider::Gemini)
-            }
-            TranscriptionProvider::Parakeet => {
-                current.transcription.parakeet != new.transcription.parakeet
-                    || Self::prompt_for(current, &TranscriptionProvider::Parakeet)
-                        != Self::prompt_for(new, &TranscriptionProvider::Parakeet)
-            }
-            TranscriptionProvider::Custom(name) => {
-                current.transcription.request_timeout_secs != new.transcription.request_timeout_secs
-                    || current.transcription.max_retries != new.transcription.max_retries
-                    || current.transcription.custom.get(name) != new.transcription.custom.get(name)
-                    || Self::prompt_for(current, &TranscriptionProvider::Custom(name.clone()))
-                        != Self::prompt_for(new, &TranscriptionProvider::Custom(name.clone()))
-            }
-        }
-    }
-
-    pub async fn transcribe(&self, audio_data: Vec<f32>) -> Result<TranscriptionResult> {
-        match self {
-            TranscriptionBackend::Whisper(manager) => manager.transcribe(audio_data).await,
-            TranscriptionBackend::Groq(provider) => provider.transcribe(audio_data).await,
-            TranscriptionBackend::Gemini(provider) => provider.transcribe(audio_data).await,
-            TranscriptionBackend::CustomOpenAi(provider, _) => {
-                provider.transcribe(audio_data).await
-            }
-            #[cfg(feature = "parakeet")]
-            TranscriptionBackend::Parakeet(provider) => provider.transcribe(audio_data).await,
-        }
-    }
-}
-
-impl TranscriptionBackend {
-    fn prompt_for(config: &Config, provider: &TranscriptionProvider) -> String {
-        match provider {
-            TranscriptionProvider::WhisperCpp => {
-                PromptBlueprint::from(config.transcription.whisper_cpp.prompt.as_str()).resolve()
-            }
-            TranscriptionProvider::Groq => {
-                PromptBlueprint::from(config.transcription.groq.prompt.as_str()).resolve()
-            }
-            TranscriptionProvider::Gemini => {
-                PromptBlueprint::from(config.transcription.gemini.prompt.as_str()).resolve()
-            }
-            TranscriptionProvider::Parakeet => {
-                PromptBlueprint::from(config.transcription.parakeet.prompt.as_str()).resolve()
-            }
-            TranscriptionProvider::Custom(name) => config
-                .transcription
-                .custom
-                .get(name)
-                .map(|custom| PromptBlueprint::from(custom.prompt.as_str()).resolve())
-                .unwrap_or_else(String::new),
-        }
-    }
+    /// Language reported by the backend when it detected one.
+    pub language: Option<String>,
+    /// Timed segments with confidence, when the backend reports them.
+    pub segments: Vec<TranscriptSegment>,
 }
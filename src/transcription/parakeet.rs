@@ -1,13 +1,16 @@
-use crate::config::ParakeetConfig;
+use crate::config::{Config, ParakeetConfig, TranscriptionProvider};
 use crate::transcription::postprocess::clean_transcription;
-use crate::transcription::{BackendMetrics, TranscriptionResult};
+use crate::transcription::{
+    BackendMetrics, TranscribeFuture, TranscribeOptions, TranscriptSegment, TranscriptionLanguage,
+    TranscriptionResult,
+};
 use anyhow::{Context, Result};
-use parakeet_rs::{ParakeetTDT, TimestampMode, Transcriber};
+use parakeet_rs::{ParakeetTDT, TimestampMode, Transcriber as _};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::Mutex;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 #[derive(Clone)]
 pub struct ParakeetTranscriber {
@@ -17,7 +20,20 @@ pub struct ParakeetTranscriber {
 }
 
 impl ParakeetTranscriber {
-    pub fn new(_config: &ParakeetConfig, model_dir: PathBuf, prompt: String) -> Result<Self> {
+    pub fn new(
+        _config: &ParakeetConfig,
+        model_dir: PathBuf,
+        prompt: String,
+        language: TranscriptionLanguage,
+    ) -> Result<Self> {
+        // Parakeet TDT v3 detects the spoken language itself and has no hint input.
+        if let Some(code) = language.code() {
+            debug!(
+                language = code,
+                "Parakeet TDT ignores the configured language and auto-detects instead"
+            );
+        }
+
         let model = ParakeetTDT::from_pretrained(&model_dir, None).with_context(|| {
             format!(
                 "Failed to load Parakeet TDT model from {}",
@@ -71,11 +87,17 @@ impl ParakeetTranscriber {
         "Parakeet TDT (NVIDIA)"
     }
 
-    pub async fn transcribe(&self, audio_data: Vec<f32>) -> Result<TranscriptionResult> {
+    pub async fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult> {
         if audio_data.is_empty() {
             return Ok(TranscriptionResult {
                 text: String::new(),
                 metrics: BackendMetrics::default(),
+                language: None,
+                segments: Vec::new(),
             });
         }
 
@@ -87,14 +109,28 @@ impl ParakeetTranscriber {
 
         let transcribe_start = Instant::now();
         let model = self.model.clone();
-        let prompt = self.prompt.clone();
+        let prompt = options.prompt(&TranscriptionProvider::Parakeet, &self.prompt);
 
-        let raw_text = tokio::task::spawn_blocking(move || -> Result<String> {
+        let (raw_text, segments) = tokio::task::spawn_blocking(move || {
             let mut guard = model.blocking_lock();
             let result = guard
                 .transcribe_samples(audio_data, 16_000, 1, Some(TimestampMode::Sentences))
                 .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?;
-            Ok(result.text)
+            // Sentence mode groups the timed tokens into one entry per sentence.
+            let segments = result
+                .tokens
+                .iter()
+                .map(|token| {
+                    TranscriptSegment::from_secs(
+                        f64::from(token.start),
+                        f64::from(token.end),
+                        &token.text,
+                        None,
+                    )
+                })
+                .filter(|segment| !segment.text.is_empty())
+                .collect::<Vec<_>>();
+            Ok::<_, anyhow::Error>((result.text, segments))
         })
         .await
         .context("Parakeet TDT worker panicked")??;
@@ -115,11 +151,39 @@ impl ParakeetTranscriber {
             response_duration: None,
             transcription_duration,
             phases: Vec::new(),
+            fallback_attempts: Vec::new(),
+            served_by: None,
         };
 
         Ok(TranscriptionResult {
             text: cleaned,
             metrics,
+            language: None,
+            segments,
         })
     }
 }
+
+impl crate::transcription::Transcriber for ParakeetTranscriber {
+    fn initialize(&self) -> Result<()> {
+        ParakeetTranscriber::initialize(self)
+    }
+
+    fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> TranscribeFuture<'_> {
+        Box::pin(ParakeetTranscriber::transcribe_with(
+            self, audio_data, options,
+        ))
+    }
+
+    fn provider(&self) -> TranscriptionProvider {
+        TranscriptionProvider::Parakeet
+    }
+
+    fn needs_refresh(&self, current: &Config, new: &Config) -> bool {
+        current.transcription.parakeet.model_dir != new.transcription.parakeet.model_dir
+    }
+}
@@ -1,7 +1,41 @@
+use crate::config::HallucinationFilterConfig;
 use regex::Regex;
+use std::time::Duration;
+use tracing::debug;
 
 const NON_SPEECH_MARKERS: &[&str] = &["BLANK_AUDIO", "INAUDIBLE", "NO_SPEECH", "SILENCE"];
 
+/// Sign-offs from subtitled videos that Whisper emits on silence or noise.
+const PHANTOM_PHRASES: &[&str] = &[
+    "thank you for watching",
+    "thanks for watching",
+    "thank you so much for watching",
+    "thank you very much for watching",
+    "thanks for watching and see you next time",
+    "please subscribe",
+    "please like and subscribe",
+    "like and subscribe",
+    "don't forget to like and subscribe",
+    "please subscribe to my channel",
+    "see you in the next video",
+];
+
+/// Credit lines; dropped only when they are the final sentence.
+const PHANTOM_PREFIXES: &[&str] = &[
+    "subtitles by",
+    "subtitles made by",
+    "captions by",
+    "transcription by",
+    "translated by",
+];
+
+/// Rest of a credit sentence up to the end of the text. Periods inside a word
+/// ("Amara.org") do not end the sentence.
+const CREDIT_TAIL: &str = r"\b(?:[^.!?]|[.!?]+[^.!?\s])*[.!?]*\s*$";
+
+/// Longest word sequence checked for repetition loops.
+const MAX_LOOP_WORDS: usize = 8;
+
 pub fn clean_transcription(transcription: &str, prompt: &str) -> String {
     let trimmed = transcription.trim();
     if trimmed.is_empty() {
@@ -79,3 +113,312 @@ pub fn is_prompt_artifact(transcription: &str, prompt: &str) -> bool {
 
     false
 }
+
+/// Length and loudness of the audio that produced a transcript.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioLevel {
+    pub duration: Duration,
+    pub rms: f32,
+}
+
+impl AudioLevel {
+    pub fn measure(samples: &[f32], sample_rate: u32) -> Self {
+        let duration = if sample_rate == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(samples.len() as f64 / sample_rate as f64)
+        };
+        let rms = if samples.is_empty() {
+            0.0
+        } else {
+            let sum: f64 = samples.iter().map(|s| f64::from(*s) * f64::from(*s)).sum();
+            (sum / samples.len() as f64).sqrt() as f32
+        };
+        Self { duration, rms }
+    }
+}
+
+/// Removes text Whisper-style models invent on near-silent input: video
+/// sign-offs, subtitle credits and repetition loops.
+#[derive(Debug, Clone)]
+pub struct HallucinationFilter {
+    enabled: bool,
+    trailing_patterns: Vec<Regex>,
+    max_repeats: Option<usize>,
+    min_audio: Duration,
+    min_rms: f32,
+}
+
+impl HallucinationFilter {
+    pub fn new(config: &HallucinationFilterConfig) -> Self {
+        let phrases = PHANTOM_PHRASES
+            .iter()
+            .copied()
+            .chain(config.phrases.iter().map(String::as_str))
+            .map(str::trim)
+            .filter(|phrase| !phrase.is_empty())
+            .map(|phrase| trailing_pattern(phrase, r"[\s.!?]*$"));
+        let prefixes = PHANTOM_PREFIXES
+            .iter()
+            .map(|prefix| trailing_pattern(prefix, CREDIT_TAIL));
+
+        Self {
+            enabled: config.enabled,
+            trailing_patterns: phrases.chain(prefixes).flatten().collect(),
+            max_repeats: (config.max_repeats > 0).then_some(config.max_repeats),
+            min_audio: Duration::from_millis(u64::from(config.min_audio_ms)),
+            min_rms: config.min_rms.max(0.0),
+        }
+    }
+
+    pub fn filter(&self, text: &str, audio: AudioLevel) -> String {
+        let trimmed = text.trim();
+        if !self.enabled || trimmed.is_empty() {
+            return trimmed.to_string();
+        }
+
+        if audio.duration < self.min_audio || audio.rms < self.min_rms {
+            debug!(
+                "Dropping transcription from {:?} of audio at RMS {:.4}: '{}'",
+                audio.duration, audio.rms, trimmed
+            );
+            return String::new();
+        }
+
+        let mut filtered = trimmed.to_string();
+        loop {
+            let stripped = self.strip_trailing_phantom(&filtered);
+            if stripped == filtered {
+                break;
+            }
+            debug!("Removed phantom phrase: '{}' -> '{}'", filtered, stripped);
+            filtered = stripped;
+        }
+
+        let Some(max_repeats) = self.max_repeats else {
+            return filtered;
+        };
+        let collapsed = collapse_repetition_loops(&filtered, max_repeats);
+        if collapsed != filtered {
+            debug!(
+                "Collapsed repetition loop: '{}' -> '{}'",
+                filtered, collapsed
+            );
+        }
+        collapsed
+    }
+
+    fn strip_trailing_phantom(&self, text: &str) -> String {
+        for pattern in &self.trailing_patterns {
+            if let Some(captures) = pattern.captures(text) {
+                let start = captures.get(0).map_or(0, |m| m.start());
+                let boundary = captures.get(1).map_or("", |m| m.as_str());
+                return format!("{}{}", &text[..start], boundary).trim().to_string();
+            }
+        }
+        text.to_string()
+    }
+}
+
+/// Matches `phrase` as the whole text or as the sentence that ends it.
+fn trailing_pattern(phrase: &str, tail: &str) -> Option<Regex> {
+    let words: Vec<String> = phrase.split_whitespace().map(regex::escape).collect();
+    let pattern = format!(r"(?i)(^|[.!?])\s*{}{}", words.join(r"\s+"), tail);
+    Regex::new(&pattern).ok()
+}
+
+fn collapse_repetition_loops(text: &str, max_repeats: usize) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let keys: Vec<String> = words
+        .iter()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .collect();
+
+    let mut kept: Vec<&str> = Vec::with_capacity(words.len());
+    let mut collapsed = false;
+    let mut index = 0;
+
+    'words: while index < words.len() {
+        for width in 1..=MAX_LOOP_WORDS {
+            if index + width * (max_repeats + 1) > words.len() {
+                break;
+            }
+            let unit = &keys[index..index + width];
+            if unit.iter().all(String::is_empty) {
+                continue;
+            }
+            let mut repeats = 1;
+            while index + (repeats + 1) * width <= words.len()
+                && &keys[index + repeats * width..index + (repeats + 1) * width] == unit
+            {
+                repeats += 1;
+            }
+            if repeats > max_repeats {
+                kept.extend_from_slice(&words[index..index + width]);
+                index += repeats * width;
+                collapsed = true;
+                continue 'words;
+            }
+        }
+        kept.push(words[index]);
+        index += 1;
+    }
+
+    if collapsed {
+        kept.join(" ")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter() -> HallucinationFilter {
+        HallucinationFilter::new(&HallucinationFilterConfig::default())
+    }
+
+    fn speech() -> AudioLevel {
+        AudioLevel {
+            duration: Duration::from_secs(3),
+            rms: 0.1,
+        }
+    }
+
+    #[test]
+    fn drops_transcripts_that_are_only_phantom_phrases() {
+        assert_eq!(filter().filter("Thank you for watching!", speech()), "");
+        assert_eq!(
+            filter().filter("Subtitles by the Amara.org community", speech()),
+            ""
+        );
+        assert_eq!(
+            filter().filter("Thanks for watching. Please subscribe.", speech()),
+            ""
+        );
+    }
+
+    #[test]
+    fn strips_trailing_phantom_sentence_but_keeps_dictation() {
+        assert_eq!(
+            filter().filter("Ship the release today. Thank you for watching.", speech()),
+            "Ship the release today."
+        );
+        assert_eq!(
+            filter().filter("Thank you for watching the build for me.", speech()),
+            "Thank you for watching the build for me."
+        );
+        assert_eq!(filter().filter("Thank you.", speech()), "Thank you.");
+    }
+
+    #[test]
+    fn keeps_credit_prefixes_that_do_not_end_the_transcript() {
+        assert_eq!(
+            filter().filter("Translated by DeepL. Please review the draft.", speech()),
+            "Translated by DeepL. Please review the draft."
+        );
+        assert_eq!(
+            filter().filter("Transcription by hand is slow. Let's script it.", speech()),
+            "Transcription by hand is slow. Let's script it."
+        );
+        assert_eq!(
+            filter().filter("Send the draft. Translated by DeepL.", speech()),
+            "Send the draft."
+        );
+    }
+
+    #[test]
+    fn configured_phrases_extend_the_builtin_list() {
+        let config = HallucinationFilterConfig {
+            phrases: vec!["Ciao a tutti".to_string()],
+            ..Default::default()
+        };
+        let filter = HallucinationFilter::new(&config);
+        assert_eq!(filter.filter("Ok. Ciao a tutti.", speech()), "Ok.");
+    }
+
+    #[test]
+    fn collapses_repetition_loops() {
+        let filter = || {
+            HallucinationFilter::new(&HallucinationFilterConfig {
+                max_repeats: 3,
+                ..Default::default()
+            })
+        };
+        assert_eq!(
+            filter().filter(
+                "I will go. I will go. I will go. I will go. I will go.",
+                speech()
+            ),
+            "I will go."
+        );
+        assert_eq!(
+            filter().filter("so so so so so what now", speech()),
+            "so what now"
+        );
+        assert_eq!(
+            filter().filter("no no no, that is fine", speech()),
+            "no no no, that is fine"
+        );
+    }
+
+    #[test]
+    fn drops_text_from_short_or_silent_audio() {
+        let short = AudioLevel {
+            duration: Duration::from_millis(120),
+            rms: 0.1,
+        };
+        let silent = AudioLevel {
+            duration: Duration::from_secs(2),
+            rms: 0.0005,
+        };
+        let gated = HallucinationFilter::new(&HallucinationFilterConfig {
+            min_audio_ms: 250,
+            min_rms: 0.002,
+            ..Default::default()
+        });
+        assert_eq!(gated.filter("you", short), "");
+        assert_eq!(gated.filter("Thank you.", silent), "");
+    }
+
+    #[test]
+    fn defaults_only_remove_phantom_phrases() {
+        let quiet = AudioLevel {
+            duration: Duration::from_millis(120),
+            rms: 0.0005,
+        };
+        assert_eq!(filter().filter("Yes.", quiet), "Yes.");
+        assert_eq!(
+            filter().filter("so so so so so what now", speech()),
+            "so so so so so what now"
+        );
+        assert_eq!(filter().filter("Thank you for watching!", quiet), "");
+    }
+
+    #[test]
+    fn disabled_filter_passes_text_through() {
+        let config = HallucinationFilterConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        let filter = HallucinationFilter::new(&config);
+        assert_eq!(
+            filter.filter(
+                " Thank you for watching. ",
+                AudioLevel::measure(&[], 16_000)
+            ),
+            "Thank you for watching."
+        );
+    }
+
+    #[test]
+    fn audio_level_measures_duration_and_rms() {
+        let level = AudioLevel::measure(&[0.5, -0.5, 0.5, -0.5], 4);
+        assert_eq!(level.duration, Duration::from_secs(1));
+        assert!((level.rms - 0.5).abs() < 1e-6);
+    }
+}
@@ -1,8 +1,16 @@
+use crate::config::{Config, TranscriptionProvider};
+
 pub const DEFAULT_PROMPT: &str = "Transcribe with proper capitalization, including sentence beginnings, proper nouns, titles, and standard English capitalization rules.";
 
+/// Prompt budget of Whisper-style decoders, which keep only the last 224
+/// tokens of the initial prompt.
+const WHISPER_PROMPT_TOKEN_LIMIT: usize = 224;
+
 pub struct PromptBlueprint<'a> {
     candidate: Option<&'a str>,
     fallback: &'a str,
+    glossary: Vec<&'a str>,
+    token_limit: Option<usize>,
 }
 
 impl<'a> PromptBlueprint<'a> {
@@ -10,25 +18,146 @@ impl<'a> PromptBlueprint<'a> {
         Self {
             candidate,
             fallback,
+            glossary: Vec::new(),
+            token_limit: None,
         }
     }
 
     pub fn from(candidate: &'a str) -> Self {
-        Self {
-            candidate: Some(candidate),
-            fallback: DEFAULT_PROMPT,
-        }
+        Self::new(Some(candidate), DEFAULT_PROMPT)
     }
 
     pub fn with_default(candidate: Option<&'a str>) -> Self {
-        Self {
-            candidate,
-            fallback: DEFAULT_PROMPT,
-        }
+        Self::new(candidate, DEFAULT_PROMPT)
+    }
+
+    /// Appends vocabulary terms as a glossary. Terms are added in order
+    /// while the whole prompt stays within `token_limit`.
+    pub fn with_glossary(
+        mut self,
+        terms: impl IntoIterator<Item = &'a str>,
+        token_limit: Option<usize>,
+    ) -> Self {
+        self.glossary = terms
+            .into_iter()
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+            .collect();
+        self.token_limit = token_limit;
+        self
     }
 
     pub fn resolve(self) -> String {
         let chosen = self.candidate.unwrap_or(self.fallback);
-        chosen.trim().to_string()
+        let mut prompt = chosen.trim().to_string();
+        if self.glossary.is_empty() {
+            return prompt;
+        }
+
+        let mut terms: Vec<&str> = Vec::new();
+        for term in self.glossary {
+            terms.push(term);
+            let candidate = with_glossary_line(&prompt, &terms);
+            if self
+                .token_limit
+                .is_some_and(|limit| estimated_tokens(&candidate) > limit)
+            {
+                terms.pop();
+                break;
+            }
+        }
+        if !terms.is_empty() {
+            prompt = with_glossary_line(&prompt, &terms);
+        }
+        prompt
+    }
+}
+
+fn with_glossary_line(prompt: &str, terms: &[&str]) -> String {
+    let glossary = format!("Glossary: {}.", terms.join(", "));
+    if prompt.is_empty() {
+        glossary
+    } else {
+        format!("{prompt} {glossary}")
+    }
+}
+
+/// Rough BPE token count. Names and jargon split into more pieces than
+/// ordinary English, so this errs on the high side.
+fn estimated_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(3)
+}
+
+pub(crate) fn provider_prompt(config: &Config, provider: &TranscriptionProvider) -> String {
+    let glossary = || config.vocabulary.iter().map(|entry| entry.term.as_str());
+    match provider {
+        TranscriptionProvider::WhisperCpp => {
+            PromptBlueprint::from(config.transcription.whisper_cpp.prompt.as_str())
+                .with_glossary(glossary(), Some(WHISPER_PROMPT_TOKEN_LIMIT))
+                .resolve()
+        }
+        TranscriptionProvider::Groq => {
+            PromptBlueprint::from(config.transcription.groq.prompt.as_str())
+                .with_glossary(glossary(), Some(WHISPER_PROMPT_TOKEN_LIMIT))
+                .resolve()
+        }
+        TranscriptionProvider::Gemini => {
+            PromptBlueprint::from(config.transcription.gemini.prompt.as_str())
+                .with_glossary(glossary(), None)
+                .resolve()
+        }
+        // Parakeet does not condition on the prompt, so a glossary cannot help.
+        TranscriptionProvider::Parakeet => {
+            PromptBlueprint::from(config.transcription.parakeet.prompt.as_str()).resolve()
+        }
+        TranscriptionProvider::Custom(name) => config
+            .transcription
+            .custom
+            .get(name)
+            .map(|custom| {
+                PromptBlueprint::from(custom.prompt.as_str())
+                    .with_glossary(glossary(), Some(WHISPER_PROMPT_TOKEN_LIMIT))
+                    .resolve()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::VocabularyTerm;
+
+    #[test]
+    fn glossary_follows_the_prompt_within_the_token_limit() {
+        let prompt = PromptBlueprint::from("Dictation.")
+            .with_glossary(
+                ["Hyprland", " ", "Waybar"],
+                Some(WHISPER_PROMPT_TOKEN_LIMIT),
+            )
+            .resolve();
+        assert_eq!(prompt, "Dictation. Glossary: Hyprland, Waybar.");
+
+        let long_terms = (0..200)
+            .map(|index| format!("Term{index}"))
+            .collect::<Vec<_>>();
+        let capped = PromptBlueprint::from("")
+            .with_glossary(long_terms.iter().map(String::as_str), Some(50))
+            .resolve();
+        assert!(capped.starts_with("Glossary: Term0, Term1"));
+        assert!(estimated_tokens(&capped) <= 50);
+    }
+
+    #[test]
+    fn vocabulary_reaches_prompting_providers() {
+        let mut config = Config::default();
+        config.vocabulary = vec![VocabularyTerm {
+            term: "hyprwhspr".to_string(),
+            aliases: vec!["hyper whisper".to_string()],
+        }];
+
+        assert!(provider_prompt(&config, &TranscriptionProvider::Groq)
+            .ends_with("Glossary: hyprwhspr."));
+        assert!(!provider_prompt(&config, &TranscriptionProvider::Parakeet).contains("Glossary"));
     }
 }
@@ -0,0 +1,109 @@
+use serde::Deserialize;
+use std::time::Duration;
+
+/// A timed span of the transcript as reported by the backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptSegment {
+    /// Offset from the start of the audio that was sent to the backend.
+    pub start: Duration,
+    pub end: Duration,
+    pub text: String,
+    /// Probability in `0.0..=1.0`, when the backend reports one.
+    pub confidence: Option<f32>,
+}
+
+impl TranscriptSegment {
+    pub fn from_secs(start: f64, end: f64, text: &str, confidence: Option<f32>) -> Self {
+        Self {
+            start: secs_to_duration(start),
+            end: secs_to_duration(end.max(start)),
+            text: text.trim().to_string(),
+            confidence: confidence.map(|value| value.clamp(0.0, 1.0)),
+        }
+    }
+}
+
+/// Segment entry of an OpenAI-style `verbose_json` response, as returned by
+/// Groq, whisper-server and OpenAI-compatible servers.
+#[derive(Debug, Deserialize)]
+pub(crate) struct VerboseSegment {
+    start: f64,
+    end: f64,
+    #[serde(default)]
+    text: String,
+    avg_logprob: Option<f64>,
+}
+
+impl VerboseSegment {
+    fn into_segment(self) -> TranscriptSegment {
+        // avg_logprob is the mean token log-probability, so exp() gives the
+        // geometric mean probability of the segment's tokens.
+        let confidence = self.avg_logprob.map(|logprob| logprob.exp() as f32);
+        TranscriptSegment::from_secs(self.start, self.end, &self.text, confidence)
+    }
+}
+
+pub(crate) fn from_verbose_segments(segments: Vec<VerboseSegment>) -> Vec<TranscriptSegment> {
+    segments
+        .into_iter()
+        .map(VerboseSegment::into_segment)
+        .filter(|segment| !segment.text.is_empty())
+        .collect()
+}
+
+/// Reads the `segments` array of a `verbose_json` body, if there is one.
+pub(crate) fn verbose_segments_from_value(body: &serde_json::Value) -> Vec<TranscriptSegment> {
+    body.get("segments")
+        .cloned()
+        .and_then(|value| serde_json::from_value::<Vec<VerboseSegment>>(value).ok())
+        .map(from_verbose_segments)
+        .unwrap_or_default()
+}
+
+fn secs_to_duration(secs: f64) -> Duration {
+    if secs.is_finite() && secs > 0.0 {
+        Duration::from_secs_f64(secs)
+    } else {
+        Duration::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn verbose_json_segments_carry_timing_and_confidence() {
+        let body = json!({
+            "text": "hello there general kenobi",
+            "segments": [
+                { "id": 0, "start": 0.0, "end": 1.5, "text": " hello there", "avg_logprob": -0.1 },
+                { "id": 1, "start": 1.5, "end": 3.25, "text": " general kenobi", "avg_logprob": -1.2 }
+            ]
+        });
+
+        let segments = verbose_segments_from_value(&body);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "hello there");
+        assert_eq!(segments[1].start, Duration::from_millis(1500));
+        assert_eq!(segments[1].end, Duration::from_millis(3250));
+        let high = segments[0].confidence.expect("confidence");
+        let low = segments[1].confidence.expect("confidence");
+        assert!(high > 0.9 && low < 0.31, "high={high} low={low}");
+    }
+
+    #[test]
+    fn missing_or_malformed_segments_yield_nothing() {
+        assert!(verbose_segments_from_value(&json!({ "text": "plain json" })).is_empty());
+        assert!(verbose_segments_from_value(&json!({ "segments": "nope" })).is_empty());
+    }
+
+    #[test]
+    fn segments_without_probabilities_have_no_confidence() {
+        let body = json!({ "segments": [{ "start": 0.2, "end": 0.9, "text": "hi" }] });
+        let segments = verbose_segments_from_value(&body);
+        assert_eq!(segments[0].confidence, None);
+        assert_eq!(segments[0].start, Duration::from_millis(200));
+    }
+}
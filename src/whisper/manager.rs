@@ -1,15 +1,18 @@
+use crate::config::{Config, TranscriptionProvider};
 use crate::transcription::{
     clean_transcription, contains_only_non_speech_markers, BackendMetrics, BackendPhaseProbe,
+    TranscribeFuture, TranscribeOptions, Transcriber, TranscriptSegment, TranscriptionLanguage,
     TranscriptionResult,
 };
 use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
 use std::convert::TryFrom;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::{debug, info, trace, warn};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WhisperVadOptions {
     pub enabled: bool,
     pub model_path: Option<PathBuf>,
@@ -35,9 +38,42 @@ impl WhisperVadOptions {
         }
     }
 
-    fn is_active(&self) -> bool {
+    pub(crate) fn is_active(&self) -> bool {
         self.enabled && self.model_path.is_some()
     }
+
+    /// Appends the `--vad*` flags shared by whisper-cli and whisper-server.
+    pub(crate) fn append_cli_args(&self, cmd: &mut Command) {
+        if !self.is_active() {
+            return;
+        }
+
+        if let Some(model_path) = &self.model_path {
+            cmd.arg("--vad");
+            cmd.arg("--vad-model");
+            cmd.arg(model_path);
+
+            cmd.arg("--vad-threshold");
+            cmd.arg(format!("{}", self.threshold));
+
+            cmd.arg("--vad-min-speech-duration-ms");
+            cmd.arg(format!("{}", self.min_speech_ms));
+
+            cmd.arg("--vad-min-silence-duration-ms");
+            cmd.arg(format!("{}", self.min_silence_ms));
+
+            if self.max_speech_s.is_finite() {
+                cmd.arg("--vad-max-speech-duration-s");
+                cmd.arg(format!("{}", self.max_speech_s));
+            }
+
+            cmd.arg("--vad-speech-pad-ms");
+            cmd.arg(format!("{}", self.speech_pad_ms));
+
+            cmd.arg("--vad-samples-overlap");
+            cmd.arg(format!("{}", self.samples_overlap));
+        }
+    }
 }
 
 pub struct WhisperManager {
@@ -49,6 +85,13 @@ pub struct WhisperManager {
     gpu_layers: i32,
     vad: WhisperVadOptions,
     no_speech_threshold: f32,
+    language: TranscriptionLanguage,
+}
+
+struct CliTranscript {
+    text: String,
+    detected_language: Option<String>,
+    segments: Vec<TranscriptSegment>,
 }
 
 impl WhisperManager {
@@ -61,6 +104,7 @@ impl WhisperManager {
         gpu_layers: i32,
         vad: WhisperVadOptions,
         no_speech_threshold: f32,
+        language: TranscriptionLanguage,
     ) -> Result<Self> {
         if binary_paths.is_empty() {
             return Err(anyhow!(
@@ -77,6 +121,7 @@ impl WhisperManager {
             gpu_layers,
             vad,
             no_speech_threshold,
+            language,
         })
     }
 
@@ -176,17 +221,26 @@ impl WhisperManager {
         "CPU only (no GPU detected)".to_string()
     }
 
-    pub async fn transcribe(&self, audio_data: Vec<f32>) -> Result<TranscriptionResult> {
+    pub async fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult> {
         if audio_data.is_empty() {
             return Ok(TranscriptionResult {
                 text: String::new(),
                 metrics: BackendMetrics::default(),
+                language: None,
+                segments: Vec::new(),
             });
         }
 
         let duration_secs = audio_data.len() as f32 / 16000.0;
         info!("🧠 Transcribing {:.2}s of audio...", duration_secs);
 
+        let prompt = options.prompt(&TranscriptionProvider::WhisperCpp, &self.whisper_prompt);
+        let language = options.language(&TranscriptionProvider::WhisperCpp, &self.language);
+
         // Save audio to temporary WAV file
         let temp_wav = self
             .temp_dir
@@ -212,15 +266,19 @@ impl WhisperManager {
         // Run whisper.cpp CLI
         let (transcription_result, mut whisper_phase) =
             BackendPhaseProbe::measure_async("backend.whisper.cli", encoded_bytes, || {
-                self.run_whisper_cli(&temp_wav)
+                self.run_whisper_cli(&temp_wav, &prompt, &language)
             })
             .await;
-        let transcription = transcription_result?;
+        let CliTranscript {
+            text: transcription,
+            detected_language,
+            segments,
+        } = transcription_result?;
         whisper_phase.set_bytes_out(transcription.len());
         let transcription_duration = whisper_phase.wall_duration;
         phases.push(whisper_phase);
         let trimmed = transcription.trim();
-        let cleaned_transcription = clean_transcription(trimmed, &self.whisper_prompt);
+        let cleaned_transcription = clean_transcription(trimmed, &prompt);
 
         // Always clean up after successful transcription pass
         let _ = fs::remove_file(&temp_wav);
@@ -232,6 +290,8 @@ impl WhisperManager {
             response_duration: None,
             transcription_duration,
             phases,
+            fallback_attempts: Vec::new(),
+            served_by: None,
         };
 
         if cleaned_transcription.is_empty() {
@@ -248,6 +308,8 @@ impl WhisperManager {
             return Ok(TranscriptionResult {
                 text: String::new(),
                 metrics,
+                language: detected_language,
+                segments,
             });
         }
 
@@ -262,57 +324,23 @@ impl WhisperManager {
         Ok(TranscriptionResult {
             text: cleaned_transcription,
             metrics,
+            language: detected_language,
+            segments,
         })
     }
 
     fn save_audio_as_wav(&self, audio_data: &[f32], path: &PathBuf) -> Result<()> {
-        use std::io::Write;
-
-        // Convert f32 samples to i16
-        let samples_i16: Vec<i16> = audio_data
-            .iter()
-            .map(|&sample| (sample * 32767.0).clamp(-32768.0, 32767.0) as i16)
-            .collect();
-
-        // WAV file header
-        let mut file = fs::File::create(path)?;
-
-        let channels: u16 = 1;
-        let sample_rate: u32 = 16000;
-        let bits_per_sample: u16 = 16;
-        let byte_rate = sample_rate * channels as u32 * bits_per_sample as u32 / 8;
-        let block_align = channels * bits_per_sample / 8;
-        let data_size = (samples_i16.len() * 2) as u32;
-
-        // RIFF header
-        file.write_all(b"RIFF")?;
-        file.write_all(&(36 + data_size).to_le_bytes())?;
-        file.write_all(b"WAVE")?;
-
-        // fmt chunk
-        file.write_all(b"fmt ")?;
-        file.write_all(&16u32.to_le_bytes())?; // Chunk size
-        file.write_all(&1u16.to_le_bytes())?; // Audio format (PCM)
-        file.write_all(&channels.to_le_bytes())?;
-        file.write_all(&sample_rate.to_le_bytes())?;
-        file.write_all(&byte_rate.to_le_bytes())?;
-        file.write_all(&block_align.to_le_bytes())?;
-        file.write_all(&bits_per_sample.to_le_bytes())?;
-
-        // data chunk
-        file.write_all(b"data")?;
-        file.write_all(&data_size.to_le_bytes())?;
-
-        // Write samples
-        for sample in samples_i16 {
-            file.write_all(&sample.to_le_bytes())?;
-        }
-
+        fs::write(path, encode_wav_pcm16(audio_data))?;
         debug!("Saved audio to WAV: {:?}", path);
         Ok(())
     }
 
-    async fn run_whisper_cli(&self, audio_file: &PathBuf) -> Result<String> {
+    async fn run_whisper_cli(
+        &self,
+        audio_file: &PathBuf,
+        prompt: &str,
+        language: &TranscriptionLanguage,
+    ) -> Result<CliTranscript> {
         let mut last_error: Option<anyhow::Error> = None;
         let mut attempted: Vec<PathBuf> = Vec::new();
 
@@ -327,7 +355,7 @@ impl WhisperManager {
 
             attempted.push(binary.clone());
 
-            match self.invoke_whisper(binary, audio_file) {
+            match self.invoke_whisper(binary, audio_file, prompt, language) {
                 Ok(result) => {
                     if last_error.is_some() {
                         info!("Whisper succeeded using fallback binary: {:?}", binary);
@@ -355,7 +383,13 @@ impl WhisperManager {
         Err(last_error.unwrap_or_else(|| anyhow!("All whisper binaries failed. Tried: {}", tried)))
     }
 
-    fn invoke_whisper(&self, binary: &Path, audio_file: &PathBuf) -> Result<String> {
+    fn invoke_whisper(
+        &self,
+        binary: &Path,
+        audio_file: &PathBuf,
+        prompt: &str,
+        language: &TranscriptionLanguage,
+    ) -> Result<CliTranscript> {
         let mut cmd = Command::new(binary);
 
         // Basic args
@@ -369,45 +403,20 @@ impl WhisperManager {
                 .to_str()
                 .ok_or_else(|| anyhow!("Audio path contains invalid UTF-8"))?,
             "--output-txt",
+            "--output-json-full",
             "--language",
-            "en",
+            language.as_str(),
             "--threads",
             &self.threads.to_string(),
             "--prompt",
-            &self.whisper_prompt,
+            prompt,
             "--no-timestamps", // Just plain text, no timestamps
         ]);
 
         cmd.arg("--no-speech-thold");
         cmd.arg(format!("{}", self.no_speech_threshold));
 
-        if self.vad.is_active() {
-            if let Some(model_path) = &self.vad.model_path {
-                cmd.arg("--vad");
-                cmd.arg("--vad-model");
-                cmd.arg(model_path);
-
-                cmd.arg("--vad-threshold");
-                cmd.arg(format!("{}", self.vad.threshold));
-
-                cmd.arg("--vad-min-speech-duration-ms");
-                cmd.arg(format!("{}", self.vad.min_speech_ms));
-
-                cmd.arg("--vad-min-silence-duration-ms");
-                cmd.arg(format!("{}", self.vad.min_silence_ms));
-
-                if self.vad.max_speech_s.is_finite() {
-                    cmd.arg("--vad-max-speech-duration-s");
-                    cmd.arg(format!("{}", self.vad.max_speech_s));
-                }
-
-                cmd.arg("--vad-speech-pad-ms");
-                cmd.arg(format!("{}", self.vad.speech_pad_ms));
-
-                cmd.arg("--vad-samples-overlap");
-                cmd.arg(format!("{}", self.vad.samples_overlap));
-            }
-        }
+        self.vad.append_cli_args(&mut cmd);
 
         // GPU control: AUR version uses --no-gpu flag (opposite logic)
         // If gpu_layers == 0, disable GPU. Otherwise let it use GPU by default
@@ -445,6 +454,25 @@ impl WhisperManager {
             ));
         }
 
+        let detected_language = if language.is_auto() {
+            parse_detected_language(&stderr)
+        } else {
+            None
+        };
+        if let Some(language) = &detected_language {
+            debug!("Whisper auto-detected language: {}", language);
+        }
+
+        // The full JSON output carries segment offsets and token probabilities.
+        let json_file = audio_file.with_extension("json");
+        let segments = match fs::read_to_string(&json_file) {
+            Ok(json) => {
+                let _ = fs::remove_file(&json_file);
+                parse_cli_json_segments(&json)
+            }
+            Err(_) => Vec::new(),
+        };
+
         // Try to read output txt file
         let txt_file = audio_file.with_extension("txt");
         if txt_file.exists() {
@@ -465,14 +493,223 @@ impl WhisperManager {
                 );
             }
 
-            Ok(transcription.trim().to_string())
+            Ok(CliTranscript {
+                text: transcription.trim().to_string(),
+                detected_language,
+                segments,
+            })
         } else {
             // Fallback to stdout
             warn!(
                 "No .txt file created by whisper using {:?}, falling back to stdout",
                 binary
             );
-            Ok(stdout.trim().to_string())
+            Ok(CliTranscript {
+                text: stdout.trim().to_string(),
+                detected_language,
+                segments,
+            })
         }
     }
 }
+
+/// Encodes mono 16 kHz f32 samples as a 16-bit PCM WAV file.
+pub(crate) fn encode_wav_pcm16(audio_data: &[f32]) -> Vec<u8> {
+    // Convert f32 samples to i16
+    let samples_i16: Vec<i16> = audio_data
+        .iter()
+        .map(|&sample| (sample * 32767.0).clamp(-32768.0, 32767.0) as i16)
+        .collect();
+
+    let channels: u16 = 1;
+    let sample_rate: u32 = 16000;
+    let bits_per_sample: u16 = 16;
+    let byte_rate = sample_rate * channels as u32 * bits_per_sample as u32 / 8;
+    let block_align = channels * bits_per_sample / 8;
+    let data_size = (samples_i16.len() * 2) as u32;
+
+    let mut wav = Vec::with_capacity(44 + data_size as usize);
+
+    // RIFF header
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+
+    // fmt chunk
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // Chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // Audio format (PCM)
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&bits_per_sample.to_le_bytes());
+
+    // data chunk
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_size.to_le_bytes());
+
+    // Write samples
+    for sample in samples_i16 {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+
+    wav
+}
+
+/// Extracts the language from whisper.cpp's `auto-detected language: de (p = 0.97)` log line.
+pub(crate) fn parse_detected_language(stderr: &str) -> Option<String> {
+    stderr.lines().find_map(|line| {
+        let (_, rest) = line.split_once("auto-detected language:")?;
+        rest.split_whitespace()
+            .next()
+            .map(|code| code.to_ascii_lowercase())
+            .filter(|code| !code.is_empty())
+    })
+}
+
+#[derive(Deserialize)]
+struct CliJsonOutput {
+    #[serde(default)]
+    transcription: Vec<CliJsonSegment>,
+}
+
+#[derive(Deserialize)]
+struct CliJsonSegment {
+    offsets: CliJsonOffsets,
+    text: String,
+    #[serde(default)]
+    tokens: Vec<CliJsonToken>,
+}
+
+#[derive(Deserialize)]
+struct CliJsonOffsets {
+    from: u64,
+    to: u64,
+}
+
+#[derive(Deserialize)]
+struct CliJsonToken {
+    text: String,
+    p: Option<f32>,
+}
+
+/// Reads segments from whisper-cli's `--output-json-full` file. Offsets are
+/// milliseconds; confidence is the mean probability of the text tokens.
+pub(crate) fn parse_cli_json_segments(json: &str) -> Vec<TranscriptSegment> {
+    let Ok(output) = serde_json::from_str::<CliJsonOutput>(json) else {
+        debug!("Could not parse whisper JSON output; continuing without segments");
+        return Vec::new();
+    };
+
+    output
+        .transcription
+        .into_iter()
+        .filter(|segment| !segment.text.trim().is_empty())
+        .map(|segment| {
+            let probabilities: Vec<f32> = segment
+                .tokens
+                .iter()
+                // Special tokens such as [_BEG_] and [_TT_150] are not words.
+                .filter(|token| !token.text.starts_with("[_"))
+                .filter_map(|token| token.p)
+                .collect();
+            let confidence = (!probabilities.is_empty())
+                .then(|| probabilities.iter().sum::<f32>() / probabilities.len() as f32);
+            TranscriptSegment::from_secs(
+                segment.offsets.from as f64 / 1000.0,
+                segment.offsets.to as f64 / 1000.0,
+                &segment.text,
+                confidence,
+            )
+        })
+        .collect()
+}
+
+/// True when moving to `new` changes which whisper.cpp binary, model or
+/// launch flags are used. Prompt and language are passed per request.
+pub(crate) fn whisper_cpp_launch_changed(current: &Config, new: &Config) -> bool {
+    let launch = |config: &Config| {
+        let mut whisper_cpp = config.transcription.whisper_cpp.clone();
+        whisper_cpp.prompt.clear();
+        whisper_cpp.language = None;
+        whisper_cpp
+    };
+    launch(current) != launch(new)
+}
+
+impl Transcriber for WhisperManager {
+    fn initialize(&self) -> Result<()> {
+        WhisperManager::initialize(self)
+    }
+
+    fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> TranscribeFuture<'_> {
+        Box::pin(WhisperManager::transcribe_with(self, audio_data, options))
+    }
+
+    fn provider(&self) -> TranscriptionProvider {
+        TranscriptionProvider::WhisperCpp
+    }
+
+    fn needs_refresh(&self, current: &Config, new: &Config) -> bool {
+        whisper_cpp_launch_changed(current, new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_cli_json_segments, parse_detected_language, whisper_cpp_launch_changed};
+    use crate::config::Config;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_auto_detected_language_from_stderr() {
+        let stderr = "whisper_full_with_state: auto-detected language: de (p = 0.973)\n";
+        assert_eq!(parse_detected_language(stderr).as_deref(), Some("de"));
+        assert_eq!(parse_detected_language("main: processing"), None);
+    }
+
+    #[test]
+    fn parses_segments_from_cli_json_output() {
+        let json = r#"{
+            "transcription": [
+                {
+                    "timestamps": { "from": "00:00:00,000", "to": "00:00:01,840" },
+                    "offsets": { "from": 0, "to": 1840 },
+                    "text": " Hello world.",
+                    "tokens": [
+                        { "text": "[_BEG_]", "p": 0.2 },
+                        { "text": " Hello", "p": 0.9 },
+                        { "text": " world.", "p": 0.7 }
+                    ]
+                },
+                { "offsets": { "from": 1840, "to": 2000 }, "text": " ", "tokens": [] }
+            ]
+        }"#;
+
+        let segments = parse_cli_json_segments(json);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "Hello world.");
+        assert_eq!(segments[0].end, Duration::from_millis(1840));
+        let confidence = segments[0].confidence.expect("confidence");
+        assert!((confidence - 0.8).abs() < 1e-6);
+        assert!(parse_cli_json_segments("not json").is_empty());
+    }
+
+    #[test]
+    fn prompt_and_language_changes_keep_the_running_backend() {
+        let current = Config::default();
+        let mut new = current.clone();
+        new.transcription.whisper_cpp.prompt = "Meeting notes.".to_string();
+        new.transcription.whisper_cpp.language = Some("de".to_string());
+        new.transcription.language = "fr".to_string();
+        assert!(!whisper_cpp_launch_changed(&current, &new));
+
+        new.transcription.whisper_cpp.model = "large-v3".to_string();
+        assert!(whisper_cpp_launch_changed(&current, &new));
+    }
+}
@@ -1,3 +1,9 @@
 pub mod manager;
+#[cfg(feature = "whisper-native")]
+pub mod native;
+pub mod server;
 
 pub use manager::{WhisperManager, WhisperVadOptions};
+#[cfg(feature = "whisper-native")]
+pub use native::WhisperNativeTranscriber;
+pub use server::{WhisperServerLaunch, WhisperServerPool, WhisperServerTranscriber};
@@ -0,0 +1,258 @@
+use crate::config::{Config, TranscriptionProvider, WhisperCppConfig};
+use crate::transcription::{
+    clean_transcription, contains_only_non_speech_markers, BackendMetrics, TranscribeFuture,
+    TranscribeOptions, Transcriber, TranscriptSegment, TranscriptionLanguage, TranscriptionResult,
+};
+use crate::whisper::manager::whisper_cpp_launch_changed;
+use crate::whisper::WhisperVadOptions;
+use anyhow::{anyhow, Context, Result};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use whisper_rs::{
+    get_lang_str, FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters,
+    WhisperState, WhisperVadParams,
+};
+
+/// whisper.cpp linked in-process through whisper-rs. The ggml model is loaded
+/// once and inference runs directly on the captured samples, with no temp WAV
+/// or child process per utterance.
+pub struct WhisperNativeTranscriber {
+    state: Arc<Mutex<WhisperState>>,
+    model_path: PathBuf,
+    threads: usize,
+    prompt: String,
+    no_speech_threshold: f32,
+    vad: WhisperVadOptions,
+    language: TranscriptionLanguage,
+}
+
+impl WhisperNativeTranscriber {
+    pub fn new(
+        config: &WhisperCppConfig,
+        model_path: PathBuf,
+        vad: WhisperVadOptions,
+        prompt: String,
+        language: TranscriptionLanguage,
+    ) -> Result<Self> {
+        if !model_path.exists() {
+            return Err(anyhow!(
+                "Whisper model not found at: {:?}\nDownload models from: https://huggingface.co/ggerganov/whisper.cpp/tree/main",
+                model_path
+            ));
+        }
+
+        let mut params = WhisperContextParameters::default();
+        params.use_gpu(config.gpu_layers != 0);
+
+        let context = WhisperContext::new_with_params(&model_path, params)
+            .with_context(|| format!("Failed to load whisper model {}", model_path.display()))?;
+        let state = context
+            .create_state()
+            .context("Failed to allocate whisper inference state")?;
+
+        Ok(Self {
+            state: Arc::new(Mutex::new(state)),
+            model_path,
+            threads: config.threads.max(1),
+            prompt,
+            no_speech_threshold: config.no_speech_threshold,
+            vad,
+            language,
+        })
+    }
+
+    pub fn initialize(&self) -> Result<()> {
+        info!("✅ Whisper (in-process) initialized");
+        info!("   Model: {:?}", self.model_path);
+        info!("   Threads: {}", self.threads);
+        info!("   Language: {}", self.language);
+        if self.vad.is_active() {
+            if let Some(path) = &self.vad.model_path {
+                info!("   VAD: enabled ({})", path.display());
+            }
+        } else {
+            info!("   VAD: disabled");
+        }
+        Ok(())
+    }
+
+    pub async fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult> {
+        if audio_data.is_empty() {
+            return Ok(TranscriptionResult {
+                text: String::new(),
+                metrics: BackendMetrics::default(),
+                language: None,
+                segments: Vec::new(),
+            });
+        }
+
+        let duration_secs = audio_data.len() as f32 / 16000.0;
+        info!("🧠 Transcribing {duration_secs:.2}s of audio in-process...");
+
+        let transcribe_start = Instant::now();
+        let state = Arc::clone(&self.state);
+        let threads = self.threads;
+        let prompt = options.prompt(&TranscriptionProvider::WhisperCpp, &self.prompt);
+        let initial_prompt = prompt.clone();
+        let no_speech_threshold = self.no_speech_threshold;
+        let vad = self.vad.clone();
+        let language = options.language(&TranscriptionProvider::WhisperCpp, &self.language);
+
+        let (raw_text, detected_language, segments) = tokio::task::spawn_blocking(move || {
+            let mut state = state.blocking_lock();
+            let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+            params.set_n_threads(threads as i32);
+            params.set_language(Some(language.as_str()));
+            params.set_no_speech_thold(no_speech_threshold);
+            params.set_print_special(false);
+            params.set_print_progress(false);
+            params.set_print_realtime(false);
+            params.set_print_timestamps(false);
+            if !initial_prompt.trim().is_empty() {
+                params.set_initial_prompt(&initial_prompt);
+            }
+            apply_vad(&mut params, &vad);
+
+            state
+                .full(params, &audio_data)
+                .map_err(|err| anyhow!("Whisper inference failed: {err}"))?;
+
+            let mut text = String::new();
+            let mut segments = Vec::new();
+            for segment in state.as_iter() {
+                let segment_text = segment.to_string();
+                let probabilities: Vec<f32> = (0..segment.n_tokens())
+                    .filter_map(|index| segment.get_token(index))
+                    // Special tokens such as [_BEG_] and [_TT_150] are not words.
+                    .filter(|token| {
+                        token
+                            .to_str_lossy()
+                            .is_ok_and(|token_text| !token_text.starts_with("[_"))
+                    })
+                    .map(|token| token.token_probability())
+                    .collect();
+                let confidence = (!probabilities.is_empty())
+                    .then(|| probabilities.iter().sum::<f32>() / probabilities.len() as f32);
+                // Timestamps are in centiseconds.
+                segments.push(TranscriptSegment::from_secs(
+                    segment.start_timestamp() as f64 / 100.0,
+                    segment.end_timestamp() as f64 / 100.0,
+                    &segment_text,
+                    confidence,
+                ));
+                text.push_str(&segment_text);
+            }
+            segments.retain(|segment| !segment.text.is_empty());
+            let detected_language = if language.is_auto() {
+                get_lang_str(state.full_lang_id_from_state()).map(str::to_string)
+            } else {
+                None
+            };
+
+            Ok::<_, anyhow::Error>((text, detected_language, segments))
+        })
+        .await
+        .context("Whisper inference worker panicked")??;
+
+        let transcription_duration = transcribe_start.elapsed();
+        let trimmed = raw_text.trim();
+        let cleaned = clean_transcription(trimmed, &prompt);
+
+        if let Some(language) = &detected_language {
+            debug!("Whisper auto-detected language: {}", language);
+        }
+
+        if cleaned.is_empty() {
+            if trimmed.is_empty() {
+                warn!("Whisper returned empty transcription");
+            } else if contains_only_non_speech_markers(trimmed) {
+                debug!("Whisper produced only non-speech markers: {}", trimmed);
+            } else {
+                debug!(
+                    "Transcription removed by prompt artifact filter: raw='{}'",
+                    trimmed
+                );
+            }
+        } else {
+            info!("✅ Transcription: {}", cleaned);
+        }
+
+        let metrics = BackendMetrics {
+            encode_duration: None,
+            encoded_bytes: None,
+            upload_duration: None,
+            response_duration: None,
+            transcription_duration,
+            phases: Vec::new(),
+            fallback_attempts: Vec::new(),
+            served_by: None,
+        };
+
+        Ok(TranscriptionResult {
+            text: cleaned,
+            metrics,
+            language: detected_language,
+            segments,
+        })
+    }
+}
+
+fn apply_vad(params: &mut FullParams<'_, '_>, vad: &WhisperVadOptions) {
+    if !vad.is_active() {
+        return;
+    }
+    let Some(model_path) = vad.model_path.as_ref().and_then(|path| path.to_str()) else {
+        warn!("VAD model path contains invalid UTF-8; running without VAD");
+        return;
+    };
+
+    let mut vad_params = WhisperVadParams::new();
+    vad_params.set_threshold(vad.threshold);
+    vad_params.set_min_speech_duration(vad.min_speech_ms as i32);
+    vad_params.set_min_silence_duration(vad.min_silence_ms as i32);
+    if vad.max_speech_s.is_finite() {
+        vad_params.set_max_speech_duration(vad.max_speech_s);
+    }
+    vad_params.set_speech_pad(vad.speech_pad_ms as i32);
+    vad_params.set_samples_overlap(vad.samples_overlap);
+
+    params.set_vad_model_path(Some(model_path));
+    params.set_vad_params(vad_params);
+    params.enable_vad(true);
+}
+
+impl Transcriber for WhisperNativeTranscriber {
+    fn initialize(&self) -> Result<()> {
+        WhisperNativeTranscriber::initialize(self)
+    }
+
+    fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> TranscribeFuture<'_> {
+        Box::pin(WhisperNativeTranscriber::transcribe_with(
+            self, audio_data, options,
+        ))
+    }
+
+    fn provider(&self) -> TranscriptionProvider {
+        TranscriptionProvider::WhisperCpp
+    }
+
+    fn label(&self) -> Cow<'_, str> {
+        Cow::Borrowed("Local (in-process)")
+    }
+
+    fn needs_refresh(&self, current: &Config, new: &Config) -> bool {
+        whisper_cpp_launch_changed(current, new)
+    }
+}
@@ -0,0 +1,932 @@
+use crate::config::{Config, TranscriptionProvider};
+use crate::transcription::{
+    clean_transcription, from_verbose_segments, BackendMetrics, BackendPhaseProbe,
+    TranscribeFuture, TranscribeOptions, Transcriber, TranscriptionLanguage, TranscriptionResult,
+    VerboseSegment,
+};
+use crate::whisper::manager::{encode_wav_pcm16, whisper_cpp_launch_changed, WhisperVadOptions};
+use anyhow::{anyhow, Context, Result};
+use bytes::Bytes;
+use reqwest::{multipart, Client, StatusCode, Url};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::cmp;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{debug, error, info, warn};
+
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const HEALTH_POLL_TIMEOUT: Duration = Duration::from_secs(2);
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(5);
+const WATCHDOG_MAX_BACKOFF: Duration = Duration::from_secs(300);
+/// How long a restarted server has to stay up before the watchdog forgets
+/// earlier restarts.
+const WATCHDOG_STABLE_PERIOD: Duration = Duration::from_secs(60);
+/// Consecutive restarts after which the watchdog gives up; the server is
+/// still started on demand by the next dictation.
+const WATCHDOG_MAX_RESTARTS: u32 = 5;
+
+/// Everything needed to launch `whisper-server` with the model preloaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhisperServerLaunch {
+    pub binary: PathBuf,
+    pub model_path: PathBuf,
+    pub host: String,
+    pub port: u16,
+    pub threads: usize,
+    pub gpu_layers: i32,
+    pub no_speech_threshold: f32,
+    pub vad: WhisperVadOptions,
+    pub startup_timeout: Duration,
+}
+
+impl WhisperServerLaunch {
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.binary);
+        cmd.arg("-m")
+            .arg(&self.model_path)
+            .arg("--host")
+            .arg(&self.host)
+            .arg("--port")
+            .arg(self.port.to_string())
+            .arg("--threads")
+            .arg(self.threads.to_string())
+            .arg("--no-speech-thold")
+            .arg(format!("{}", self.no_speech_threshold));
+
+        self.vad.append_cli_args(&mut cmd);
+
+        // Mirror the CLI backend: gpu_layers == 0 means CPU only.
+        if self.gpu_layers == 0 {
+            cmd.arg("--no-gpu");
+        }
+
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        cmd
+    }
+}
+
+/// `whisper-server` processes by address, so backends built for the same
+/// launch, e.g. a profile's provider and the primary fallback chain, share
+/// one process and watchdog instead of racing for the port.
+#[derive(Clone, Default)]
+pub struct WhisperServerPool {
+    processes: Arc<Mutex<HashMap<String, Weak<ServerProcess>>>>,
+}
+
+impl WhisperServerPool {
+    /// Returns the live process for `launch`, or a new one that is not
+    /// started yet. A process at the same address with different launch
+    /// settings is replaced; it is expected to be shut down already.
+    fn process(&self, launch: WhisperServerLaunch) -> Arc<ServerProcess> {
+        let mut processes = self
+            .processes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        processes.retain(|_, process| process.strong_count() > 0);
+
+        let address = format!("{}:{}", launch.host, launch.port);
+        if let Some(process) = processes.get(&address).and_then(Weak::upgrade) {
+            if process.launch == launch {
+                return process;
+            }
+        }
+
+        let process = Arc::new(ServerProcess::new(launch));
+        processes.insert(address, Arc::downgrade(&process));
+        process
+    }
+}
+
+/// Supervised `whisper-server` child process. The process is killed when the
+/// last handle is dropped, e.g. after a config reload swaps the backend.
+struct ServerProcess {
+    launch: WhisperServerLaunch,
+    child: Mutex<Option<Child>>,
+    healthy: AtomicBool,
+    /// Backends that have not been shut down yet; the last one to go stops
+    /// the server.
+    users: AtomicUsize,
+    /// Restarts the server between dictations; aborted on shutdown.
+    watchdog: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ServerProcess {
+    fn new(launch: WhisperServerLaunch) -> Self {
+        Self {
+            launch,
+            child: Mutex::new(None),
+            healthy: AtomicBool::new(false),
+            users: AtomicUsize::new(0),
+            watchdog: Mutex::new(None),
+        }
+    }
+
+    /// Starts the server unless it is already running. Returns `true` when a
+    /// new process was spawned and still has to load the model.
+    fn ensure_running(&self) -> Result<bool> {
+        let mut guard = self.child.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(child) = guard.as_mut() {
+            match child.try_wait() {
+                Ok(None) => return Ok(false),
+                Ok(Some(status)) => {
+                    warn!("whisper-server exited unexpectedly ({status}); restarting")
+                }
+                Err(err) => warn!("Failed to poll whisper-server: {err}; restarting"),
+            }
+        }
+
+        self.healthy.store(false, Ordering::SeqCst);
+        let mut cmd = self.launch.command();
+        debug!("Starting whisper-server: {:?}", cmd);
+        let child = cmd.spawn().with_context(|| {
+            format!(
+                "Failed to start whisper-server at {}",
+                self.launch.binary.display()
+            )
+        })?;
+        info!(
+            "🚀 whisper-server started (pid {}, {}:{})",
+            child.id(),
+            self.launch.host,
+            self.launch.port
+        );
+        *guard = Some(child);
+
+        Ok(true)
+    }
+
+    fn exit_status(&self) -> Option<String> {
+        let mut guard = self.child.lock().unwrap_or_else(PoisonError::into_inner);
+        match guard.as_mut().map(Child::try_wait) {
+            Some(Ok(Some(status))) => Some(status.to_string()),
+            None => Some("not running".to_string()),
+            _ => None,
+        }
+    }
+
+    /// Stops the watchdog and then the server.
+    fn shutdown(&self) {
+        let watchdog = self
+            .watchdog
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(watchdog) = watchdog {
+            watchdog.abort();
+        }
+        self.stop();
+    }
+
+    fn stop(&self) {
+        self.healthy.store(false, Ordering::SeqCst);
+        let child = self
+            .child
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(mut child) = child {
+            debug!("Stopping whisper-server (pid {})", child.id());
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Drop for ServerProcess {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// whisper.cpp backend that keeps the model loaded in a long-lived
+/// `whisper-server` instead of spawning whisper-cli per utterance.
+pub struct WhisperServerTranscriber {
+    client: Client,
+    process: Arc<ServerProcess>,
+    /// Set once this backend stopped counting as a user of `process`.
+    released: AtomicBool,
+    inference_url: Url,
+    health_url: Url,
+    prompt: String,
+    language: TranscriptionLanguage,
+    request_timeout: Duration,
+    max_retries: u32,
+}
+
+impl WhisperServerTranscriber {
+    /// Builds a backend for `launch`, sharing the server process with other
+    /// backends from the same `pool`.
+    pub fn new(
+        pool: &WhisperServerPool,
+        launch: WhisperServerLaunch,
+        request_timeout: Duration,
+        max_retries: u32,
+        prompt: String,
+        language: TranscriptionLanguage,
+    ) -> Result<Self> {
+        let base_url = Url::parse(&format!("http://{}:{}/", launch.host, launch.port))
+            .with_context(|| {
+                format!(
+                    "Invalid whisper-server address: {}:{}",
+                    launch.host, launch.port
+                )
+            })?;
+        let inference_url = base_url.join("inference")?;
+        let process = pool.process(launch);
+        process.users.fetch_add(1, Ordering::SeqCst);
+        let health_url = base_url.join("health")?;
+
+        // Timeouts are set per request, so a config reload can change them
+        // without restarting the server.
+        let client = Client::builder()
+            .user_agent("hyprwhspr-rs (whisper-server)")
+            .connect_timeout(Duration::from_secs(2))
+            .build()
+            .context("Failed to build whisper-server HTTP client")?;
+
+        Ok(Self {
+            client,
+            process,
+            released: AtomicBool::new(false),
+            inference_url,
+            health_url,
+            prompt,
+            language,
+            request_timeout,
+            max_retries,
+        })
+    }
+
+    /// Stops counting as a user of the server, stopping it when no other
+    /// backend uses it any more.
+    fn release(&self) {
+        if !self.released.swap(true, Ordering::SeqCst)
+            && self.process.users.fetch_sub(1, Ordering::SeqCst) == 1
+        {
+            self.process.shutdown();
+        }
+    }
+
+    pub fn initialize(&self) -> Result<()> {
+        let launch = &self.process.launch;
+        if !launch.model_path.exists() {
+            return Err(anyhow!(
+                "Whisper model not found at: {:?}\nDownload models from: https://huggingface.co/ggerganov/whisper.cpp/tree/main",
+                launch.model_path
+            ));
+        }
+
+        self.process.ensure_running()?;
+        self.spawn_watchdog();
+
+        info!("✅ whisper-server backend initialized");
+        info!("   Model: {:?}", launch.model_path);
+        info!("   Binary: {:?}", launch.binary);
+        info!("   Endpoint: {}", self.inference_url);
+        info!("   Language: {}", self.language);
+
+        Ok(())
+    }
+
+    /// Restarts the server in the background when it crashes between
+    /// dictations, so the next utterance does not pay for the model load.
+    /// Backends sharing the process share its one watchdog.
+    fn spawn_watchdog(&self) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            debug!("No async runtime; whisper-server will be restarted on demand");
+            return;
+        };
+
+        let mut watchdog = self
+            .process
+            .watchdog
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if watchdog.as_ref().is_some_and(|task| !task.is_finished()) {
+            return;
+        }
+
+        let weak_process: Weak<ServerProcess> = Arc::downgrade(&self.process);
+        *watchdog = Some(handle.spawn(async move {
+            // Restarts that keep failing (or crashing soon after starting)
+            // back off instead of reloading the model every few seconds.
+            let mut backoff = RestartBackoff::default();
+            loop {
+                sleep(watchdog_delay(backoff.failures)).await;
+                let Some(process) = weak_process.upgrade() else {
+                    break;
+                };
+                match process.ensure_running() {
+                    Ok(false) => backoff.record_running(Instant::now()),
+                    Ok(true) => backoff.record_restart(Instant::now()),
+                    Err(err) => {
+                        backoff.record_restart(Instant::now());
+                        warn!(
+                            "whisper-server watchdog failed to restart server: {:#}",
+                            err
+                        );
+                    }
+                }
+
+                if backoff.failures >= WATCHDOG_MAX_RESTARTS {
+                    error!(
+                        "whisper-server failed {} times in a row; watchdog stopped, \
+                         the next dictation will try to start it again",
+                        backoff.failures
+                    );
+                    break;
+                }
+            }
+        }));
+    }
+
+    pub async fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult> {
+        if audio_data.is_empty() {
+            return Ok(TranscriptionResult {
+                text: String::new(),
+                metrics: BackendMetrics::default(),
+                language: None,
+                segments: Vec::new(),
+            });
+        }
+
+        let duration_secs = audio_data.len() as f32 / 16000.0;
+        info!(
+            "🧠 Transcribing {:.2}s of audio via whisper-server...",
+            duration_secs
+        );
+
+        let prompt = options.prompt(&TranscriptionProvider::WhisperCpp, &self.prompt);
+        let language = options.language(&TranscriptionProvider::WhisperCpp, &self.language);
+        let request = InferenceRequest {
+            prompt: &prompt,
+            language: &language,
+            timeout: options.request_timeout(self.request_timeout),
+        };
+
+        let mut phases = Vec::new();
+        let (wav_result, mut encode_phase) = BackendPhaseProbe::measure(
+            "backend.whisper_server.wav",
+            Some(audio_data.len() * std::mem::size_of::<f32>()),
+            || Ok::<_, anyhow::Error>(Bytes::from(encode_wav_pcm16(&audio_data))),
+        );
+        let wav = wav_result?;
+        let encoded_len = wav.len();
+        encode_phase.set_bytes_out(encoded_len);
+        let encode_duration = encode_phase.wall_duration;
+        phases.push(encode_phase);
+
+        let (send_result, mut send_phase) = BackendPhaseProbe::measure_async(
+            "backend.whisper_server.request",
+            Some(encoded_len),
+            || self.send_with_retry(&wav, &request, options.max_retries(self.max_retries)),
+        )
+        .await;
+        let (payload, timings) = send_result?;
+        let raw = payload.text.unwrap_or_default();
+        send_phase.set_bytes_out(raw.len());
+        let transcription_duration = send_phase.wall_duration;
+        phases.push(send_phase);
+        let cleaned = clean_transcription(raw.trim(), &prompt);
+
+        if cleaned.is_empty() {
+            warn!("whisper-server returned empty or non-speech transcription");
+        } else {
+            info!("✅ Transcription: {}", cleaned);
+        }
+
+        let metrics = BackendMetrics {
+            encode_duration: Some(encode_duration),
+            encoded_bytes: Some(encoded_len),
+            upload_duration: Some(timings.upload),
+            response_duration: Some(timings.response),
+            transcription_duration,
+            phases,
+            fallback_attempts: Vec::new(),
+            served_by: None,
+        };
+
+        Ok(TranscriptionResult {
+            text: cleaned,
+            metrics,
+            language: payload.language,
+            segments: from_verbose_segments(payload.segments),
+        })
+    }
+
+    async fn send_with_retry(
+        &self,
+        audio: &Bytes,
+        request: &InferenceRequest<'_>,
+        max_retries: u32,
+    ) -> Result<(ServerTranscriptionResponse, NetworkTimings)> {
+        let attempts = cmp::max(1, max_retries.saturating_add(1));
+
+        for attempt in 0..attempts {
+            let result = match self.ensure_ready().await {
+                Ok(()) => self.send_once(audio, request).await,
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok(result) => return Ok(result),
+                Err(err) => {
+                    // Force a health check (and restart if it died) before the next attempt.
+                    self.process.healthy.store(false, Ordering::SeqCst);
+
+                    let is_last_attempt = attempt + 1 == attempts;
+                    if is_last_attempt {
+                        return Err(err);
+                    }
+
+                    warn!(
+                        attempt = attempt + 1,
+                        max_attempts = attempts,
+                        "whisper-server transcription attempt failed: {:#}",
+                        err
+                    );
+
+                    let backoff = Duration::from_millis(250 * (1 << attempt));
+                    sleep(backoff).await;
+                }
+            }
+        }
+
+        Err(anyhow!("Unknown whisper-server transcription failure"))
+    }
+
+    /// Makes sure the server process is alive and has finished loading the
+    /// model. A server that never turns healthy is killed so the next attempt
+    /// starts a fresh one.
+    async fn ensure_ready(&self) -> Result<()> {
+        let restarted = self.process.ensure_running()?;
+        if !restarted && self.process.healthy.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        match self.wait_until_healthy().await {
+            Ok(()) => {
+                self.process.healthy.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+            Err(err) => {
+                self.process.stop();
+                Err(err)
+            }
+        }
+    }
+
+    async fn wait_until_healthy(&self) -> Result<()> {
+        let startup_timeout = self.process.launch.startup_timeout;
+        let deadline = Instant::now() + startup_timeout;
+
+        loop {
+            if let Some(status) = self.process.exit_status() {
+                return Err(anyhow!(
+                    "whisper-server exited before becoming ready ({status})"
+                ));
+            }
+
+            let health = self
+                .client
+                .get(self.health_url.clone())
+                .timeout(HEALTH_POLL_TIMEOUT)
+                .send()
+                .await;
+            match health {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) if response.status() == StatusCode::SERVICE_UNAVAILABLE => {
+                    debug!("whisper-server is still loading the model");
+                }
+                Ok(response) => {
+                    debug!("whisper-server health check returned {}", response.status());
+                }
+                Err(err) => debug!("whisper-server not reachable yet: {}", err),
+            }
+
+            if Instant::now() >= deadline {
+                return Err(anyhow!(
+                    "whisper-server did not become healthy within {:?}",
+                    startup_timeout
+                ));
+            }
+            sleep(HEALTH_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn send_once(
+        &self,
+        audio: &Bytes,
+        request: &InferenceRequest<'_>,
+    ) -> Result<(ServerTranscriptionResponse, NetworkTimings)> {
+        // verbose_json carries segment timings and the detected language.
+        let mut form = multipart::Form::new()
+            .text("response_format", "verbose_json")
+            .text("temperature", "0.0")
+            .text("language", request.language.as_str().to_string());
+
+        if !request.prompt.trim().is_empty() {
+            form = form.text("prompt", request.prompt.to_string());
+        }
+
+        let file_part = multipart::Part::stream(audio.clone())
+            .file_name("audio.wav")
+            .mime_str("audio/wav")
+            .context("Failed to set whisper-server audio content type")?;
+        form = form.part("file", file_part);
+
+        let request_start = Instant::now();
+        let response = self
+            .client
+            .post(self.inference_url.clone())
+            .timeout(request.timeout)
+            .multipart(form)
+            .send()
+            .await
+            .context("Failed to send whisper-server inference request")?;
+        let upload_duration = request_start.elapsed();
+
+        let status = response.status();
+        let parse_start = Instant::now();
+        let body = response
+            .bytes()
+            .await
+            .context("Failed to read whisper-server response")?;
+        let response_duration = parse_start.elapsed();
+
+        if !status.is_success() {
+            let message = serde_json::from_slice::<ServerTranscriptionResponse>(&body)
+                .ok()
+                .and_then(|payload| payload.error)
+                .unwrap_or_else(|| String::from_utf8_lossy(&body).trim().to_string());
+            return Err(anyhow!(
+                "whisper-server inference failed with status {status}: {message}"
+            ));
+        }
+
+        let payload: ServerTranscriptionResponse = serde_json::from_slice(&body)
+            .context("Failed to parse whisper-server inference response")?;
+        if let Some(error) = payload.error {
+            return Err(anyhow!(error).context("whisper-server reported an error"));
+        }
+
+        Ok((
+            payload,
+            NetworkTimings {
+                upload: upload_duration,
+                response: response_duration,
+            },
+        ))
+    }
+}
+
+impl Transcriber for WhisperServerTranscriber {
+    fn initialize(&self) -> Result<()> {
+        WhisperServerTranscriber::initialize(self)
+    }
+
+    fn transcribe_with(
+        &self,
+        audio_data: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> TranscribeFuture<'_> {
+        Box::pin(WhisperServerTranscriber::transcribe_with(
+            self, audio_data, options,
+        ))
+    }
+
+    fn provider(&self) -> TranscriptionProvider {
+        TranscriptionProvider::WhisperCpp
+    }
+
+    fn label(&self) -> Cow<'_, str> {
+        Cow::Borrowed("Local (whisper-server)")
+    }
+
+    /// Only launch changes respawn the server; prompt, language, timeout and
+    /// retries are taken from each request.
+    fn needs_refresh(&self, current: &Config, new: &Config) -> bool {
+        whisper_cpp_launch_changed(current, new)
+    }
+
+    fn shutdown(&self) {
+        self.release();
+    }
+}
+
+impl Drop for WhisperServerTranscriber {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// Consecutive restarts seen by the watchdog. They are only forgotten once
+/// the server stays up for [`WATCHDOG_STABLE_PERIOD`], so one that crashes a
+/// little while after each start keeps backing off too.
+#[derive(Debug, Default)]
+struct RestartBackoff {
+    failures: u32,
+    last_restart: Option<Instant>,
+}
+
+impl RestartBackoff {
+    fn record_restart(&mut self, now: Instant) {
+        self.failures += 1;
+        self.last_restart = Some(now);
+    }
+
+    fn record_running(&mut self, now: Instant) {
+        if self
+            .last_restart
+            .is_some_and(|restart| now.duration_since(restart) >= WATCHDOG_STABLE_PERIOD)
+        {
+            self.failures = 0;
+            self.last_restart = None;
+        }
+    }
+}
+
+/// Delay before the watchdog's next check: the base interval while the
+/// server is up, doubling with each consecutive restart.
+fn watchdog_delay(failures: u32) -> Duration {
+    WATCHDOG_INTERVAL
+        .saturating_mul(1 << failures.min(16))
+        .min(WATCHDOG_MAX_BACKOFF)
+}
+
+/// Per-request settings of one inference call.
+struct InferenceRequest<'a> {
+    prompt: &'a str,
+    language: &'a TranscriptionLanguage,
+    timeout: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct NetworkTimings {
+    upload: Duration,
+    response: Duration,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServerTranscriptionResponse {
+    text: Option<String>,
+    language: Option<String>,
+    error: Option<String>,
+    #[serde(default)]
+    segments: Vec<VerboseSegment>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http::{read_request, write_response};
+    use std::os::unix::fs::PermissionsExt;
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[tokio::test]
+    async fn transcribes_through_supervised_server() {
+        let root = temp_root("transcribe");
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test server");
+        let port = listener.local_addr().expect("local addr").port();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.expect("accept request");
+                tokio::spawn(serve_stub_request(
+                    stream,
+                    br#"{"text":" hello from the server\n"}"#,
+                ));
+            }
+        });
+
+        let transcriber = WhisperServerTranscriber::new(
+            &WhisperServerPool::default(),
+            fake_launch(&root, port),
+            Duration::from_secs(5),
+            0,
+            String::new(),
+            TranscriptionLanguage::Code("en".to_string()),
+        )
+        .expect("transcriber");
+
+        let result = transcriber
+            .transcribe(vec![0.1; 1600])
+            .await
+            .expect("transcribe");
+        assert_eq!(result.text, "hello from the server");
+        assert_eq!(result.metrics.encoded_bytes, Some(44 + 1600 * 2));
+
+        drop(transcriber);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn malformed_server_response_is_an_error() {
+        let root = temp_root("malformed");
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test server");
+        let port = listener.local_addr().expect("local addr").port();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.expect("accept request");
+                tokio::spawn(serve_stub_request(stream, b"<html>oops</html>"));
+            }
+        });
+
+        let transcriber = WhisperServerTranscriber::new(
+            &WhisperServerPool::default(),
+            fake_launch(&root, port),
+            Duration::from_secs(5),
+            0,
+            String::new(),
+            TranscriptionLanguage::Auto,
+        )
+        .expect("transcriber");
+
+        let err = transcriber
+            .transcribe(vec![0.1; 1600])
+            .await
+            .expect_err("malformed response");
+        assert!(format!("{err:#}").contains("Failed to parse whisper-server inference response"));
+
+        drop(transcriber);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn shutdown_stops_the_server_for_its_replacement() {
+        let root = temp_root("shutdown");
+        let transcriber = WhisperServerTranscriber::new(
+            &WhisperServerPool::default(),
+            fake_launch(&root, 0),
+            Duration::from_secs(5),
+            0,
+            String::new(),
+            TranscriptionLanguage::Auto,
+        )
+        .expect("transcriber");
+        assert!(transcriber.process.ensure_running().expect("start"));
+
+        Transcriber::shutdown(&transcriber);
+        assert_eq!(
+            transcriber.process.exit_status().as_deref(),
+            Some("not running")
+        );
+        assert!(transcriber.process.watchdog.lock().expect("lock").is_none());
+
+        let current = Config::default();
+        let mut new = current.clone();
+        new.transcription.request_timeout_secs += 10;
+        new.transcription.whisper_cpp.prompt = "Names: Ada.".to_string();
+        assert!(!transcriber.needs_refresh(&current, &new));
+        new.transcription.whisper_cpp.server.port += 1;
+        assert!(transcriber.needs_refresh(&current, &new));
+
+        drop(transcriber);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn watchdog_backs_off_after_repeated_restarts() {
+        assert_eq!(watchdog_delay(0), WATCHDOG_INTERVAL);
+        assert_eq!(watchdog_delay(2), WATCHDOG_INTERVAL * 4);
+        assert_eq!(watchdog_delay(30), WATCHDOG_MAX_BACKOFF);
+    }
+
+    #[test]
+    fn restarts_are_forgotten_only_after_a_stable_period() {
+        let start = Instant::now();
+        let mut backoff = RestartBackoff::default();
+        backoff.record_restart(start);
+        backoff.record_restart(start);
+
+        backoff.record_running(start + WATCHDOG_INTERVAL);
+        assert_eq!(backoff.failures, 2);
+
+        backoff.record_running(start + WATCHDOG_STABLE_PERIOD);
+        assert_eq!(backoff.failures, 0);
+    }
+
+    #[test]
+    fn backends_for_the_same_launch_share_one_server() {
+        let root = temp_root("pool");
+        let pool = WhisperServerPool::default();
+        let build = |launch| {
+            WhisperServerTranscriber::new(
+                &pool,
+                launch,
+                Duration::from_secs(5),
+                0,
+                String::new(),
+                TranscriptionLanguage::Auto,
+            )
+            .expect("transcriber")
+        };
+
+        let primary = build(fake_launch(&root, 0));
+        let profile = build(fake_launch(&root, 0));
+        assert!(Arc::ptr_eq(&primary.process, &profile.process));
+
+        // Shutting down one user leaves the server running for the other.
+        assert!(primary.process.ensure_running().expect("start"));
+        Transcriber::shutdown(&profile);
+        assert_eq!(primary.process.exit_status(), None);
+        Transcriber::shutdown(&primary);
+        assert_eq!(
+            primary.process.exit_status().as_deref(),
+            Some("not running")
+        );
+
+        let mut relaunched = fake_launch(&root, 0);
+        relaunched.threads = 2;
+        let other = build(relaunched);
+        assert!(!Arc::ptr_eq(&primary.process, &other.process));
+
+        drop((primary, profile, other));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn crashed_server_is_restarted() {
+        let root = temp_root("restart");
+        let process = ServerProcess::new(fake_launch(&root, 0));
+        process.healthy.store(true, Ordering::SeqCst);
+
+        assert!(process.ensure_running().expect("first start"));
+        assert!(!process.ensure_running().expect("still running"));
+
+        {
+            let mut guard = process.child.lock().expect("lock");
+            let child = guard.as_mut().expect("child");
+            child.kill().expect("kill");
+            child.wait().expect("wait");
+        }
+
+        assert!(process.ensure_running().expect("restart"));
+        assert!(!process.healthy.load(Ordering::SeqCst));
+
+        drop(process);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "hyprwhspr-rs-whisper-server-{name}-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&root).expect("create temp dir");
+        root
+    }
+
+    /// A stand-in for whisper-server that just stays alive until killed.
+    fn fake_launch(root: &std::path::Path, port: u16) -> WhisperServerLaunch {
+        let binary = root.join("whisper-server");
+        std::fs::write(&binary, b"#!/bin/sh\nexec sleep 30\n").expect("write fake server");
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod fake server");
+
+        WhisperServerLaunch {
+            binary,
+            model_path: root.join("ggml-base.bin"),
+            host: "127.0.0.1".to_string(),
+            port,
+            threads: 1,
+            gpu_layers: 0,
+            no_speech_threshold: 0.6,
+            vad: WhisperVadOptions::disabled(),
+            startup_timeout: Duration::from_secs(5),
+        }
+    }
+
+    async fn serve_stub_request(mut stream: TcpStream, inference_body: &'static [u8]) {
+        let Ok((headers, _)) = read_request(&mut stream).await else {
+            return;
+        };
+        let body: &[u8] = if headers.starts_with("GET /health") {
+            br#"{"status":"ok"}"#
+        } else if headers.starts_with("POST /inference") {
+            inference_body
+        } else {
+            br#"{"error":"unexpected request"}"#
+        };
+        write_response(&mut stream, "200 OK", body)
+            .await
+            .expect("write response");
+    }
+}
@@ -98,3 +98,39 @@ fn paste_hints_shift_insert_deserializes() {
         vec!["dev.zed.Zed".to_string()]
     );
 }
+
+#[test]
+fn normalization_steps_reject_unknown_or_repeated_names() {
+    let json = r#"{"normalization":{"steps":["collapse_spaces","word_overrides"]}}"#;
+    let config: Config = serde_json::from_str(json).expect("deserialize config");
+    assert_eq!(
+        config.normalization.steps,
+        Some(vec![
+            "collapse_spaces".to_string(),
+            "word_overrides".to_string()
+        ])
+    );
+
+    let unknown = r#"{"normalization":{"steps":["collapse_spaces","shout"]}}"#;
+    let err = serde_json::from_str::<Config>(unknown).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unknown normalization step 'shout'"));
+
+    let repeated = r#"{"normalization":{"steps":["trim_whitespace","trim_whitespace"]}}"#;
+    assert!(serde_json::from_str::<Config>(repeated).is_err());
+}
+
+#[test]
+fn schema_lists_normalization_step_names() {
+    let schema: serde_json::Value =
+        serde_json::from_str(include_str!("../config/schema.json")).expect("parse schema");
+    let names = schema
+        .pointer("/$defs/NormalizationConfig/properties/steps/items/enum")
+        .expect("normalization step enum");
+
+    assert_eq!(
+        names,
+        &serde_json::json!(hyprwhspr_rs::text::normalize_step_names())
+    );
+}
@@ -2,7 +2,7 @@ use hyprwhspr_rs::config::{
     Config, CustomProviderConfig, CustomProviderKind, SecretSource, SubscriptionAuthSource,
     TranscriptionProvider, ValueSource,
 };
-use hyprwhspr_rs::transcription::CustomOpenAiTranscriber;
+use hyprwhspr_rs::transcription::{CustomOpenAiTranscriber, TranscriptionLanguage};
 use std::time::Duration;
 
 #[test]
@@ -91,6 +91,54 @@ fn custom_provider_config_deserializes_requested_shape() {
     assert_eq!(custom.prompt, "Transcribe technical notes.");
 }
 
+#[test]
+fn raw_and_json_provider_kinds_deserialize() {
+    let json = r#"{
+        "transcription": {
+            "custom": {
+                "deepgram": {
+                    "kind": "raw_audio",
+                    "endpoint": "https://api.deepgram.com/v1/listen",
+                    "model": "nova-3",
+                    "content_type": "audio/wav",
+                    "auth_scheme": "Token",
+                    "api_key": { "env": "DEEPGRAM_API_KEY" },
+                    "query": { "smart_format": "true" },
+                    "transcript_pointer": "/results/channels/0/alternatives/0/transcript"
+                },
+                "base64_server": {
+                    "kind": "json_base64",
+                    "endpoint": "http://localhost:9000/transcribe",
+                    "audio_field": "audio_data",
+                    "transcript_pointer": "/result/text",
+                    "language_pointer": "/result/language"
+                }
+            }
+        }
+    }"#;
+
+    let config: Config = serde_json::from_str(json).expect("deserialize config");
+    let deepgram = &config.transcription.custom["deepgram"];
+    assert_eq!(deepgram.kind, CustomProviderKind::RawAudio);
+    assert_eq!(deepgram.content_type.as_deref(), Some("audio/wav"));
+    assert_eq!(deepgram.auth_scheme, "Token");
+    assert_eq!(
+        deepgram.query.get("smart_format").map(String::as_str),
+        Some("true")
+    );
+    assert_eq!(
+        deepgram.transcript_pointer,
+        "/results/channels/0/alternatives/0/transcript"
+    );
+    assert_eq!(deepgram.language_pointer, "/language");
+
+    let base64_server = &config.transcription.custom["base64_server"];
+    assert_eq!(base64_server.kind, CustomProviderKind::JsonBase64);
+    assert_eq!(base64_server.audio_field, "audio_data");
+    assert_eq!(base64_server.auth_scheme, "Bearer");
+    assert_eq!(base64_server.language_pointer, "/result/language");
+}
+
 #[test]
 fn custom_provider_round_trips() {
     let mut config = Config::default();
@@ -116,6 +164,8 @@ fn custom_provider_round_trips() {
             headers: [("x-provider".to_string(), "whisper.cpp".to_string())].into(),
             body: [("response_format".to_string(), "json".to_string())].into(),
             prompt: "Transcribe technical notes.".to_string(),
+            language: Some("de".to_string()),
+            ..Default::default()
         },
     );
 
@@ -139,6 +189,8 @@ fn custom_provider_allows_absolute_endpoint_without_base_url() {
         headers: Default::default(),
         body: Default::default(),
         prompt: String::new(),
+        language: None,
+        ..Default::default()
     };
 
     CustomOpenAiTranscriber::new(
@@ -147,6 +199,7 @@ fn custom_provider_allows_absolute_endpoint_without_base_url() {
         Duration::from_secs(5),
         0,
         String::new(),
+        TranscriptionLanguage::Auto,
     )
     .expect("absolute endpoint should not require base_url");
 }
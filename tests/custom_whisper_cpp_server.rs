@@ -13,7 +13,7 @@ use hyprwhspr_rs::config::{
     Config, CustomProviderConfig, CustomProviderKind, SubscriptionAuthSource,
     TranscriptionProvider, ValueSource,
 };
-use hyprwhspr_rs::transcription::CustomOpenAiTranscriber;
+use hyprwhspr_rs::transcription::{CustomOpenAiTranscriber, Transcriber, TranscriptionLanguage};
 use std::process::{Child, Command, Stdio};
 use std::time::{Duration, Instant};
 
@@ -49,6 +49,8 @@ async fn custom_provider_transcribes_against_whisper_cpp_server() {
             headers: Default::default(),
             body: Default::default(),
             prompt: String::new(),
+            language: None,
+            ..Default::default()
         },
     );
 
@@ -63,6 +65,7 @@ async fn custom_provider_transcribes_against_whisper_cpp_server() {
         Duration::from_secs(30),
         0,
         String::new(),
+        TranscriptionLanguage::Auto,
     )
     .expect("custom transcriber");
 
@@ -1,7 +1,12 @@
+use hyprwhspr_rs::config::SnippetConfig;
+use hyprwhspr_rs::text::NormalizeTextService;
+use hyprwhspr_rs::Config;
+
 const SYSTEMD_UNIT: &str = include_str!("../config/systemd/hyprwhspr-rs.service");
 const WAYBAR_MODULE: &str = include_str!("../config/waybar/hyprwhspr-module.jsonc");
 const ELEPHANT_MENU: &str = include_str!("../config/elephant/hyprwhspr.lua");
 const WAYBAR_INSTALL_SCRIPT: &str = include_str!("../scripts/install-waybar.sh");
+const STANDUP_SNIPPET: &str = include_str!("../config/snippets/standup.md");
 
 #[test]
 fn waybar_module_uses_xdg_cache_home() {
@@ -63,3 +68,40 @@ fn systemd_unit_uses_path_execstart_and_cache_directory() {
         "systemd unit should not shell out for cache dir creation"
     );
 }
+
+#[test]
+fn standup_snippet_only_uses_known_placeholders() {
+    let mut rest = STANDUP_SNIPPET;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}').expect("unterminated placeholder") + start;
+        let placeholder = &rest[start..=end];
+        assert!(
+            ["{date}", "{time}", "{clipboard}"].contains(&placeholder),
+            "standup snippet uses unknown placeholder {placeholder}"
+        );
+        rest = &rest[end + 1..];
+    }
+}
+
+#[test]
+fn snippet_files_expand_verbatim() {
+    let mut config = Config::default();
+    config.snippets.insert(
+        "insert standup template".to_string(),
+        SnippetConfig::File {
+            file: concat!(env!("CARGO_MANIFEST_DIR"), "/config/snippets/standup.md").to_string(),
+        },
+    );
+    let normalizer = NormalizeTextService::from_config(&config).expect("valid config");
+
+    let expanded = normalizer.expand_snippets(&normalizer.normalize("Insert standup template."));
+    assert!(
+        !expanded.contains("{date}"),
+        "date placeholder should render"
+    );
+    let template_lines = STANDUP_SNIPPET.trim_end().lines().skip(1);
+    assert!(
+        expanded.lines().skip(1).eq(template_lines),
+        "snippet body should be injected untouched: {expanded:?}"
+    );
+}
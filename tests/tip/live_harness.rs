@@ -5,7 +5,7 @@ use anyhow::{Context, Result, bail};
 use hyprwhspr_rs::audio::FastVad;
 use hyprwhspr_rs::config::{Config, ConfigManager, TranscriptionProvider};
 use hyprwhspr_rs::text::NormalizeTextService;
-use hyprwhspr_rs::transcription::TranscriptionBackend;
+use hyprwhspr_rs::transcription::TranscriberRegistry;
 use hyprwhspr_rs::whisper::WhisperVadOptions;
 use std::collections::HashMap;
 use std::env;
@@ -122,7 +122,8 @@ async fn run_live_case(
 
     let vad_options = WhisperVadOptions::disabled();
     let backend = timeline.measure("backend.init", None, None, || {
-        let backend = TranscriptionBackend::build(&config_manager, &config, vad_options)
+        let backend = TranscriberRegistry::default()
+            .build(&config_manager, &config, vad_options)
             .with_context(|| format!("build {} backend", provider.id))?;
         backend
             .initialize()